@@ -0,0 +1,503 @@
+// src-tauri/src/abs.rs
+// AudiobookShelf API client. One place for auth, retries and error mapping
+// so the Whisper pipeline and push/import commands talk to ABS the same way.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+use crate::config::{get_secret, AppConfig, Secret};
+use crate::error::{AppError, ErrorKind};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const PAGE_SIZE: u32 = 100;
+/// Longest wait honoured from a Retry-After header.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+// ---- API types (only the fields we use; ABS sends many more) ----
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Library {
+    pub id: String,
+    pub name: String,
+    pub media_type: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LibraryItem {
+    pub id: String,
+    pub library_id: String,
+    pub path: Option<String>,
+    pub media_type: String,
+    pub media: BookMedia,
+    pub added_at: Option<i64>,
+    pub updated_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BookMedia {
+    pub metadata: BookMetadata,
+    pub audio_files: Vec<AudioFile>,
+    pub chapters: Vec<Chapter>,
+    pub tags: Vec<String>,
+    pub duration: Option<f64>,
+    pub cover_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BookMetadata {
+    pub title: Option<String>,
+    pub subtitle: Option<String>,
+    pub authors: Vec<AuthorRef>,
+    pub narrators: Vec<String>,
+    pub series: Vec<SeriesRef>,
+    pub genres: Vec<String>,
+    pub published_year: Option<String>,
+    pub publisher: Option<String>,
+    pub description: Option<String>,
+    pub isbn: Option<String>,
+    pub asin: Option<String>,
+    pub language: Option<String>,
+    pub explicit: bool,
+    pub abridged: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuthorRef {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeriesRef {
+    pub id: String,
+    pub name: String,
+    pub sequence: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AudioFile {
+    pub index: Option<u32>,
+    pub ino: String,
+    pub duration: Option<f64>,
+    pub metadata: FileMetadata,
+    /// Embedded tags as ABS read them (tagAlbum, tagArtist, ...).
+    pub meta_tags: serde_json::Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FileMetadata {
+    pub filename: String,
+    pub path: String,
+    pub size: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Chapter {
+    pub id: u32,
+    pub start: f64,
+    pub end: f64,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Author {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub num_books: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Series {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// One page of a paginated list.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Page<T> {
+    pub results: Vec<T>,
+    pub total: u64,
+    pub limit: u64,
+    pub page: u64,
+}
+
+/// Response to PATCH media and match: whether ABS changed anything.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UpdateResult {
+    pub updated: bool,
+    pub library_item: Option<LibraryItem>,
+}
+
+// ---- Client ----
+
+/// How often and how patiently to retry 5xx, 429 and connection failures.
+/// 4xx errors are never retried.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy { max_attempts: 3, base_delay: Duration::from_millis(500) }
+    }
+}
+
+#[derive(Clone)]
+pub struct AbsClient {
+    base_url: String,
+    token: String,
+    http: reqwest::Client,
+    retry: RetryPolicy,
+}
+
+impl AbsClient {
+    pub fn new(base_url: &str, token: &str) -> Result<Self, AppError> {
+        let base_url = base_url.trim().trim_end_matches('/');
+        let token = token.trim();
+        if base_url.is_empty() || token.is_empty() {
+            return Err(AppError::new(ErrorKind::AbsNotConfigured, "AudiobookShelf URL or API token is not set")
+                .with_hint("Connect to AudiobookShelf in Settings"));
+        }
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| AppError::request_failed("ABS", &e))?;
+        Ok(AbsClient { base_url: base_url.to_string(), token: token.to_string(), http, retry: RetryPolicy::default() })
+    }
+
+    /// Client for the URL and token saved in Settings.
+    pub fn from_config() -> Result<Self, AppError> {
+        let config = AppConfig::load_or_default();
+        let token = get_secret(Secret::AbsApiToken).unwrap_or_default();
+        Self::new(config.abs_url().unwrap_or(""), &token)
+    }
+
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Bearer token, for callers (FFmpeg) that stream file URLs themselves.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Direct URL of one audio file, streamable with the bearer token.
+    pub fn file_url(&self, item_id: &str, ino: &str) -> String {
+        format!("{}/api/items/{}/file/{}", self.base_url, item_id, ino)
+    }
+
+    // ---- Reads ----
+
+    pub async fn libraries(&self) -> Result<Vec<Library>, AppError> {
+        #[derive(Deserialize)]
+        struct Libraries {
+            libraries: Vec<Library>,
+        }
+        Ok(self.get_json::<Libraries>("/api/libraries").await?.libraries)
+    }
+
+    pub async fn library_items(&self, library_id: &str, page: u32, limit: u32) -> Result<Page<LibraryItem>, AppError> {
+        self.get_json(&format!("/api/libraries/{}/items?limit={}&page={}&expanded=1", library_id, limit, page)).await
+    }
+
+    /// Every item in a library, fetched a page at a time.
+    pub async fn all_library_items(&self, library_id: &str) -> Result<Vec<LibraryItem>, AppError> {
+        let mut items = Vec::new();
+        for page in 0.. {
+            let batch = self.library_items(library_id, page, PAGE_SIZE).await?;
+            let done = batch.results.is_empty() || items.len() as u64 + batch.results.len() as u64 >= batch.total;
+            items.extend(batch.results);
+            if done {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    pub async fn item(&self, item_id: &str) -> Result<LibraryItem, AppError> {
+        self.get_json(&format!("/api/items/{}?expanded=1", item_id)).await
+    }
+
+    pub async fn item_files(&self, item_id: &str) -> Result<Vec<AudioFile>, AppError> {
+        Ok(self.item(item_id).await?.media.audio_files)
+    }
+
+    /// URL of the item's first audio file.
+    pub async fn first_audio_file_url(&self, item_id: &str) -> Result<String, AppError> {
+        let files = self.item_files(item_id).await?;
+        let first = files
+            .first()
+            .ok_or_else(|| AppError::new(ErrorKind::AbsNotFound, "AudiobookShelf item has no audio files"))?;
+        Ok(self.file_url(item_id, &first.ino))
+    }
+
+    pub async fn chapters(&self, item_id: &str) -> Result<Vec<Chapter>, AppError> {
+        Ok(self.item(item_id).await?.media.chapters)
+    }
+
+    pub async fn authors(&self, library_id: &str) -> Result<Vec<Author>, AppError> {
+        #[derive(Deserialize)]
+        struct Authors {
+            authors: Vec<Author>,
+        }
+        Ok(self.get_json::<Authors>(&format!("/api/libraries/{}/authors", library_id)).await?.authors)
+    }
+
+    pub async fn author(&self, author_id: &str) -> Result<Author, AppError> {
+        self.get_json(&format!("/api/authors/{}", author_id)).await
+    }
+
+    pub async fn series(&self, library_id: &str, page: u32, limit: u32) -> Result<Page<Series>, AppError> {
+        self.get_json(&format!("/api/libraries/{}/series?limit={}&page={}", library_id, limit, page)).await
+    }
+
+    // ---- Writes ----
+
+    /// PATCH the item's media. `patch` is the ABS body, e.g.
+    /// `{ "metadata": { "title": ... }, "tags": [...] }`; only the fields
+    /// present are changed.
+    pub async fn update_media(&self, item_id: &str, patch: &Value) -> Result<UpdateResult, AppError> {
+        let url = self.url(&format!("/api/items/{}/media", item_id));
+        let response = self.send(|| self.http.patch(&url).json(patch)).await?;
+        decode(response).await
+    }
+
+    pub async fn update_chapters(&self, item_id: &str, chapters: &[Chapter]) -> Result<(), AppError> {
+        let url = self.url(&format!("/api/items/{}/chapters", item_id));
+        let body = serde_json::json!({ "chapters": chapters });
+        self.send(|| self.http.post(&url).json(&body)).await?;
+        Ok(())
+    }
+
+    /// Upload image bytes as the item's cover.
+    pub async fn upload_cover(&self, item_id: &str, image: Vec<u8>, filename: &str) -> Result<(), AppError> {
+        let url = self.url(&format!("/api/items/{}/cover", item_id));
+        self.send(|| {
+            let part = reqwest::multipart::Part::bytes(image.clone()).file_name(filename.to_string());
+            self.http.post(&url).multipart(reqwest::multipart::Form::new().part("cover", part))
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Have ABS download the cover from `cover_url` itself.
+    pub async fn set_cover_url(&self, item_id: &str, cover_url: &str) -> Result<(), AppError> {
+        let url = self.url(&format!("/api/items/{}/cover", item_id));
+        let body = serde_json::json!({ "url": cover_url });
+        self.send(|| self.http.post(&url).json(&body)).await?;
+        Ok(())
+    }
+
+    /// Run ABS's own metadata match against `provider` ("audible", "google", ...).
+    pub async fn match_item(&self, item_id: &str, provider: &str) -> Result<UpdateResult, AppError> {
+        let url = self.url(&format!("/api/items/{}/match", item_id));
+        let body = serde_json::json!({ "provider": provider });
+        let response = self.send(|| self.http.post(&url).json(&body)).await?;
+        decode(response).await
+    }
+
+    pub async fn scan_library(&self, library_id: &str) -> Result<(), AppError> {
+        let url = self.url(&format!("/api/libraries/{}/scan", library_id));
+        self.send(|| self.http.post(&url)).await?;
+        Ok(())
+    }
+
+    pub async fn scan_item(&self, item_id: &str) -> Result<(), AppError> {
+        let url = self.url(&format!("/api/items/{}/scan", item_id));
+        self.send(|| self.http.post(&url)).await?;
+        Ok(())
+    }
+
+    // ---- Transport ----
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T, AppError> {
+        let url = self.url(path);
+        let response = self.send(|| self.http.get(&url)).await?;
+        decode(response).await
+    }
+
+    /// Send with auth, retrying transient failures. `build` is called once
+    /// per attempt because multipart bodies can't be cloned.
+    async fn send(&self, build: impl Fn() -> reqwest::RequestBuilder) -> Result<reqwest::Response, AppError> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let last = attempt >= self.retry.max_attempts.max(1);
+            let delay = self.retry.base_delay * 2u32.pow(attempt - 1);
+
+            let response = match build().bearer_auth(&self.token).send().await {
+                Ok(response) => response,
+                Err(e) if !last => {
+                    eprintln!("   ABS request failed (attempt {}), retrying: {}", attempt, e);
+                    tokio::time::sleep(delay).await;
+                    continue;
+                }
+                Err(e) => return Err(AppError::request_failed("ABS", &e)),
+            };
+
+            let status = response.status();
+            if status.is_success() {
+                return Ok(response);
+            }
+            let retryable = status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+            if retryable && !last {
+                let wait = retry_after(&response).unwrap_or(delay);
+                tokio::time::sleep(wait).await;
+                continue;
+            }
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::http_status("ABS", status.as_u16(), &body));
+        }
+    }
+}
+
+fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    let secs: u64 = response.headers().get(reqwest::header::RETRY_AFTER)?.to_str().ok()?.trim().parse().ok()?;
+    Some(Duration::from_secs(secs).min(MAX_RETRY_AFTER))
+}
+
+async fn decode<T: DeserializeOwned>(response: reqwest::Response) -> Result<T, AppError> {
+    let bytes = response.bytes().await.map_err(|e| AppError::request_failed("ABS", &e))?;
+    serde_json::from_slice(&bytes).map_err(|e| {
+        AppError::new(ErrorKind::Parse, "Unexpected response from AudiobookShelf").with_detail(e.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abs_mock::{MockAbs, LIBRARY_ID};
+
+    #[tokio::test]
+    async fn fetches_expanded_item_with_bearer_auth() {
+        let mock = MockAbs::start().await;
+        let client = AbsClient::new(&format!("{}/", mock.url()), crate::abs_mock::TOKEN).unwrap();
+
+        let item = client.item("li_dune").await.unwrap();
+        assert_eq!(item.media.metadata.title.as_deref(), Some("Dune"));
+        assert_eq!(item.media.metadata.authors[0].name, "Frank Herbert");
+        assert_eq!(item.media.metadata.series[0].sequence.as_deref(), Some("1"));
+        assert_eq!(item.media.chapters[1].title, "Book One: Dune");
+        assert_eq!(
+            client.first_audio_file_url("li_dune").await.unwrap(),
+            format!("{}/api/items/li_dune/file/1001", mock.url())
+        );
+
+        let requests = mock.requests();
+        assert_eq!(requests[0].path, "/api/items/li_dune?expanded=1");
+        assert_eq!(requests[0].headers["authorization"], "Bearer mock-abs-token");
+    }
+
+    #[tokio::test]
+    async fn pages_through_library_items() {
+        let mock = MockAbs::start().await;
+        let client = mock.client();
+
+        let page = client.library_items(LIBRARY_ID, 1, 2).await.unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(page.results.iter().map(|i| i.id.as_str()).collect::<Vec<_>>(), ["li_empty"]);
+
+        let all = client.all_library_items(LIBRARY_ID).await.unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(client.authors(LIBRARY_ID).await.unwrap()[1].name, "J.R.R. Tolkien");
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors_but_not_auth_failures() {
+        let mock = MockAbs::start().await;
+        let client = mock.client();
+
+        mock.fail_next(&[503]);
+        assert_eq!(client.libraries().await.unwrap()[0].name, "Audiobooks");
+        assert_eq!(mock.requests().len(), 2);
+
+        mock.reject_auth(true);
+        let err = client.author("au_herbert").await.unwrap_err();
+        assert_eq!(err.kind, ErrorKind::AbsUnauthorized);
+        assert_eq!(mock.requests().len(), 3);
+
+        mock.reject_auth(false);
+        let err = client.item("li_missing").await.unwrap_err();
+        assert_eq!(err.kind, ErrorKind::AbsNotFound);
+    }
+
+    #[tokio::test]
+    async fn slow_server_times_out_as_unreachable() {
+        let mock = MockAbs::start().await;
+        mock.delay(Some(Duration::from_millis(300)));
+        let mut client = mock.client().with_retry(RetryPolicy { max_attempts: 1, base_delay: Duration::ZERO });
+        client.http = reqwest::Client::builder().timeout(Duration::from_millis(50)).build().unwrap();
+
+        let err = client.libraries().await.unwrap_err();
+        assert_eq!(err.kind, ErrorKind::AbsUnreachable);
+        assert!(err.is_transient());
+    }
+
+    #[tokio::test]
+    async fn patches_media_and_chapters() {
+        let mock = MockAbs::start().await;
+        let client = mock.client();
+
+        let patch = serde_json::json!({ "metadata": { "title": "Dune Messiah" }, "tags": ["Sequel"] });
+        assert!(client.update_media("li_dune", &patch).await.unwrap().updated);
+        let request = mock.requests().pop().unwrap();
+        assert_eq!((request.method.as_str(), request.path.as_str()), ("PATCH", "/api/items/li_dune/media"));
+        assert_eq!(serde_json::from_str::<Value>(&request.body).unwrap(), patch);
+
+        let chapters = vec![Chapter { id: 0, start: 0.0, end: 4.0, title: "Whole Book".to_string() }];
+        client.update_chapters("li_dune", &chapters).await.unwrap();
+        let item = client.item("li_dune").await.unwrap();
+        assert_eq!(item.media.metadata.title.as_deref(), Some("Dune Messiah"));
+        assert_eq!(item.media.tags, ["Sequel"]);
+        assert_eq!(item.media.chapters, chapters);
+    }
+
+    #[tokio::test]
+    async fn streams_audio_files_with_ranges() {
+        let mock = MockAbs::start().await;
+        let client = mock.client();
+        let url = client.file_url("li_dune", "1001");
+
+        let partial = client.http.get(&url).bearer_auth(client.token()).header("Range", "bytes=0-11").send().await.unwrap();
+        assert_eq!(partial.status(), 206);
+        assert_eq!(&partial.bytes().await.unwrap()[..], &mock.audio()[..12]);
+        assert_eq!(&mock.audio()[..4], b"RIFF");
+
+        let missing = client.http.get(client.file_url("li_dune", "9999")).bearer_auth(client.token()).send().await.unwrap();
+        assert_eq!(missing.status(), 404);
+    }
+
+    #[test]
+    fn missing_url_or_token_is_not_configured() {
+        assert_eq!(AbsClient::new("", "tok").err().unwrap().kind, ErrorKind::AbsNotConfigured);
+        assert_eq!(AbsClient::new("http://nas", " ").err().unwrap().kind, ErrorKind::AbsNotConfigured);
+    }
+}
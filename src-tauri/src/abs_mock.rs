@@ -0,0 +1,436 @@
+// src-tauri/src/abs_mock.rs
+// In-process AudiobookShelf stand-in for tests. Serves the endpoints the app
+// uses from fixture items, streams a generated WAV for audio files (with
+// Range support, as FFmpeg seeks), applies PATCHes to its own state, and can
+// be told to reject the token, answer slowly or fail the next requests.
+// Also answers OpenAI-style `/v1/audio/transcriptions` with a fixed
+// transcript, so cloud transcription can be tested against it.
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::abs::{AbsClient, RetryPolicy};
+
+pub const TOKEN: &str = "mock-abs-token";
+pub const LIBRARY_ID: &str = "lib_books";
+/// Seconds of audio in each fixture file.
+pub const AUDIO_SECS: u32 = 4;
+/// What the transcription endpoint hears in any upload.
+pub const TRANSCRIPT: &str = "This is Audible. Dune, by Frank Herbert. Narrated by Scott Brick.";
+
+#[derive(Debug, Clone)]
+pub struct Recorded {
+    pub method: String,
+    /// Path and query, e.g. `/api/items/li_dune?expanded=1`.
+    pub path: String,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+#[derive(Default)]
+struct Faults {
+    reject_auth: bool,
+    delay: Option<Duration>,
+    fail_next: Vec<u16>,
+}
+
+#[derive(Default)]
+struct State {
+    items: Vec<Value>,
+    authors: Vec<Value>,
+    requests: Vec<Recorded>,
+    faults: Faults,
+}
+
+pub struct MockAbs {
+    url: String,
+    state: Arc<Mutex<State>>,
+    audio: Arc<Vec<u8>>,
+}
+
+impl MockAbs {
+    /// Start a server on a free local port with the fixture library.
+    pub async fn start() -> MockAbs {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind mock ABS");
+        let url = format!("http://{}", listener.local_addr().expect("mock ABS address"));
+        let state = Arc::new(Mutex::new(State { items: fixture_items(), authors: fixture_authors(), ..Default::default() }));
+        let audio = Arc::new(wav_tone(AUDIO_SECS));
+
+        let (server_state, server_audio) = (Arc::clone(&state), Arc::clone(&audio));
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let (state, audio) = (Arc::clone(&server_state), Arc::clone(&server_audio));
+                tokio::spawn(async move { serve(socket, state, audio).await });
+            }
+        });
+        MockAbs { url, state, audio }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Client for this server, retrying quickly so tests stay fast.
+    pub fn client(&self) -> AbsClient {
+        AbsClient::new(&self.url, TOKEN)
+            .expect("mock ABS client")
+            .with_retry(RetryPolicy { max_attempts: 3, base_delay: Duration::from_millis(1) })
+    }
+
+    /// Bytes served for every audio file.
+    pub fn audio(&self) -> &[u8] {
+        &self.audio
+    }
+
+    /// Answer every request with 401, as if the token were revoked.
+    pub fn reject_auth(&self, reject: bool) {
+        self.lock().faults.reject_auth = reject;
+    }
+
+    /// Wait this long before answering each request.
+    pub fn delay(&self, delay: Option<Duration>) {
+        self.lock().faults.delay = delay;
+    }
+
+    /// Answer the next requests with these statuses, one each, in order.
+    pub fn fail_next(&self, statuses: &[u16]) {
+        self.lock().faults.fail_next.extend_from_slice(statuses);
+    }
+
+    pub fn requests(&self) -> Vec<Recorded> {
+        self.lock().requests.clone()
+    }
+
+    /// An item as the server currently holds it, PATCHes included.
+    pub fn item(&self, id: &str) -> Option<Value> {
+        self.lock().items.iter().find(|i| i["id"] == id).cloned()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// ---- Fixtures ----
+
+fn fixture_items() -> Vec<Value> {
+    let file = |ino: &str, name: &str| {
+        json!({
+            "index": 1, "ino": ino, "duration": AUDIO_SECS,
+            "metadata": { "filename": name, "path": format!("/audiobooks/{}", name), "size": 0 },
+            "metaTags": {},
+        })
+    };
+    vec![
+        json!({
+            "id": "li_dune", "libraryId": LIBRARY_ID, "mediaType": "book", "path": "/audiobooks/Dune",
+            "media": {
+                "metadata": {
+                    "title": "Dune", "subtitle": null,
+                    "authors": [{ "id": "au_herbert", "name": "Frank Herbert" }],
+                    "narrators": ["Scott Brick"],
+                    "series": [{ "id": "se_dune", "name": "Dune", "sequence": "1" }],
+                    "genres": ["Science Fiction"], "publishedYear": "1965", "publisher": "Macmillan Audio",
+                    "description": null, "isbn": null, "asin": "B002V1OF70", "language": "English",
+                    "explicit": false, "abridged": false,
+                },
+                "audioFiles": [file("1001", "Dune.m4b")],
+                "chapters": [
+                    { "id": 0, "start": 0.0, "end": 2.0, "title": "Opening Credits" },
+                    { "id": 1, "start": 2.0, "end": AUDIO_SECS, "title": "Book One: Dune" },
+                ],
+                "tags": ["Classic"], "duration": AUDIO_SECS,
+            },
+        }),
+        json!({
+            "id": "li_hobbit", "libraryId": LIBRARY_ID, "mediaType": "book", "path": "/audiobooks/The Hobbit",
+            "media": {
+                "metadata": {
+                    "title": "The Hobbit", "authors": [{ "id": "au_tolkien", "name": "J.R.R. Tolkien" }],
+                    "narrators": ["Andy Serkis"], "series": [], "genres": ["Fantasy"],
+                },
+                "audioFiles": [file("2001", "01 - Chapter 1.mp3"), file("2002", "02 - Chapter 2.mp3")],
+                "chapters": [], "tags": [], "duration": AUDIO_SECS * 2,
+            },
+        }),
+        json!({
+            "id": "li_empty", "libraryId": LIBRARY_ID, "mediaType": "book", "path": "/audiobooks/Missing Files",
+            "media": { "metadata": { "title": "Missing Files" }, "audioFiles": [], "chapters": [], "tags": [] },
+        }),
+    ]
+}
+
+fn fixture_authors() -> Vec<Value> {
+    vec![
+        json!({ "id": "au_herbert", "name": "Frank Herbert", "description": null, "numBooks": 1 }),
+        json!({ "id": "au_tolkien", "name": "J.R.R. Tolkien", "description": null, "numBooks": 1 }),
+    ]
+}
+
+/// 16 kHz mono 16-bit PCM WAV of a 440 Hz tone.
+fn wav_tone(secs: u32) -> Vec<u8> {
+    const RATE: u32 = 16_000;
+    let samples = RATE * secs;
+    let data_len = samples * 2;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&RATE.to_le_bytes());
+    wav.extend_from_slice(&(RATE * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for n in 0..samples {
+        let t = n as f64 / RATE as f64;
+        let sample = ((t * 440.0 * std::f64::consts::TAU).sin() * 8000.0) as i16;
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
+
+// ---- HTTP ----
+
+struct Response {
+    status: u16,
+    content_type: &'static str,
+    body: Vec<u8>,
+    headers: Vec<(&'static str, String)>,
+}
+
+impl Response {
+    fn json(status: u16, body: Value) -> Self {
+        Response { status, content_type: "application/json", body: body.to_string().into_bytes(), headers: Vec::new() }
+    }
+
+    fn text(status: u16, body: &str) -> Self {
+        Response { status, content_type: "text/plain", body: body.as_bytes().to_vec(), headers: Vec::new() }
+    }
+}
+
+/// Answer one request, then close the connection.
+async fn serve(mut socket: TcpStream, state: Arc<Mutex<State>>, audio: Arc<Vec<u8>>) {
+    let Some(request) = read_request(&mut socket).await else { return };
+
+    let (delay, fault) = {
+        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+        state.requests.push(request.clone());
+        let authorized = request.headers.get("authorization").is_some_and(|v| v == &format!("Bearer {}", TOKEN));
+        let fault = if state.faults.reject_auth || !authorized {
+            Some(401)
+        } else if !state.faults.fail_next.is_empty() {
+            Some(state.faults.fail_next.remove(0))
+        } else {
+            None
+        };
+        (state.faults.delay, fault)
+    };
+    if let Some(delay) = delay {
+        tokio::time::sleep(delay).await;
+    }
+
+    let response = match fault {
+        Some(401) => Response::text(401, "Unauthorized"),
+        Some(status) => Response::text(status, "Injected failure"),
+        None => route(&request, &state, &audio),
+    };
+    let status_text = match response.status {
+        200 => "OK",
+        206 => "Partial Content",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        416 => "Range Not Satisfiable",
+        _ => "Error",
+    };
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        status_text,
+        response.content_type,
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+    if socket.write_all(head.as_bytes()).await.is_ok() && request.method != "HEAD" {
+        let _ = socket.write_all(&response.body).await;
+    }
+    let _ = socket.shutdown().await;
+}
+
+async fn read_request(socket: &mut TcpStream) -> Option<Recorded> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let head_end = loop {
+        let n = socket.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(i) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break i + 4;
+        }
+    };
+    let head = String::from_utf8_lossy(&buf[..head_end]).to_string();
+    let mut lines = head.lines();
+    let mut first = lines.next()?.split_whitespace();
+    let (method, path) = (first.next()?.to_string(), first.next()?.to_string());
+    let headers: HashMap<String, String> = lines
+        .filter_map(|l| l.split_once(':'))
+        .map(|(k, v)| (k.trim().to_ascii_lowercase(), v.trim().to_string()))
+        .collect();
+
+    let length: usize = headers.get("content-length").and_then(|v| v.parse().ok()).unwrap_or(0);
+    while buf.len() < head_end + length {
+        let n = socket.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    let body = String::from_utf8_lossy(&buf[head_end..]).to_string();
+    Some(Recorded { method, path, headers, body })
+}
+
+fn route(request: &Recorded, state: &Mutex<State>, audio: &[u8]) -> Response {
+    let (path, query) = request.path.split_once('?').unwrap_or((&request.path, ""));
+    let param = |name: &str| {
+        query.split('&').find_map(|kv| kv.strip_prefix(name).and_then(|v| v.strip_prefix('='))).and_then(|v| v.parse::<usize>().ok())
+    };
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+    let not_found = || Response::text(404, "Not Found");
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["api", "libraries"]) => Response::json(
+            200,
+            json!({ "libraries": [{ "id": LIBRARY_ID, "name": "Audiobooks", "mediaType": "book" }] }),
+        ),
+        ("GET", ["api", "libraries", LIBRARY_ID, "items"]) => {
+            let limit = param("limit").unwrap_or(0);
+            let page = param("page").unwrap_or(0);
+            let results: Vec<&Value> = match limit {
+                0 => state.items.iter().collect(),
+                _ => state.items.iter().skip(page * limit).take(limit).collect(),
+            };
+            Response::json(200, json!({ "results": results, "total": state.items.len(), "limit": limit, "page": page }))
+        }
+        ("GET", ["api", "libraries", LIBRARY_ID, "authors"]) => Response::json(200, json!({ "authors": state.authors })),
+        ("GET", ["api", "authors", id]) => match state.authors.iter().find(|a| a["id"] == *id) {
+            Some(author) => Response::json(200, author.clone()),
+            None => not_found(),
+        },
+        ("GET", ["api", "items", id]) => match state.items.iter().find(|i| i["id"] == *id) {
+            Some(item) => Response::json(200, item.clone()),
+            None => not_found(),
+        },
+        ("GET" | "HEAD", ["api", "items", id, "file", ino]) => {
+            let exists = state
+                .items
+                .iter()
+                .find(|i| i["id"] == *id)
+                .and_then(|i| i["media"]["audioFiles"].as_array())
+                .is_some_and(|files| files.iter().any(|f| f["ino"] == *ino));
+            if exists { audio_response(request, audio) } else { not_found() }
+        }
+        ("PATCH", ["api", "items", id, "media"]) => {
+            let Ok(patch) = serde_json::from_str::<Value>(&request.body) else {
+                return Response::text(400, "Invalid JSON");
+            };
+            let Some(item) = state.items.iter_mut().find(|i| i["id"] == *id) else { return not_found() };
+            let mut updated = false;
+            if let Some(fields) = patch["metadata"].as_object() {
+                for (key, value) in fields {
+                    updated |= item["media"]["metadata"][key] != *value;
+                    item["media"]["metadata"][key] = value.clone();
+                }
+            }
+            if let Some(tags) = patch.get("tags") {
+                updated |= item["media"]["tags"] != *tags;
+                item["media"]["tags"] = tags.clone();
+            }
+            Response::json(200, json!({ "updated": updated, "libraryItem": item }))
+        }
+        ("POST", ["api", "items", id, "chapters"]) => {
+            let Ok(body) = serde_json::from_str::<Value>(&request.body) else {
+                return Response::text(400, "Invalid JSON");
+            };
+            let Some(item) = state.items.iter_mut().find(|i| i["id"] == *id) else { return not_found() };
+            item["media"]["chapters"] = body["chapters"].clone();
+            Response::json(200, json!({ "success": true, "updated": true }))
+        }
+        ("POST", ["api", "libraries", LIBRARY_ID, "scan"]) | ("POST", ["api", "items", _, "scan"]) => {
+            Response::text(200, "OK")
+        }
+        ("POST", ["v1", "audio", "transcriptions"]) => transcription_response(request),
+        _ => not_found(),
+    }
+}
+
+/// Reply in the `response_format` of the multipart form.
+fn transcription_response(request: &Recorded) -> Response {
+    let field = |name: &str| {
+        let marker = format!("name=\"{}\"\r\n\r\n", name);
+        let start = request.body.find(&marker)? + marker.len();
+        request.body[start..].split("\r\n").next().map(str::to_string)
+    };
+    if field("model").is_none() {
+        return Response::text(400, "Missing model");
+    }
+    match field("response_format").as_deref() {
+        Some("text") => Response::text(200, TRANSCRIPT),
+        Some("json") => Response::json(200, json!({ "text": TRANSCRIPT })),
+        _ => Response::json(
+            200,
+            json!({
+                "text": TRANSCRIPT,
+                "language": "english",
+                "segments": [
+                    { "start": 0.0, "end": 1.5, "text": " This is Audible." },
+                    { "start": 1.5, "end": 4.0, "text": " Dune, by Frank Herbert. Narrated by Scott Brick." },
+                ],
+            }),
+        ),
+    }
+}
+
+/// Whole file, or the requested `bytes=start-[end]` slice.
+fn audio_response(request: &Recorded, audio: &[u8]) -> Response {
+    let len = audio.len();
+    let range = request.headers.get("range").and_then(|r| r.strip_prefix("bytes=")).and_then(|r| {
+        let (start, end) = r.split_once('-')?;
+        let start: usize = start.parse().ok()?;
+        let end: usize = if end.is_empty() { len.saturating_sub(1) } else { end.parse().ok()? };
+        Some((start, end.min(len.saturating_sub(1))))
+    });
+    let mut response = match range {
+        Some((start, _)) if start >= len => {
+            let mut r = Response::text(416, "");
+            r.headers.push(("Content-Range", format!("bytes */{}", len)));
+            return r;
+        }
+        Some((start, end)) => {
+            let mut r = Response::json(206, Value::Null);
+            r.body = audio[start..=end].to_vec();
+            r.headers.push(("Content-Range", format!("bytes {}-{}/{}", start, end, len)));
+            r
+        }
+        None => {
+            let mut r = Response::json(200, Value::Null);
+            r.body = audio.to_vec();
+            r
+        }
+    };
+    response.content_type = "audio/wav";
+    response.headers.push(("Accept-Ranges", "bytes".to_string()));
+    response
+}
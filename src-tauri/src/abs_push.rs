@@ -0,0 +1,670 @@
+// src-tauri/src/abs_push.rs
+// Push edited metadata to AudiobookShelf gently. Each item is fetched first
+// and only fields that differ are PATCHed, under a requests-per-second budget
+// that slows down when the server starts failing (NAS-hosted ABS falls over
+// under a burst of writes). Pushes run as jobs, so a crash mid-push resumes
+// with the items not yet done.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{json, Map, Value};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::abs::{AbsClient, LibraryItem, RetryPolicy};
+use crate::error::{AppError, ErrorKind};
+use crate::library::{with_library, LibraryDb};
+use crate::names::{normalize_name, split_people, PersonRole};
+use crate::scanner::BookMetadata;
+use crate::series::{SeriesEntry, SeriesPosition};
+
+/// Attempts per request before the item is reported as failed.
+const MAX_ATTEMPTS: u32 = 4;
+/// Slowest the throttle backs off to.
+const MAX_INTERVAL: Duration = Duration::from_secs(10);
+
+fn default_requests_per_second() -> f64 { 2.0 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PushOptions {
+    /// Report what would change without writing anything.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Request budget while the server is healthy. GETs and PATCHes both count.
+    #[serde(default = "default_requests_per_second")]
+    pub requests_per_second: f64,
+}
+
+impl Default for PushOptions {
+    fn default() -> Self {
+        PushOptions { dry_run: false, requests_per_second: default_requests_per_second() }
+    }
+}
+
+/// One book to push: its ABS item id and the metadata edited in the app.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PushItem {
+    pub abs_id: String,
+    /// The library group the metadata came from, so the push can be recorded
+    /// in its history. ABS-only imports have none.
+    #[serde(default)]
+    pub group_id: Option<String>,
+    pub metadata: PushMetadata,
+}
+
+/// A book's metadata as the frontend holds it. Empty fields mean "leave
+/// ABS alone", never "clear it".
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PushMetadata {
+    pub title: Option<String>,
+    pub subtitle: Option<String>,
+    /// As edited in the UI; split with `names::split_people`.
+    pub author: Option<String>,
+    /// As edited in the UI; split with `names::split_people`.
+    pub narrator: Option<String>,
+    pub series: Option<String>,
+    #[serde(deserialize_with = "string_or_number", alias = "series_number")]
+    pub sequence: Option<String>,
+    /// Every series the book is in; takes precedence over `series`/`sequence`.
+    pub all_series: Vec<PushSeries>,
+    pub genres: Vec<String>,
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+    pub publisher: Option<String>,
+    #[serde(deserialize_with = "string_or_number", alias = "year")]
+    pub published_year: Option<String>,
+    pub language: Option<String>,
+    pub isbn: Option<String>,
+    pub asin: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PushSeries {
+    pub name: String,
+    #[serde(deserialize_with = "string_or_number")]
+    pub sequence: Option<String>,
+}
+
+/// Series positions and years arrive as strings or numbers depending on
+/// where they were edited.
+fn string_or_number<'de, D: Deserializer<'de>>(d: D) -> Result<Option<String>, D::Error> {
+    Ok(match Value::deserialize(d)? {
+        Value::String(s) => Some(s),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub from: Value,
+    pub to: Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PushStatus {
+    Updated,
+    Unchanged,
+    /// Dry run: these changes would have been written.
+    WouldUpdate,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PushOutcome {
+    pub abs_id: String,
+    pub title: Option<String>,
+    pub status: PushStatus,
+    pub changes: Vec<FieldChange>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<AppError>,
+}
+
+// ---- Diffing ----
+
+fn clean(s: &Option<String>) -> Option<&str> {
+    s.as_deref().map(str::trim).filter(|s| !s.is_empty())
+}
+
+/// The names in an edited author or narrator field, split the way the
+/// library splits them: inverted "Last, First" pairs stay one person and
+/// role suffixes are dropped.
+fn split_names(raw: &str, role: PersonRole) -> Vec<String> {
+    split_people(raw, role).into_iter().map(|p| p.name).collect()
+}
+
+fn same_set(a: &[String], b: &[String]) -> bool {
+    let norm = |v: &[String]| {
+        let mut v: Vec<String> = v.iter().map(|s| s.trim().to_lowercase()).collect();
+        v.sort();
+        v.dedup();
+        v
+    };
+    norm(a) == norm(b)
+}
+
+/// The PATCH body for fields that differ between ABS and `desired`, plus a
+/// readable list of the changes. The body is None when nothing differs.
+pub fn diff(current: &LibraryItem, desired: &PushMetadata) -> (Option<Value>, Vec<FieldChange>) {
+    let meta = &current.media.metadata;
+    let mut patch = Map::new();
+    let mut changes = Vec::new();
+    let mut change = |field: &str, from: Value, to: Value| {
+        changes.push(FieldChange { field: field.to_string(), from, to });
+    };
+
+    let scalars: [(&str, &Option<String>, &Option<String>); 8] = [
+        ("title", &desired.title, &meta.title),
+        ("subtitle", &desired.subtitle, &meta.subtitle),
+        ("description", &desired.description, &meta.description),
+        ("publisher", &desired.publisher, &meta.publisher),
+        ("publishedYear", &desired.published_year, &meta.published_year),
+        ("language", &desired.language, &meta.language),
+        ("isbn", &desired.isbn, &meta.isbn),
+        ("asin", &desired.asin, &meta.asin),
+    ];
+    for (field, want, have) in scalars {
+        if let Some(want) = clean(want) {
+            if clean(have) != Some(want) {
+                change(field, json!(clean(have)), json!(want));
+                patch.insert(field.to_string(), json!(want));
+            }
+        }
+    }
+
+    if let Some(author) = clean(&desired.author) {
+        let want = split_names(author, PersonRole::Author);
+        let have: Vec<String> = meta.authors.iter().map(|a| a.name.clone()).collect();
+        if want != have.iter().map(|n| normalize_name(n)).collect::<Vec<_>>() {
+            // Reuse ABS's ids for authors it already knows so it doesn't create duplicates
+            let authors: Vec<Value> = want
+                .iter()
+                .enumerate()
+                .map(|(i, name)| {
+                    let id = meta
+                        .authors
+                        .iter()
+                        .find(|a| a.name.eq_ignore_ascii_case(name))
+                        .map(|a| a.id.clone())
+                        .unwrap_or_else(|| format!("new-{}", i + 1));
+                    json!({ "id": id, "name": name })
+                })
+                .collect();
+            change("authors", json!(have), json!(want));
+            patch.insert("authors".to_string(), Value::Array(authors));
+        }
+    }
+
+    if let Some(narrator) = clean(&desired.narrator) {
+        let want = split_names(narrator, PersonRole::Narrator);
+        if want != meta.narrators.iter().map(|n| normalize_name(n)).collect::<Vec<_>>() {
+            change("narrators", json!(meta.narrators), json!(want));
+            patch.insert("narrators".to_string(), json!(want));
+        }
+    }
+
+    let want_series: Vec<(String, Option<String>)> = if !desired.all_series.is_empty() {
+        desired
+            .all_series
+            .iter()
+            .filter(|s| !s.name.trim().is_empty())
+            .map(|s| (s.name.trim().to_string(), clean(&s.sequence).map(str::to_string)))
+            .collect()
+    } else {
+        clean(&desired.series)
+            .map(|name| vec![(name.to_string(), clean(&desired.sequence).map(str::to_string))])
+            .unwrap_or_default()
+    };
+    let have_series: Vec<(String, Option<String>)> =
+        meta.series.iter().map(|s| (s.name.trim().to_string(), clean(&s.sequence).map(str::to_string))).collect();
+    if !want_series.is_empty() && want_series != have_series {
+        // No ids: ABS matches series by name
+        let to: Vec<Value> = want_series.iter().map(|(name, seq)| json!({ "name": name, "sequence": seq })).collect();
+        let from: Vec<Value> = have_series.iter().map(|(name, seq)| json!({ "name": name, "sequence": seq })).collect();
+        change("series", Value::Array(from), Value::Array(to.clone()));
+        patch.insert("series".to_string(), Value::Array(to));
+    }
+
+    if !desired.genres.is_empty() && !same_set(&desired.genres, &meta.genres) {
+        change("genres", json!(meta.genres), json!(desired.genres));
+        patch.insert("genres".to_string(), json!(desired.genres));
+    }
+
+    let mut body = Map::new();
+    if !patch.is_empty() {
+        body.insert("metadata".to_string(), Value::Object(patch));
+    }
+    // Tags live on the media, not in its metadata
+    if !desired.tags.is_empty() && !same_set(&desired.tags, &current.media.tags) {
+        change("tags", json!(current.media.tags), json!(desired.tags));
+        body.insert("tags".to_string(), json!(desired.tags));
+    }
+
+    ((!body.is_empty()).then_some(Value::Object(body)), changes)
+}
+
+// ---- Library ----
+
+impl PushMetadata {
+    /// Overlay the pushed fields onto library metadata. Empty fields were not
+    /// pushed, so they leave `metadata` alone, as they leave ABS alone.
+    pub fn apply_to(&self, metadata: &mut BookMetadata) {
+        let set = |field: &mut String, value: &Option<String>| {
+            if let Some(v) = clean(value) {
+                *field = v.to_string();
+            }
+        };
+        set(&mut metadata.title, &self.title);
+        set(&mut metadata.description, &self.description);
+        set(&mut metadata.year, &self.published_year);
+        if let Some(author) = clean(&self.author) {
+            metadata.author = author.to_string();
+            metadata.authors = split_people(author, PersonRole::Author);
+        }
+        if let Some(narrator) = clean(&self.narrator) {
+            metadata.narrator = narrator.to_string();
+            metadata.narrators = split_people(narrator, PersonRole::Narrator);
+        }
+        if !self.all_series.is_empty() {
+            metadata.series_list = self
+                .all_series
+                .iter()
+                .filter(|s| !s.name.trim().is_empty())
+                .map(|s| SeriesEntry {
+                    name: s.name.trim().to_string(),
+                    position: s.sequence.as_deref().and_then(SeriesPosition::parse),
+                })
+                .collect();
+        }
+        set(&mut metadata.series, &self.series);
+        set(&mut metadata.series_number, &self.sequence);
+        if !self.genres.is_empty() {
+            metadata.genres = self.genres.clone();
+        }
+        if !self.tags.is_empty() {
+            metadata.tags = self.tags.clone();
+        }
+    }
+}
+
+/// Record a finished push against the item's library group: the pushed
+/// metadata becomes a `push` version and the push status is updated. Dry
+/// runs wrote nothing, whatever their outcome, so they are skipped, as are
+/// items outside the library.
+pub fn record_in_library(item: &PushItem, outcome: &PushOutcome, options: &PushOptions) -> Result<(), String> {
+    if options.dry_run || item.group_id.is_none() {
+        return Ok(());
+    }
+    with_library(|db| record_outcome(db, item, outcome, options))
+}
+
+fn record_outcome(db: &mut LibraryDb, item: &PushItem, outcome: &PushOutcome, options: &PushOptions) -> Result<(), String> {
+    let Some(group_id) = item.group_id.as_deref().filter(|_| !options.dry_run) else {
+        return Ok(());
+    };
+    let Some(group) = db.get_group(group_id)? else {
+        return Ok(());
+    };
+    match &outcome.error {
+        Some(e) => db.record_push(group_id, None, Some(e.to_string())),
+        None => {
+            let mut pushed = group.metadata;
+            item.metadata.apply_to(&mut pushed);
+            db.record_push(group_id, Some(&pushed), None)
+        }
+    }
+}
+
+// ---- Throttling ----
+
+/// Spaces requests out to a budget. Transient failures double the gap, up
+/// to `MAX_INTERVAL`; each success eases it back toward the budget.
+pub struct Throttle {
+    base: Duration,
+    state: Mutex<(Duration, Option<Instant>)>,
+}
+
+impl Throttle {
+    pub fn new(requests_per_second: f64) -> Self {
+        let rps = if requests_per_second.is_finite() && requests_per_second > 0.0 { requests_per_second } else { 1.0 };
+        let base = Duration::from_secs_f64(1.0 / rps).min(MAX_INTERVAL);
+        Throttle { base, state: Mutex::new((base, None)) }
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.lock().0
+    }
+
+    /// Wait for the next request slot.
+    pub async fn wait(&self) {
+        let wake = {
+            let mut state = self.lock();
+            let now = Instant::now();
+            let slot = state.1.map_or(now, |last| (last + state.0).max(now));
+            state.1 = Some(slot);
+            slot
+        };
+        tokio::time::sleep_until(wake.into()).await;
+    }
+
+    pub fn slow_down(&self) {
+        let mut state = self.lock();
+        state.0 = (state.0 * 2).clamp(Duration::from_millis(250), MAX_INTERVAL);
+    }
+
+    pub fn recover(&self) {
+        let mut state = self.lock();
+        state.0 = (state.0 * 3 / 4).max(self.base);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, (Duration, Option<Instant>)> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// ---- Pushing ----
+
+pub struct Pusher {
+    client: AbsClient,
+    throttle: Throttle,
+    dry_run: bool,
+}
+
+impl Pusher {
+    pub fn new(client: AbsClient, options: PushOptions) -> Self {
+        Pusher {
+            // Retries are paced by the throttle here, not the client's own backoff
+            client: client.with_retry(RetryPolicy { max_attempts: 1, ..Default::default() }),
+            throttle: Throttle::new(options.requests_per_second),
+            dry_run: options.dry_run,
+        }
+    }
+
+    pub fn throttle(&self) -> &Throttle {
+        &self.throttle
+    }
+
+    /// Diff and, unless this is a dry run, write one item. Failures are
+    /// reported in the outcome.
+    pub async fn push(&self, item: &PushItem) -> PushOutcome {
+        let mut outcome = PushOutcome {
+            abs_id: item.abs_id.clone(),
+            title: clean(&item.metadata.title).map(str::to_string),
+            status: PushStatus::Failed,
+            changes: Vec::new(),
+            error: None,
+        };
+        if item.abs_id.trim().is_empty() {
+            outcome.error = Some(AppError::invalid_input("Book has no AudiobookShelf item id"));
+            return outcome;
+        }
+
+        let current = match self.throttled(|| self.client.item(&item.abs_id)).await {
+            Ok(current) => current,
+            Err(e) => {
+                outcome.error = Some(e);
+                return outcome;
+            }
+        };
+        if outcome.title.is_none() {
+            outcome.title = current.media.metadata.title.clone();
+        }
+        let (patch, changes) = diff(&current, &item.metadata);
+        outcome.changes = changes;
+
+        outcome.status = match patch {
+            None => PushStatus::Unchanged,
+            Some(_) if self.dry_run => PushStatus::WouldUpdate,
+            Some(patch) => match self.throttled(|| self.client.update_media(&item.abs_id, &patch)).await {
+                Ok(_) => PushStatus::Updated,
+                Err(e) => {
+                    outcome.error = Some(e);
+                    PushStatus::Failed
+                }
+            },
+        };
+        outcome
+    }
+
+    async fn throttled<T, F, Fut>(&self, request: F) -> Result<T, AppError>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<T, AppError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            self.throttle.wait().await;
+            match request().await {
+                Ok(value) => {
+                    self.throttle.recover();
+                    return Ok(value);
+                }
+                Err(e) if e.is_transient() && attempt < MAX_ATTEMPTS => self.throttle.slow_down(),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Dry-run report for a set of books: what a push would change, without
+/// writing. Larger pushes go through the job queue as `abs_push` jobs.
+#[cfg_attr(feature = "gui", tauri::command)]
+pub async fn abs_push_preview(items: Vec<PushItem>, options: Option<PushOptions>) -> Result<Vec<PushOutcome>, AppError> {
+    let options = PushOptions { dry_run: true, ..options.unwrap_or_default() };
+    let pusher = Pusher::new(AbsClient::from_config()?, options);
+    let mut outcomes = Vec::with_capacity(items.len());
+    for item in &items {
+        let outcome = pusher.push(item).await;
+        // Every later request would fail the same way
+        if let Some(e) = outcome.error.as_ref().filter(|e| e.kind == ErrorKind::AbsUnauthorized) {
+            return Err(e.clone());
+        }
+        outcomes.push(outcome);
+    }
+    Ok(outcomes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abs::{AuthorRef, BookMedia, BookMetadata, SeriesRef};
+
+    fn abs_item() -> LibraryItem {
+        LibraryItem {
+            id: "li_1".to_string(),
+            media: BookMedia {
+                metadata: BookMetadata {
+                    title: Some("Dune".to_string()),
+                    authors: vec![AuthorRef { id: "au_1".to_string(), name: "Frank Herbert".to_string() }],
+                    narrators: vec!["Scott Brick".to_string()],
+                    series: vec![SeriesRef { id: "se_1".to_string(), name: "Dune".to_string(), sequence: Some("1".to_string()) }],
+                    genres: vec!["Science Fiction".to_string()],
+                    ..Default::default()
+                },
+                tags: vec!["Epic".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn identical_metadata_produces_no_patch() {
+        let desired: PushMetadata = serde_json::from_value(json!({
+            "title": "Dune ", "author": "Frank Herbert", "narrator": "Scott Brick",
+            "series": "Dune", "sequence": 1, "genres": ["science fiction"], "tags": ["Epic"],
+            "subtitle": "", "publisher": null,
+        }))
+        .unwrap();
+        let (patch, changes) = diff(&abs_item(), &desired);
+        assert_eq!(patch, None);
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn patch_contains_only_changed_fields() {
+        let desired: PushMetadata = serde_json::from_value(json!({
+            "title": "Dune",
+            "author": "Frank Herbert & Brian Herbert",
+            "published_year": 1965,
+            "tags": ["Epic", "Desert"],
+        }))
+        .unwrap();
+        let (patch, changes) = diff(&abs_item(), &desired);
+        let patch = patch.unwrap();
+
+        assert_eq!(
+            patch,
+            json!({
+                "metadata": {
+                    "publishedYear": "1965",
+                    "authors": [{ "id": "au_1", "name": "Frank Herbert" }, { "id": "new-2", "name": "Brian Herbert" }],
+                },
+                "tags": ["Epic", "Desert"],
+            })
+        );
+        let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, ["publishedYear", "authors", "tags"]);
+    }
+
+    #[test]
+    fn inverted_and_joined_names_are_one_person_each() {
+        let desired: PushMetadata = serde_json::from_value(json!({
+            "author": "Herbert, Frank",
+            "narrator": "Scott Brick and Simon Vance",
+        }))
+        .unwrap();
+        let (patch, changes) = diff(&abs_item(), &desired);
+        // "Herbert, Frank" is the author ABS already has
+        assert!(changes.iter().all(|c| c.field != "authors"));
+        assert_eq!(patch.unwrap(), json!({ "metadata": { "narrators": ["Scott Brick", "Simon Vance"] } }));
+
+        let desired: PushMetadata = serde_json::from_value(json!({ "author": "Herbert, Frank & Anderson, Kevin J., editor" })).unwrap();
+        let (patch, _) = diff(&abs_item(), &desired);
+        assert_eq!(
+            patch.unwrap()["metadata"]["authors"],
+            json!([{ "id": "au_1", "name": "Frank Herbert" }, { "id": "new-2", "name": "Kevin J. Anderson" }])
+        );
+    }
+
+    #[test]
+    fn throttle_backs_off_and_recovers() {
+        let throttle = Throttle::new(4.0);
+        assert_eq!(throttle.interval(), Duration::from_millis(250));
+        throttle.slow_down();
+        throttle.slow_down();
+        assert_eq!(throttle.interval(), Duration::from_secs(1));
+        for _ in 0..20 {
+            throttle.recover();
+        }
+        assert_eq!(throttle.interval(), Duration::from_millis(250));
+        for _ in 0..20 {
+            throttle.slow_down();
+        }
+        assert_eq!(throttle.interval(), MAX_INTERVAL);
+    }
+
+    #[tokio::test]
+    async fn pushes_only_changes_against_mock_server() {
+        let mock = crate::abs_mock::MockAbs::start().await;
+        let item = PushItem {
+            abs_id: "li_dune".to_string(),
+            group_id: None,
+            metadata: serde_json::from_value(json!({ "title": "Dune", "narrator": "Scott Brick, Simon Vance" })).unwrap(),
+        };
+        let patches = || mock.requests().iter().filter(|r| r.method == "PATCH").count();
+        let options = PushOptions { dry_run: true, requests_per_second: 1000.0 };
+
+        let preview = Pusher::new(mock.client(), options).push(&item).await;
+        assert_eq!(preview.status, PushStatus::WouldUpdate);
+        assert_eq!(preview.changes[0].field, "narrators");
+        assert_eq!(patches(), 0);
+
+        // A 503 slows the throttle down and the request is retried
+        mock.fail_next(&[503]);
+        let pusher = Pusher::new(mock.client(), PushOptions { dry_run: false, ..options });
+        let outcome = pusher.push(&item).await;
+        assert_eq!(outcome.status, PushStatus::Updated);
+        assert!(pusher.throttle().interval() > Duration::from_millis(1));
+        assert_eq!(patches(), 1);
+        assert_eq!(mock.item("li_dune").unwrap()["media"]["metadata"]["narrators"], json!(["Scott Brick", "Simon Vance"]));
+
+        assert_eq!(pusher.push(&item).await.status, PushStatus::Unchanged);
+        assert_eq!(patches(), 1);
+
+        let missing = PushItem { abs_id: "li_missing".to_string(), ..item };
+        let failed = pusher.push(&missing).await;
+        assert_eq!(failed.status, PushStatus::Failed);
+        assert_eq!(failed.error.unwrap().kind, ErrorKind::AbsNotFound);
+    }
+
+    #[test]
+    fn pushed_fields_overlay_library_metadata() {
+        let mut library = crate::scanner::BookMetadata {
+            title: "Dune".to_string(),
+            description: "Arrakis.".to_string(),
+            ..Default::default()
+        };
+        let pushed: PushMetadata = serde_json::from_value(json!({
+            "narrator": "Scott Brick, Simon Vance",
+            "all_series": [{ "name": "Dune", "sequence": 1 }],
+            "year": 1965,
+        }))
+        .unwrap();
+        pushed.apply_to(&mut library);
+        assert_eq!(library.description, "Arrakis.");
+        assert_eq!(library.year, "1965");
+        assert_eq!(library.narrators.len(), 2);
+        assert_eq!(library.series_list[0].position.as_ref().map(|p| p.to_string()).as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn dry_runs_leave_the_library_alone() {
+        let mut db = LibraryDb::open_in_memory().unwrap();
+        let mut groups = vec![crate::scanner::BookGroup {
+            id: "g1".to_string(),
+            group_name: "Dune".to_string(),
+            group_type: "single".to_string(),
+            metadata: crate::scanner::BookMetadata { title: "Dune".to_string(), ..Default::default() },
+            files: vec![crate::scanner::AudioFile {
+                id: "f1".to_string(),
+                path: "/lib/Dune/dune.m4b".to_string(),
+                filename: "dune.m4b".to_string(),
+                changes: Default::default(),
+                status: "unchanged".to_string(),
+            }],
+            total_changes: 0,
+            scan_status: "not_scanned".to_string(),
+            abs_id: Some("li_1".to_string()),
+        }];
+        db.record_scan(&mut groups, &[]).unwrap();
+
+        let item = PushItem {
+            abs_id: "li_1".to_string(),
+            group_id: Some("g1".to_string()),
+            metadata: serde_json::from_value(json!({ "title": "Dune", "author": "Frank Herbert" })).unwrap(),
+        };
+        let dry_run = PushOptions { dry_run: true, ..Default::default() };
+        let unchanged = PushOutcome {
+            abs_id: "li_1".to_string(),
+            title: Some("Dune".to_string()),
+            status: PushStatus::Unchanged,
+            changes: vec![],
+            error: None,
+        };
+        record_outcome(&mut db, &item, &unchanged, &dry_run).unwrap();
+        let failed = PushOutcome { status: PushStatus::Failed, error: Some(AppError::invalid_input("x")), ..unchanged.clone() };
+        record_outcome(&mut db, &item, &failed, &dry_run).unwrap();
+        assert!(db.get_push_status("g1").unwrap().is_none());
+        assert_eq!(db.history("g1").unwrap().len(), 1);
+
+        // The same outcome of a real push is recorded
+        record_outcome(&mut db, &item, &unchanged, &PushOptions::default()).unwrap();
+        assert_eq!(db.get_push_status("g1").unwrap().unwrap().status, "pushed");
+        assert_eq!(db.history("g1").unwrap().len(), 2);
+    }
+}
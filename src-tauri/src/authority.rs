@@ -0,0 +1,418 @@
+// src-tauri/src/authority.rs
+// Local authority file for authors and narrators: one canonical name per
+// person plus the variants seen in the library ("Kramer, Michael",
+// "M. Kramer"). Used by the scanner and Whisper parsing to map variants.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::error::{AppError, ErrorKind};
+use crate::names::{normalize_name, split_people, PersonRole};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorityEntry {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuthorityStore {
+    #[serde(default)]
+    pub people: Vec<AuthorityEntry>,
+}
+
+/// A proposed merge of `name` into the canonical `canonical`.
+#[derive(Debug, Clone, Serialize)]
+pub struct MergeSuggestion {
+    pub name: String,
+    pub canonical: String,
+    pub canonical_id: Option<String>,
+    pub score: f32,
+}
+
+const SUGGESTION_THRESHOLD: f32 = 0.8;
+
+// ---- Paths ----
+
+fn authority_path() -> Result<PathBuf, String> {
+    let dir = dirs::data_dir()
+        .ok_or("Cannot find data directory")?
+        .join("Audiobook Tagger");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Data dir error: {}", e))?;
+    Ok(dir.join("authority.json"))
+}
+
+// ---- Matching ----
+
+/// Comparison key: un-inverted, initials normalised, case and punctuation
+/// folded. "Kramer, Michael" and "michael  kramer" share a key.
+fn alias_key(name: &str) -> String {
+    let person = split_people(name, PersonRole::Author)
+        .into_iter()
+        .next()
+        .map(|p| p.name)
+        .unwrap_or_else(|| normalize_name(name));
+    person
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub(crate) fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b_chars.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b_chars.len() + 1];
+        for (j, cb) in b_chars.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b_chars.len()]
+}
+
+/// Similarity in 0..=1 between two names. Same surname with compatible given
+/// names ("M. Kramer" / "Michael Kramer") scores 0.9; otherwise edit distance.
+pub fn name_similarity(a: &str, b: &str) -> f32 {
+    let (ka, kb) = (alias_key(a), alias_key(b));
+    if ka.is_empty() || kb.is_empty() {
+        return 0.0;
+    }
+    if ka == kb {
+        return 1.0;
+    }
+
+    let wa: Vec<&str> = ka.split_whitespace().collect();
+    let wb: Vec<&str> = kb.split_whitespace().collect();
+    if wa.len() > 1 && wb.len() > 1 && wa.last() == wb.last() {
+        let given_a = &wa[..wa.len() - 1];
+        let given_b = &wb[..wb.len() - 1];
+        let compatible = given_a.iter().zip(given_b.iter()).all(|(x, y)| {
+            x == y || (x.len() == 1 && y.starts_with(*x)) || (y.len() == 1 && x.starts_with(*y))
+        });
+        if compatible {
+            return 0.9;
+        }
+    }
+
+    let max_len = ka.chars().count().max(kb.chars().count()) as f32;
+    1.0 - levenshtein(&ka, &kb) as f32 / max_len
+}
+
+impl AuthorityStore {
+    /// The saved table for matching; empty when it is missing or unreadable.
+    pub fn load() -> Self {
+        Self::try_load().unwrap_or_default()
+    }
+
+    /// The saved table, or an empty one when none exists yet. Anything that
+    /// saves afterwards must use this: a file that exists but can't be read
+    /// is an error, not an empty table to write over it.
+    pub fn try_load() -> Result<Self, AppError> {
+        Self::load_or_default(&authority_path()?)
+    }
+
+    fn load_or_default(path: &Path) -> Result<Self, AppError> {
+        match std::fs::read_to_string(path) {
+            Ok(data) => Self::parse(&data),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn load_from(path: &Path) -> Result<Self, AppError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    fn parse(data: &str) -> Result<Self, AppError> {
+        serde_json::from_str(data).map_err(|e| {
+            AppError::new(ErrorKind::Parse, "Invalid authority file")
+                .with_detail(e.to_string())
+                .with_hint("Fix or restore authority.json; it was left unchanged")
+        })
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        self.save_to(&authority_path()?)
+    }
+
+    fn save_to(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| format!("Serialize error: {}", e))?;
+        std::fs::write(path, json).map_err(|e| format!("Write error: {}", e))
+    }
+
+    /// Find the entry whose canonical name or any alias matches `name`.
+    pub fn resolve(&self, name: &str) -> Option<&AuthorityEntry> {
+        let key = alias_key(name);
+        if key.is_empty() {
+            return None;
+        }
+        self.people.iter().find(|e| {
+            alias_key(&e.name) == key || e.aliases.iter().any(|a| alias_key(a) == key)
+        })
+    }
+
+    /// Canonical form of `name`, or `name` itself when the store doesn't know it.
+    pub fn canonicalize(&self, name: &str) -> String {
+        self.resolve(name)
+            .map(|e| e.name.clone())
+            .unwrap_or_else(|| name.to_string())
+    }
+
+    /// Record `alias` as a variant of `canonical`, creating the entry if needed.
+    /// `canonical` may itself be a known variant; the alias joins its entry.
+    pub fn add_alias(&mut self, canonical: &str, alias: &str) -> &AuthorityEntry {
+        let key = alias_key(canonical);
+        let existing = self
+            .people
+            .iter()
+            .position(|e| alias_key(&e.name) == key)
+            .or_else(|| self.people.iter().position(|e| e.aliases.iter().any(|a| alias_key(a) == key)));
+        let idx = match existing {
+            Some(i) => i,
+            None => {
+                self.people.push(AuthorityEntry {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    name: canonical.trim().to_string(),
+                    aliases: Vec::new(),
+                });
+                self.people.len() - 1
+            }
+        };
+        let entry = &mut self.people[idx];
+        let alias = alias.trim();
+        if !alias.is_empty()
+            && alias_key(alias) != alias_key(&entry.name)
+            && !entry.aliases.iter().any(|a| alias_key(a) == alias_key(alias))
+        {
+            entry.aliases.push(alias.to_string());
+        }
+        &self.people[idx]
+    }
+
+    /// Fold `merge_ids` into `keep_id`: their names and aliases become aliases.
+    pub fn merge(&mut self, keep_id: &str, merge_ids: &[String]) -> Result<(), String> {
+        let keep_name = self
+            .people
+            .iter()
+            .find(|e| e.id == keep_id)
+            .map(|e| e.name.clone())
+            .ok_or_else(|| format!("Unknown authority entry: {}", keep_id))?;
+
+        let (merged, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.people)
+            .into_iter()
+            .partition(|e| e.id != keep_id && merge_ids.contains(&e.id));
+        self.people = kept;
+
+        for entry in merged {
+            self.add_alias(&keep_name, &entry.name);
+            for alias in &entry.aliases {
+                self.add_alias(&keep_name, alias);
+            }
+        }
+        Ok(())
+    }
+
+    /// Merge another table into this one, unioning aliases by canonical name.
+    pub fn import(&mut self, other: AuthorityStore) {
+        for entry in other.people {
+            self.add_alias(&entry.name, "");
+            for alias in &entry.aliases {
+                self.add_alias(&entry.name, alias);
+            }
+        }
+    }
+
+    /// Suggest merges for names seen in the library: near-misses of existing
+    /// canonical names first, then near-duplicates among the unknown names.
+    pub fn suggest_merges(&self, names: &[String]) -> Vec<MergeSuggestion> {
+        let mut unknown: Vec<&String> = Vec::new();
+        for name in names {
+            if self.resolve(name).is_none() && !unknown.iter().any(|u| alias_key(u) == alias_key(name)) {
+                unknown.push(name);
+            }
+        }
+
+        let mut out = Vec::new();
+        for name in &unknown {
+            let best = self
+                .people
+                .iter()
+                .flat_map(|e| std::iter::once(&e.name).chain(e.aliases.iter()).map(move |n| (e, n)))
+                .map(|(e, n)| (e, name_similarity(name, n)))
+                .filter(|(_, score)| *score >= SUGGESTION_THRESHOLD)
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+            if let Some((entry, score)) = best {
+                out.push(MergeSuggestion {
+                    name: name.to_string(),
+                    canonical: entry.name.clone(),
+                    canonical_id: Some(entry.id.clone()),
+                    score,
+                });
+            }
+        }
+
+        for (i, a) in unknown.iter().enumerate() {
+            for b in unknown.iter().skip(i + 1) {
+                let score = name_similarity(a, b);
+                if score >= SUGGESTION_THRESHOLD {
+                    // Prefer the longer, un-inverted spelling as canonical.
+                    let (name, canonical) = if b.len() > a.len() && !b.contains(',') { (a, b) } else { (b, a) };
+                    out.push(MergeSuggestion {
+                        name: name.to_string(),
+                        canonical: canonical.to_string(),
+                        canonical_id: None,
+                        score,
+                    });
+                }
+            }
+        }
+
+        out.sort_by(|a, b| b.score.total_cmp(&a.score));
+        out
+    }
+}
+
+// ---- Tauri commands ----
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn authority_list() -> Result<Vec<AuthorityEntry>, AppError> {
+    Ok(AuthorityStore::try_load()?.people)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn authority_add_alias(canonical: String, alias: String) -> Result<AuthorityEntry, AppError> {
+    let mut store = AuthorityStore::try_load()?;
+    let entry = store.add_alias(&canonical, &alias).clone();
+    store.save()?;
+    Ok(entry)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn authority_merge(keep_id: String, merge_ids: Vec<String>) -> Result<Vec<AuthorityEntry>, AppError> {
+    let mut store = AuthorityStore::try_load()?;
+    store.merge(&keep_id, &merge_ids)?;
+    store.save()?;
+    Ok(store.people)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn authority_remove(id: String) -> Result<String, AppError> {
+    let mut store = AuthorityStore::try_load()?;
+    let before = store.people.len();
+    store.people.retain(|e| e.id != id);
+    if store.people.len() == before {
+        return Err(AppError::new(ErrorKind::NotFound, format!("Unknown authority entry: {}", id)));
+    }
+    store.save()?;
+    Ok("Removed".to_string())
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn authority_suggest_merges(names: Vec<String>) -> Result<Vec<MergeSuggestion>, AppError> {
+    Ok(AuthorityStore::try_load()?.suggest_merges(&names))
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn authority_export(path: String) -> Result<String, AppError> {
+    let store = AuthorityStore::try_load()?;
+    store.save_to(Path::new(&path))?;
+    Ok(format!("Exported {} people", store.people.len()))
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn authority_import(path: String, replace: bool) -> Result<String, AppError> {
+    let incoming = AuthorityStore::load_from(Path::new(&path))?;
+    let count = incoming.people.len();
+    let store = if replace {
+        incoming
+    } else {
+        let mut store = AuthorityStore::try_load()?;
+        store.import(incoming);
+        store
+    };
+    store.save()?;
+    Ok(format!("Imported {} people", count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> AuthorityStore {
+        let mut s = AuthorityStore::default();
+        s.add_alias("Michael Kramer", "M. Kramer");
+        s.add_alias("Kate Reading", "");
+        s
+    }
+
+    #[test]
+    fn resolves_inverted_and_aliased_names() {
+        let s = store();
+        assert_eq!(s.canonicalize("Kramer, Michael"), "Michael Kramer");
+        assert_eq!(s.canonicalize("michael  kramer"), "Michael Kramer");
+        assert_eq!(s.canonicalize("M. Kramer"), "Michael Kramer");
+        assert_eq!(s.canonicalize("Tim Gerard Reynolds"), "Tim Gerard Reynolds");
+    }
+
+    #[test]
+    fn suggests_near_misses() {
+        let s = store();
+        let names = vec!["Kate Readng".to_string(), "Steven Pacey".to_string(), "Stephen Pacey".to_string()];
+        let suggestions = s.suggest_merges(&names);
+        assert!(suggestions.iter().any(|m| m.name == "Kate Readng" && m.canonical == "Kate Reading"));
+        assert!(suggestions.iter().any(|m| m.canonical_id.is_none()
+            && (m.name == "Steven Pacey" || m.name == "Stephen Pacey")));
+        assert!(name_similarity("M. Kramer", "Michael Kramer") >= SUGGESTION_THRESHOLD);
+        assert!(name_similarity("Michael Kramer", "Michael Caine") < SUGGESTION_THRESHOLD);
+    }
+
+    #[test]
+    fn merge_folds_entries_into_aliases() {
+        let mut s = store();
+        s.add_alias("Mike Kramer", "");
+        let keep = s.resolve("Michael Kramer").unwrap().id.clone();
+        let drop = s.resolve("Mike Kramer").unwrap().id.clone();
+        s.merge(&keep, &[drop]).unwrap();
+        assert_eq!(s.people.len(), 2);
+        assert_eq!(s.canonicalize("Mike Kramer"), "Michael Kramer");
+    }
+
+    #[test]
+    fn export_import_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aliases.json");
+        store().save_to(&path).unwrap();
+
+        let mut other = AuthorityStore::default();
+        other.add_alias("Michael Kramer", "Kramer, M.");
+        other.import(AuthorityStore::load_from(&path).unwrap());
+        assert_eq!(other.people.len(), 2);
+        assert_eq!(other.resolve("M. Kramer").unwrap().aliases, ["Kramer, M."]);
+    }
+
+    #[test]
+    fn aliases_join_the_entry_of_a_known_variant() {
+        let mut s = store();
+        s.add_alias("M. Kramer", "Mike Kramer");
+        assert_eq!(s.people.len(), 2);
+        assert_eq!(s.canonicalize("Mike Kramer"), "Michael Kramer");
+    }
+
+    #[test]
+    fn unreadable_file_is_an_error_not_an_empty_table() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("authority.json");
+        assert!(AuthorityStore::load_or_default(&path).unwrap().people.is_empty());
+
+        std::fs::write(&path, "{ not json").unwrap();
+        assert_eq!(AuthorityStore::load_or_default(&path).unwrap_err().kind, ErrorKind::Parse);
+    }
+}
@@ -0,0 +1,448 @@
+// src-tauri/src/bin/cli.rs
+// Headless front end for servers: scan, inspect and tag files, run Whisper
+// intro extraction, full transcription and chapter naming, and manage
+// models without the Tauri window.
+// Build with: cargo build --no-default-features --features cli --bin audiobook-tagger-cli
+
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+
+use audiobook_tagger_v2::chapter_titles::{self, ChapterTitleRequest};
+use audiobook_tagger_v2::error::{AppError, ErrorKind};
+use audiobook_tagger_v2::progress::TerminalProgress;
+use audiobook_tagger_v2::scanner::{self, BookMetadata};
+use audiobook_tagger_v2::transcribe::{self, TranscribeRequest, TranscriptFormat};
+use audiobook_tagger_v2::whisper::{self, AudioIntroRequest};
+use audiobook_tagger_v2::{chapters, ollama, tags, whisper_local};
+
+#[derive(Parser)]
+#[command(name = "audiobook-tagger-cli", version, about = "Audiobook Tagger without the desktop window")]
+struct Cli {
+    /// Print machine-readable JSON on stdout instead of a summary
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Scan folders into book groups and record them in the library database
+    Scan {
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+    /// Show every tag item, duration and bitrate of one audio file
+    Inspect { file: PathBuf },
+    /// Write metadata into a file, or every audio file in a folder
+    Write {
+        path: PathBuf,
+        /// JSON file with BookMetadata fields; missing fields are left untouched
+        #[arg(long)]
+        metadata: Option<PathBuf>,
+        /// Set a single field, e.g. --set narrator="Kate Reading" (repeatable)
+        #[arg(long = "set", value_name = "KEY=VALUE")]
+        set: Vec<String>,
+    },
+    /// Transcribe the opening of a book and parse title, author and narrator
+    ExtractIntro {
+        /// Local audio file, or the ABS item id with --abs-url
+        target: String,
+        #[arg(long)]
+        title: Option<String>,
+        #[arg(long)]
+        author: Option<String>,
+        /// The book's current narrator, to check the intro against
+        #[arg(long)]
+        narrator: Option<String>,
+        /// Use the local whisper.cpp install instead of the OpenAI API
+        #[arg(long)]
+        local_whisper: bool,
+        #[arg(long, default_value = "base")]
+        whisper_model: String,
+        #[arg(long, env = "OPENAI_API_KEY", hide_env_values = true)]
+        openai_api_key: Option<String>,
+        /// Parse the transcript with this Ollama model instead of OpenAI
+        #[arg(long)]
+        ollama_model: Option<String>,
+        #[arg(long)]
+        ollama_url: Option<String>,
+        #[arg(long, env = "ABS_URL")]
+        abs_url: Option<String>,
+        #[arg(long, env = "ABS_API_TOKEN", hide_env_values = true)]
+        abs_token: Option<String>,
+        /// Final file of a multi-file book, for the closing-credits pass
+        #[arg(long)]
+        last_file: Option<String>,
+        /// Scan the closing credits (true/false); unset scans only when the intro left gaps
+        #[arg(long)]
+        outro: Option<bool>,
+        /// Length of the closing-credits window in minutes
+        #[arg(long)]
+        outro_minutes: Option<u32>,
+        /// Ignore the transcript cache
+        #[arg(long)]
+        force: bool,
+    },
+    /// Transcribe a book (a file, or a folder of files in name order) to
+    /// timestamped SRT/VTT/JSON. Rerun the same command to resume.
+    Transcribe {
+        path: PathBuf,
+        /// Start of the range on the book's timeline, in seconds
+        #[arg(long)]
+        from: Option<u32>,
+        /// End of the range on the book's timeline, in seconds
+        #[arg(long)]
+        to: Option<u32>,
+        /// Output formats; all of them when not given
+        #[arg(long = "format", value_parser = ["srt", "vtt", "json"])]
+        formats: Vec<String>,
+        /// Defaults to the book's folder
+        #[arg(long)]
+        out_dir: Option<String>,
+        /// Output file name without extension
+        #[arg(long)]
+        name: Option<String>,
+        /// Seconds of audio per Whisper call (and per resume step)
+        #[arg(long)]
+        chunk_secs: Option<u32>,
+        /// Use the local whisper.cpp install instead of the OpenAI API
+        /// (whose key is read from the app's secret store)
+        #[arg(long)]
+        local_whisper: bool,
+        #[arg(long, default_value = "base")]
+        whisper_model: String,
+    },
+    /// List the chapter markers embedded in a file
+    Chapters { file: PathBuf },
+    /// Propose chapter titles from the headings read at each chapter start
+    /// (embedded markers, or long silences with --silence)
+    ChapterTitles {
+        path: PathBuf,
+        /// Find chapter starts from silences even when the file has markers
+        #[arg(long)]
+        silence: bool,
+        /// Seconds transcribed at each chapter start
+        #[arg(long)]
+        window_secs: Option<u32>,
+        /// Ask an LLM about chapters the patterns can't read
+        #[arg(long)]
+        llm: bool,
+        /// Use the local whisper.cpp install instead of the OpenAI API
+        /// (whose key is read from the app's secret store)
+        #[arg(long)]
+        local_whisper: bool,
+        #[arg(long, default_value = "base")]
+        whisper_model: String,
+        /// Ask this Ollama model instead of OpenAI
+        #[arg(long)]
+        ollama_model: Option<String>,
+        #[arg(long)]
+        ollama_url: Option<String>,
+    },
+    /// Manage Whisper and Ollama models
+    Models {
+        #[command(subcommand)]
+        command: ModelsCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum ModelsCommand {
+    /// Download a Whisper model (--whisper) or pull an Ollama model
+    Pull {
+        name: String,
+        #[arg(long)]
+        whisper: bool,
+        #[arg(long)]
+        ollama_url: Option<String>,
+    },
+    /// Show installed Whisper and Ollama models
+    List {
+        #[arg(long)]
+        ollama_url: Option<String>,
+    },
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    let result = tokio::select! {
+        result = run(cli.command, cli.json) => result,
+        _ = tokio::signal::ctrl_c() => Err(AppError::new(ErrorKind::Cancelled, "Interrupted")),
+    };
+    // With server mode on, transcribing starts whisper-server; it would
+    // outlive the CLI and hold the port
+    whisper_local::stop_server().await;
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        if let Some(detail) = &e.detail {
+            eprintln!("{}", detail);
+        }
+        if let Some(hint) = &e.hint {
+            eprintln!("Hint: {}", hint);
+        }
+        std::process::exit(1);
+    }
+}
+
+async fn run(command: Command, json: bool) -> Result<(), AppError> {
+    match command {
+        Command::Scan { paths } => {
+            let result = scanner::scan_paths(paths, &TerminalProgress::default()).await?;
+            print_output(json, &result, || {
+                for g in &result.groups {
+                    let m = &g.metadata;
+                    println!("{} — {} [{} files]", m.title, m.author, g.files.len());
+                }
+                println!("{} books, {} files", result.groups.len(), result.total_files);
+            })
+        }
+
+        Command::Inspect { file } => {
+            let info = tags::read_file_tags(&file)?;
+            print_output(json, &info, || {
+                println!("{}", info.path);
+                println!("  tag: {}", info.tag_type.as_deref().unwrap_or("none"));
+                println!("  duration: {}s, pictures: {}", info.duration_secs, info.pictures);
+                for item in &info.items {
+                    println!("  {} = {}", item.key, item.value);
+                }
+            })
+        }
+
+        Command::Write { path, metadata, set } => {
+            let metadata = build_metadata(metadata.as_deref(), &set)?;
+            let files = audio_files_in(&path);
+            if files.is_empty() {
+                return Err(AppError::invalid_input(format!("No audio files found at {}", path.display())));
+            }
+            let mut written = Vec::new();
+            for file in &files {
+                tags::write_book_metadata(file, &metadata)
+                    .map_err(|e| format!("{}: {}", file.display(), e))?;
+                written.push(file.to_string_lossy().into_owned());
+            }
+            print_output(json, &written, || println!("Wrote tags to {} file(s)", written.len()))
+        }
+
+        Command::ExtractIntro {
+            target, title, author, narrator, local_whisper, whisper_model, openai_api_key,
+            ollama_model, ollama_url, abs_url, abs_token, last_file, outro, outro_minutes, force,
+        } => {
+            let is_abs = abs_url.is_some();
+            let request = AudioIntroRequest {
+                item_id: target.clone(),
+                source: if is_abs { "abs" } else { "local" }.to_string(),
+                title,
+                author,
+                narrator,
+                file_ino: None,
+                file_path: if is_abs { None } else { Some(target) },
+                abs_base_url: abs_url,
+                abs_api_token: abs_token,
+                openai_api_key,
+                use_local_ai: Some(ollama_model.is_some()),
+                ollama_model,
+                ollama_base_url: ollama_url,
+                use_local_whisper: Some(local_whisper),
+                whisper_model: Some(whisper_model),
+                last_file_ino: None,
+                last_file_path: last_file,
+                scan_outro: outro,
+                outro_minutes,
+            };
+            let results = whisper::batch_extract_intros(vec![request], force, Default::default(), &TerminalProgress::default()).await?;
+            let result = results.into_iter().next().ok_or("No result")?;
+            if let Some(e) = result.error {
+                return Err(e);
+            }
+            print_output(json, &result, || {
+                println!("Title:     {}", result.title.as_deref().unwrap_or("-"));
+                println!("Authors:   {}", result.authors.join(", "));
+                println!("Narrators: {}", result.narrators.join(", "));
+                println!("Cast:      {}", result.production.map_or("-", |p| p.as_str()));
+                println!("Publisher: {}", result.publisher.as_deref().unwrap_or("-"));
+                println!("Copyright: {}", result.copyright_year.as_deref().unwrap_or("-"));
+                println!("Method:    {} ({:.0}% confidence)", result.parse_method, result.confidence * 100.0);
+                for (field, check) in &result.field_status {
+                    if let Some(current) = check.current.as_deref() {
+                        println!("Conflict:  {} differs from the book's \"{}\"", field, current);
+                    }
+                }
+            })
+        }
+
+        Command::Transcribe {
+            path, from, to, formats, out_dir, name, chunk_secs, local_whisper, whisper_model,
+        } => {
+            let files = audio_files_in(&path);
+            if files.is_empty() {
+                return Err(AppError::invalid_input(format!("No audio files found at {}", path.display())));
+            }
+            let formats = formats
+                .iter()
+                .map(|f| match f.as_str() {
+                    "srt" => TranscriptFormat::Srt,
+                    "vtt" => TranscriptFormat::Vtt,
+                    _ => TranscriptFormat::Json,
+                })
+                .collect();
+            let request = TranscribeRequest {
+                files: files.iter().map(|f| f.to_string_lossy().into_owned()).collect(),
+                start_secs: from,
+                end_secs: to,
+                formats,
+                output_dir: out_dir,
+                output_name: name,
+                chunk_secs,
+                use_local_whisper: Some(local_whisper),
+                whisper_model: Some(whisper_model),
+            };
+            let summary = transcribe::transcribe_book(&request, &TerminalProgress::default()).await?;
+            print_output(json, &summary, || {
+                if summary.resumed > 0 {
+                    println!("Resumed after {} of {} chunks", summary.resumed, summary.chunks);
+                }
+                println!("{} segments ({})", summary.segments, summary.language.as_deref().unwrap_or("language unknown"));
+                for output in &summary.outputs {
+                    println!("  {}", output);
+                }
+            })
+        }
+
+        Command::Chapters { file } => {
+            let chapters = chapters::read_chapters(&file)?;
+            print_output(json, &chapters, || {
+                for (i, c) in chapters.iter().enumerate() {
+                    println!("{:>3}  {}  {}", i + 1, format_ms(c.start_ms), c.title);
+                }
+            })
+        }
+
+        Command::ChapterTitles {
+            path, silence, window_secs, llm, local_whisper, whisper_model, ollama_model, ollama_url,
+        } => {
+            let files = audio_files_in(&path);
+            if files.is_empty() {
+                return Err(AppError::invalid_input(format!("No audio files found at {}", path.display())));
+            }
+            let request = ChapterTitleRequest {
+                files: files.iter().map(|f| f.to_string_lossy().into_owned()).collect(),
+                detect_silence: silence,
+                window_secs,
+                use_llm: llm,
+                use_local_whisper: Some(local_whisper),
+                whisper_model: Some(whisper_model),
+                use_local_ai: Some(ollama_model.is_some()),
+                ollama_model,
+                ollama_base_url: ollama_url,
+                ..Default::default()
+            };
+            let titles = chapter_titles::propose_chapter_titles(&request, &TerminalProgress::default()).await?;
+            print_output(json, &titles, || {
+                for (i, c) in titles.chapters.iter().enumerate() {
+                    let mark = if c.method.is_some() { ' ' } else { '?' };
+                    println!("{:>3}{} {}  {}", i + 1, mark, format_ms(c.chapter.start_ms), c.chapter.title);
+                }
+                println!("Headings found for {} of {} chapters", titles.named, titles.chapters.len());
+            })
+        }
+
+        Command::Models { command: ModelsCommand::Pull { name, whisper, ollama_url } } => {
+            let message = if whisper {
+                whisper_local::download_model(&name, &TerminalProgress::default()).await?
+            } else {
+                ollama::pull_model(&name, ollama_url.as_deref(), &TerminalProgress::default()).await?
+            };
+            print_output(json, &message, || println!("{}", message))
+        }
+
+        Command::Models { command: ModelsCommand::List { ollama_url } } => {
+            let whisper = whisper_local::whisper_local_get_status().await?;
+            let ollama = ollama::ollama_get_status(ollama_url).await?;
+            let both = serde_json::json!({ "whisper": whisper, "ollama": ollama });
+            print_output(json, &both, || {
+                println!("Whisper ({}):", if whisper.installed { "installed" } else { "not installed" });
+                for m in &whisper.models {
+                    println!("  {} ({} MB)", m.id, m.size_bytes / 1_000_000);
+                }
+                println!("Ollama ({}):", if ollama.running { "running" } else { "not running" });
+                for m in &ollama.models {
+                    println!("  {} ({} MB)", m.name, m.size_bytes / 1_000_000);
+                }
+            })
+        }
+    }
+}
+
+fn print_output<T: Serialize>(json: bool, value: &T, human: impl FnOnce()) -> Result<(), AppError> {
+    if json {
+        let text = serde_json::to_string_pretty(value)?;
+        println!("{}", text);
+    } else {
+        human();
+    }
+    Ok(())
+}
+
+/// Start from an empty BookMetadata, overlay the JSON file, then `--set` pairs.
+fn build_metadata(file: Option<&Path>, set: &[String]) -> Result<BookMetadata, AppError> {
+    let mut value = serde_json::to_value(BookMetadata::default()).map_err(|e| format!("JSON error: {}", e))?;
+
+    if let Some(file) = file {
+        let text = std::fs::read_to_string(file)?;
+        let overlay: serde_json::Value = serde_json::from_str(&text)?;
+        let overlay = overlay
+            .as_object()
+            .ok_or_else(|| AppError::invalid_input("Metadata file must contain a JSON object"))?;
+        for (k, v) in overlay {
+            value[k] = v.clone();
+        }
+    }
+
+    for pair in set {
+        let (key, raw) = pair
+            .split_once('=')
+            .ok_or_else(|| AppError::invalid_input(format!("Expected KEY=VALUE, got '{}'", pair)))?;
+        let key = key.trim();
+        if value.get(key).is_none() {
+            return Err(AppError::invalid_input(format!("Unknown metadata field '{}'", key)));
+        }
+        value[key] = match key {
+            "genres" | "tags" => serde_json::json!(raw.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()).collect::<Vec<_>>()),
+            _ => serde_json::Value::String(raw.to_string()),
+        };
+    }
+
+    serde_json::from_value(value)
+        .map_err(|e| AppError::invalid_input("Invalid metadata").with_detail(e.to_string()))
+}
+
+fn audio_files_in(path: &Path) -> Vec<PathBuf> {
+    let is_audio = |p: &Path| {
+        p.extension()
+            .and_then(|e| e.to_str())
+            .map(|e| scanner::AUDIO_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+            .unwrap_or(false)
+    };
+    if path.is_file() {
+        return if is_audio(path) { vec![path.to_path_buf()] } else { vec![] };
+    }
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && is_audio(e.path()))
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+    files
+}
+
+fn format_ms(ms: u64) -> String {
+    let secs = ms / 1000;
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
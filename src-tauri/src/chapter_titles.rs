@@ -0,0 +1,514 @@
+// src-tauri/src/chapter_titles.rs
+// Chapter names from the narration: transcribe a few seconds at each chapter
+// start (the book's markers, or long silences when it has none) and read
+// spoken headings such as "Chapter Twelve: The Storm" or "Part Two". Number
+// words become digits, and the named list is proposed for review rather
+// than written anywhere.
+
+use regex::{Match, Regex};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::chapters;
+use crate::config::{get_secret, AppConfig, Secret};
+use crate::error::{AppError, ErrorKind};
+use crate::library::Chapter;
+use crate::progress::{ProgressEvent, ProgressSink, Stage};
+use crate::transcribe::{self, Chunk, TranscribeRequest};
+use crate::whisper::{self, LlmSettings};
+
+const DEFAULT_WINDOW_SECS: u32 = 15;
+const DEFAULT_NOISE_DB: f32 = -30.0;
+const DEFAULT_MIN_SILENCE_SECS: f32 = 2.0;
+const DEFAULT_MIN_CHAPTER_SECS: u32 = 120;
+/// Windows open slightly before the marker; markers often land a beat after
+/// the narrator has started speaking.
+const LEAD_IN_MS: u64 = 500;
+
+/// Keywords that need a number after them to count as a heading.
+const NUMBERED: [&str; 5] = ["chapter", "part", "book", "section", "volume"];
+
+lazy_static::lazy_static! {
+    static ref HEADING_WORD: Regex = Regex::new(
+        r"(?i)\b(chapter|part|book|section|volume|prologue|epilogue|introduction|preface|foreword|afterword|interlude)\b"
+    ).unwrap();
+    static ref TOKEN: Regex = Regex::new(r"[A-Za-z0-9]+").unwrap();
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChapterTitleRequest {
+    /// Audio files in playback order.
+    pub files: Vec<String>,
+    /// Chapters to name, on the book's timeline. When empty, the markers
+    /// embedded in a single-file book, then silences.
+    #[serde(default)]
+    pub chapters: Vec<Chapter>,
+    /// Find boundaries from silences even when there are markers.
+    #[serde(default)]
+    pub detect_silence: bool,
+    /// Seconds transcribed at each chapter start.
+    #[serde(default)]
+    pub window_secs: Option<u32>,
+    #[serde(default)]
+    pub noise_db: Option<f32>,
+    #[serde(default)]
+    pub min_silence_secs: Option<f32>,
+    #[serde(default)]
+    pub min_chapter_secs: Option<u32>,
+    /// Ask the LLM about windows the patterns can't read.
+    #[serde(default)]
+    pub use_llm: bool,
+    #[serde(default)]
+    pub use_local_whisper: Option<bool>,
+    #[serde(default)]
+    pub whisper_model: Option<String>,
+    #[serde(default)]
+    pub use_local_ai: Option<bool>,
+    #[serde(default)]
+    pub ollama_model: Option<String>,
+    #[serde(default)]
+    pub ollama_base_url: Option<String>,
+}
+
+/// Where the chapter boundaries came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BoundarySource {
+    Given,
+    Embedded,
+    Silence,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposedChapter {
+    /// Titled with the spoken heading, or the previous title if none was heard.
+    #[serde(flatten)]
+    pub chapter: Chapter,
+    pub previous_title: String,
+    /// What Whisper heard at the start of the chapter.
+    pub heard: String,
+    /// "regex" or "llm"; unset when the title was kept.
+    pub method: Option<String>,
+    pub confidence: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChapterTitles {
+    pub source: BoundarySource,
+    pub chapters: Vec<ProposedChapter>,
+    /// Chapters given a spoken heading.
+    pub named: usize,
+}
+
+/// A heading read from a transcript, normalised ("Chapter 12: The Storm").
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heading {
+    pub title: String,
+    pub confidence: f32,
+}
+
+/// Transcribe the start of every chapter and propose titles from what the
+/// narrator announces.
+pub async fn propose_chapter_titles(request: &ChapterTitleRequest, progress: &dyn ProgressSink) -> Result<ChapterTitles, AppError> {
+    let (request, openai_api_key) = with_saved_config(request.clone());
+    if request.files.is_empty() {
+        return Err(AppError::invalid_input("No audio files to name chapters from"));
+    }
+    if !whisper::check_ffmpeg_available() {
+        return Err(AppError::ffmpeg_missing());
+    }
+
+    let mut durations = Vec::with_capacity(request.files.len());
+    for file in &request.files {
+        let ms = crate::tags::audio_duration_ms(Path::new(file)).map_err(|e| {
+            AppError::new(ErrorKind::Io, format!("Could not read the length of {}", file)).with_detail(e)
+        })?;
+        durations.push(ms);
+    }
+    let (source, book_chapters) = {
+        let (request, durations) = (request.clone(), durations.clone());
+        whisper::run_blocking(move || find_boundaries(&request, &durations)).await?
+    };
+    if book_chapters.is_empty() {
+        return Err(AppError::invalid_input("No chapter boundaries found"));
+    }
+
+    let offsets = transcribe::file_offsets(&durations);
+    let window_secs = request.window_secs.unwrap_or(DEFAULT_WINDOW_SECS).max(1);
+    let transcription = TranscribeRequest {
+        files: request.files.clone(),
+        use_local_whisper: request.use_local_whisper,
+        whisper_model: request.whisper_model.clone(),
+        ..Default::default()
+    };
+    let llm = LlmSettings {
+        use_local_ai: request.use_local_ai.unwrap_or(false),
+        ollama_model: request.ollama_model.as_deref(),
+        ollama_base_url: request.ollama_base_url.as_deref(),
+        openai_api_key: openai_api_key.as_deref(),
+    };
+
+    let total = book_chapters.len();
+    let mut proposed = Vec::with_capacity(total);
+    for (i, chapter) in book_chapters.into_iter().enumerate() {
+        progress.report(ProgressEvent::transcription(
+            Stage::Transcribing,
+            i,
+            total,
+            format!("Listening to chapter {} of {}", i + 1, total),
+        ));
+        let heard = match window_chunk(chapter.start_ms, &durations, &offsets, window_secs) {
+            Some(chunk) => {
+                let (segments, _) = transcribe::transcribe_chunk(&transcription, &chunk, openai_api_key.as_deref()).await?;
+                segments.iter().map(|s| s.text.trim()).collect::<Vec<_>>().join(" ")
+            }
+            None => String::new(),
+        };
+
+        let mut found = parse_heading(&heard).map(|h| (h, "regex"));
+        if found.is_none() && request.use_llm && !heard.is_empty() {
+            match ask_llm_heading(&heard, &llm).await {
+                Ok(heading) => found = heading.map(|h| (h, "llm")),
+                Err(e) => progress.report(ProgressEvent::transcription(
+                    Stage::Parsing,
+                    i,
+                    total,
+                    format!("LLM heading parse failed for chapter {}, keeping its title: {}", i + 1, e.message),
+                )),
+            }
+        }
+
+        let previous_title = chapter.title.clone();
+        let (title, method, confidence) = match found {
+            Some((heading, method)) => (heading.title, Some(method.to_string()), heading.confidence),
+            None => (chapter.title.clone(), None, 0.0),
+        };
+        proposed.push(ProposedChapter {
+            chapter: Chapter { title, ..chapter },
+            previous_title,
+            heard,
+            method,
+            confidence,
+        });
+    }
+
+    let named = proposed.iter().filter(|c| c.method.is_some()).count();
+    progress.report(ProgressEvent::transcription(
+        Stage::Complete, total, total,
+        format!("Found headings for {} of {} chapters", named, total),
+    ));
+    Ok(ChapterTitles { source, chapters: proposed, named })
+}
+
+/// Fill transcription and LLM settings the caller left out from the saved
+/// config. The OpenAI key comes from the secret store only, since requests
+/// are saved with their jobs.
+fn with_saved_config(mut request: ChapterTitleRequest) -> (ChapterTitleRequest, Option<String>) {
+    let config = AppConfig::load_or_default();
+    request.use_local_whisper = request.use_local_whisper.or(config.use_local_whisper);
+    request.whisper_model = request.whisper_model.take().or(config.whisper_model);
+    request.use_local_ai = request.use_local_ai.or(config.use_local_ai);
+    request.ollama_model = request.ollama_model.take().or(config.ollama_model);
+    request.ollama_base_url = request.ollama_base_url.take().or(config.ollama_base_url);
+    (request, get_secret(Secret::OpenaiApiKey))
+}
+
+/// The request's chapters, a single file's markers, or chapters between
+/// long silences (every file start counts as one).
+fn find_boundaries(request: &ChapterTitleRequest, durations_ms: &[u64]) -> Result<(BoundarySource, Vec<Chapter>), AppError> {
+    if !request.detect_silence {
+        if !request.chapters.is_empty() {
+            return Ok((BoundarySource::Given, request.chapters.clone()));
+        }
+        if let [file] = request.files.as_slice() {
+            let embedded = chapters::read_chapters(Path::new(file))?;
+            if embedded.len() > 1 {
+                return Ok((BoundarySource::Embedded, embedded));
+            }
+        }
+    }
+
+    let noise_db = request.noise_db.unwrap_or(DEFAULT_NOISE_DB);
+    let min_silence = request.min_silence_secs.unwrap_or(DEFAULT_MIN_SILENCE_SECS);
+    let mut boundaries = Vec::new();
+    for (file, offset) in request.files.iter().zip(transcribe::file_offsets(durations_ms)) {
+        boundaries.push(offset);
+        boundaries.extend(chapters::detect_silences(Path::new(file), noise_db, min_silence)?.into_iter().map(|ms| offset + ms));
+    }
+    let min_chapter_ms = u64::from(request.min_chapter_secs.unwrap_or(DEFAULT_MIN_CHAPTER_SECS)) * 1000;
+    let total_ms = durations_ms.iter().sum();
+    Ok((BoundarySource::Silence, chapters::chapters_from_boundaries(&boundaries, total_ms, min_chapter_ms)))
+}
+
+/// The stretch of audio to transcribe for a chapter starting at `start_ms`
+/// on the book's timeline, cut short at the end of its file.
+fn window_chunk(start_ms: u64, durations_ms: &[u64], offsets: &[u64], window_secs: u32) -> Option<Chunk> {
+    let at = start_ms.saturating_sub(LEAD_IN_MS);
+    let file = offsets.iter().rposition(|&o| o <= at)?;
+    let within = at - offsets[file];
+    if within >= durations_ms[file] {
+        return None;
+    }
+    let start_secs = (within / 1000) as u32;
+    let left = (durations_ms[file] - u64::from(start_secs) * 1000).div_ceil(1000) as u32;
+    Some(Chunk {
+        file,
+        start_secs,
+        duration_secs: window_secs.min(left),
+        offset_ms: offsets[file] + u64::from(start_secs) * 1000,
+    })
+}
+
+// ---- Heading parsing ----
+
+/// The first spoken heading in a transcript, e.g. "Chapter twenty-one. The
+/// Storm." becomes "Chapter 21: The Storm".
+pub fn parse_heading(transcript: &str) -> Option<Heading> {
+    HEADING_WORD.find_iter(transcript).find_map(|m| read_heading(transcript, m))
+}
+
+fn read_heading(text: &str, keyword: Match<'_>) -> Option<Heading> {
+    let word = keyword.as_str().to_lowercase();
+    let rest = &text[keyword.end()..];
+    let (label, rest, confidence) = if NUMBERED.contains(&word.as_str()) {
+        let (n, used) = leading_number(rest)?;
+        (format!("{} {}", capitalize(&word), n), &rest[used..], 0.9)
+    } else {
+        (capitalize(&word), rest, 0.75)
+    };
+
+    // "Part Two. Chapter Five." is one heading
+    let next = rest.trim_start_matches(|c: char| c.is_whitespace() || ".,:;!?-\u{2013}\u{2014}".contains(c));
+    if let Some(inner) = HEADING_WORD.find(next).filter(|m| m.start() == 0).and_then(|m| read_heading(next, m)) {
+        return Some(Heading { title: format!("{}, {}", label, inner.title), confidence });
+    }
+    let title = match subtitle(rest) {
+        Some(sub) => format!("{}: {}", label, sub),
+        None => label,
+    };
+    Some(Heading { title, confidence })
+}
+
+/// The longest run of number words (or digits, or a Roman numeral) right
+/// after a keyword, and how many bytes of `rest` it covers.
+fn leading_number(rest: &str) -> Option<(u32, usize)> {
+    let mut words = Vec::new();
+    let mut end = 0;
+    let mut best = None;
+    for token in TOKEN.find_iter(rest).take(6) {
+        let gap = &rest[end..token.start()];
+        if gap.is_empty() || !gap.chars().all(|c| c.is_whitespace() || c == '-') {
+            break;
+        }
+        words.push(token.as_str());
+        end = token.end();
+        if let Some(n) = parse_number(&words) {
+            best = Some((n, end));
+        }
+    }
+    best
+}
+
+/// "twenty one" -> 21, "one hundred and three" -> 103, "12" -> 12,
+/// "twelfth" -> 12, "XIV" -> 14.
+pub fn parse_number(words: &[&str]) -> Option<u32> {
+    if let [word] = words {
+        if let Ok(n) = word.parse() {
+            return Some(n);
+        }
+        if let Some(n) = roman(word) {
+            return Some(n);
+        }
+        const ORDINALS: [&str; 12] = [
+            "first", "second", "third", "fourth", "fifth", "sixth",
+            "seventh", "eighth", "ninth", "tenth", "eleventh", "twelfth",
+        ];
+        if let Some(i) = ORDINALS.iter().position(|o| word.eq_ignore_ascii_case(o)) {
+            return Some(i as u32 + 1);
+        }
+    }
+
+    let words: Vec<String> = words.iter().map(|w| w.to_lowercase()).collect();
+    let (head, used) = below_hundred(&words)?;
+    let rest = &words[used..];
+    match rest {
+        [] => Some(head),
+        [hundred, tail @ ..] if hundred == "hundred" && head < 10 => {
+            let tail = match tail {
+                [and, more @ ..] if and == "and" && !more.is_empty() => more,
+                _ => tail,
+            };
+            if tail.is_empty() {
+                return Some(head * 100);
+            }
+            let (low, used) = below_hundred(tail)?;
+            (used == tail.len()).then_some(head * 100 + low)
+        }
+        _ => None,
+    }
+}
+
+/// 1 to 99 from the start of `words`, and how many words it took.
+fn below_hundred(words: &[String]) -> Option<(u32, usize)> {
+    const SMALL: [&str; 19] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+        "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+    ];
+    const TENS: [&str; 8] = ["twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+    let small = |w: &str| SMALL.iter().position(|s| *s == w).map(|i| i as u32 + 1);
+
+    let first = words.first()?;
+    if let Some(t) = TENS.iter().position(|s| s == first) {
+        let tens = (t as u32 + 2) * 10;
+        return match words.get(1).and_then(|w| small(w)) {
+            Some(unit) if unit < 10 => Some((tens + unit, 2)),
+            _ => Some((tens, 1)),
+        };
+    }
+    small(first).map(|n| (n, 1))
+}
+
+/// Upper-case Roman numerals up to L, as Whisper writes "Part IV".
+fn roman(word: &str) -> Option<u32> {
+    if word.is_empty() || !word.chars().all(|c| matches!(c, 'I' | 'V' | 'X' | 'L')) {
+        return None;
+    }
+    let value = |c: char| match c {
+        'I' => 1,
+        'V' => 5,
+        'X' => 10,
+        _ => 50,
+    };
+    let digits: Vec<u32> = word.chars().map(value).collect();
+    let n = digits
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| if digits.get(i + 1).is_some_and(|&next| next > d) { -(d as i32) } else { d as i32 })
+        .sum::<i32>();
+    // Only the canonical spelling, so stray capitals don't become numbers
+    (n > 0 && to_roman(n as u32) == word).then_some(n as u32)
+}
+
+fn to_roman(mut n: u32) -> String {
+    let mut out = String::new();
+    for (value, numeral) in [(50, "L"), (40, "XL"), (10, "X"), (9, "IX"), (5, "V"), (4, "IV"), (1, "I")] {
+        while n >= value {
+            out.push_str(numeral);
+            n -= value;
+        }
+    }
+    out
+}
+
+/// The heading's name after the number: text after a colon or dash, or a
+/// following short sentence written like a title ("Chapter One. The Storm.").
+fn subtitle(rest: &str) -> Option<String> {
+    let rest = rest.trim_start();
+    let (explicit, body) = match rest.chars().next() {
+        Some(c @ (':' | '-' | '\u{2013}' | '\u{2014}')) => (true, &rest[c.len_utf8()..]),
+        Some(c @ ('.' | ',' | ';' | '!' | '?')) => (false, &rest[c.len_utf8()..]),
+        _ => (false, rest),
+    };
+    let body = body.trim_start();
+    let end = body.find(['.', '!', '?']).unwrap_or(body.len());
+    let candidate = body[..end].trim().trim_matches(|c: char| matches!(c, '"' | '\'' | ',' | ';'));
+    let words = candidate.split_whitespace().count();
+    let fits = if explicit { (1..=10).contains(&words) } else { (1..=8).contains(&words) && looks_like_title(candidate) };
+    fits.then(|| candidate.to_string())
+}
+
+/// Capitalised like a title: the first word and every longer word.
+fn looks_like_title(text: &str) -> bool {
+    const SMALL_WORDS: [&str; 6] = ["with", "from", "into", "over", "upon", "that"];
+    let capital = |w: &str| w.chars().next().is_some_and(char::is_uppercase);
+    let mut words = text.split_whitespace();
+    words.next().is_some_and(capital)
+        && words.all(|w| w.chars().count() <= 3 || SMALL_WORDS.contains(&w) || capital(w))
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map_or_else(String::new, |first| first.to_uppercase().chain(chars).collect())
+}
+
+/// Ask the LLM for a heading the patterns missed.
+async fn ask_llm_heading(heard: &str, settings: &LlmSettings<'_>) -> Result<Option<Heading>, AppError> {
+    let system_prompt = "You find chapter headings in audiobook transcripts. Return only valid JSON.";
+    let user_prompt = format!(
+        r#"This transcript is the first seconds of an audiobook chapter. If the narrator announces a chapter heading (a number, a name, or both), return it.
+
+Return ONLY a JSON object:
+{{ "heading": "the heading as announced, or null" }}
+
+RULES:
+- Only a heading the narrator actually says; do NOT summarise the text
+- Keep the chapter's name if one is announced (e.g. "Chapter Three: The Storm")
+- Fix phonetic misspellings from speech-to-text
+
+Transcript: "{}""#,
+        heard
+    );
+    let parsed = whisper::ask_llm(system_prompt, &user_prompt, settings).await?;
+    let heading = parsed["heading"].as_str().map(str::trim).filter(|s| !s.is_empty() && *s != "null" && s.len() <= 120);
+    Ok(heading.map(|h| Heading {
+        title: parse_heading(h).map_or_else(|| h.to_string(), |parsed| parsed.title),
+        confidence: 0.6,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn title(transcript: &str) -> Option<String> {
+        parse_heading(transcript).map(|h| h.title)
+    }
+
+    #[test]
+    fn reads_spoken_headings() {
+        assert_eq!(title("Chapter Twelve. The Storm. The wind had been rising all afternoon.").as_deref(), Some("Chapter 12: The Storm"));
+        assert_eq!(title("chapter twenty-one, in which we meet the captain").as_deref(), Some("Chapter 21"));
+        assert_eq!(title("Part Two. Chapter Five. Homecoming.").as_deref(), Some("Part 2, Chapter 5: Homecoming"));
+        assert_eq!(title("Chapter One Hundred and Three: Into the Woods").as_deref(), Some("Chapter 103: Into the Woods"));
+        assert_eq!(title("Chapter 7 - Night Falls").as_deref(), Some("Chapter 7: Night Falls"));
+        assert_eq!(title("Part IV").as_deref(), Some("Part 4"));
+        assert_eq!(title("Prologue. It was the best of times, it was the worst of times.").as_deref(), Some("Prologue"));
+        // End of the previous chapter still in the window
+        assert_eq!(title("and she never looked back. Chapter Three. Harbour Lights.").as_deref(), Some("Chapter 3: Harbour Lights"));
+    }
+
+    #[test]
+    fn ignores_keywords_that_are_not_headings() {
+        assert_eq!(title("She closed the book and walked out into the rain."), None);
+        assert_eq!(title("It was part of the plan all along."), None);
+        assert_eq!(title(""), None);
+    }
+
+    #[test]
+    fn normalises_number_words() {
+        let n = |s: &str| parse_number(&s.split([' ', '-']).collect::<Vec<_>>());
+        assert_eq!(n("twenty-one"), Some(21));
+        assert_eq!(n("Ninety Nine"), Some(99));
+        assert_eq!(n("one hundred"), Some(100));
+        assert_eq!(n("two hundred and twelve"), Some(212));
+        assert_eq!(n("twelfth"), Some(12));
+        assert_eq!(n("42"), Some(42));
+        assert_eq!(n("XIV"), Some(14));
+        assert_eq!(n("one one"), None);
+        assert_eq!(n("iv"), None);
+        assert_eq!(n("IIII"), None);
+    }
+
+    #[test]
+    fn places_windows_on_the_right_file() {
+        let durations = [600_000, 300_000];
+        let offsets = transcribe::file_offsets(&durations);
+        let at = |ms| window_chunk(ms, &durations, &offsets, 15).map(|c| (c.file, c.start_secs, c.duration_secs, c.offset_ms));
+        assert_eq!(at(0), Some((0, 0, 15, 0)));
+        assert_eq!(at(120_000), Some((0, 119, 15, 119_000)));
+        // Just before the end of the first file: cut short, not spilling over
+        assert_eq!(at(595_000), Some((0, 594, 6, 594_000)));
+        assert_eq!(at(600_700), Some((1, 0, 15, 600_000)));
+        assert_eq!(at(900_600), None);
+    }
+}
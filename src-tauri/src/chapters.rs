@@ -0,0 +1,462 @@
+// src-tauri/src/chapters.rs
+// Embedded chapter markers, read and written through FFmpeg's ffmetadata
+// format so only the ffmpeg binary (bundled or system) is needed. Also the
+// round trip with AudiobookShelf: pull an item's chapters, compare them with
+// the file's (or detected) chapters, push corrections back, or embed the ABS
+// list in the local file.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::abs::{self, AbsClient};
+use crate::error::{AppError, ErrorKind};
+use crate::library::Chapter;
+
+/// Chapter starts this close together count as the same boundary.
+const DEFAULT_TOLERANCE_MS: u64 = 2000;
+
+/// Chapters embedded in an audio file (M4B chapter atoms, ID3 CHAP frames, ...).
+pub fn read_chapters(path: &Path) -> Result<Vec<Chapter>, String> {
+    let ffmpeg = crate::whisper_local::find_ffmpeg_binary()
+        .ok_or("FFmpeg is not installed. Install it with: brew install ffmpeg")?;
+
+    let output = Command::new(ffmpeg)
+        .arg("-v").arg("error")
+        .arg("-i").arg(path)
+        .args(["-f", "ffmetadata", "-"])
+        .output()
+        .map_err(|e| format!("FFmpeg error: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("FFmpeg failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(parse_ffmetadata_chapters(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Replace the chapter markers in `path` with `chapters`, keeping the audio
+/// and all other tags. The file is rewritten beside the original and swapped
+/// in only once FFmpeg succeeds.
+pub fn write_chapters(path: &Path, chapters: &[Chapter]) -> Result<(), AppError> {
+    let duration_ms = crate::tags::audio_duration_ms(path)
+        .map_err(|e| AppError::new(ErrorKind::InvalidInput, "Could not read the file's duration").with_detail(e))?;
+    // A zero duration means the container doesn't say, not an empty file
+    validate_chapters(chapters, Some(duration_ms).filter(|&d| d > 0))?;
+    let ffmpeg = crate::whisper_local::find_ffmpeg_binary().ok_or_else(AppError::ffmpeg_missing)?;
+
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let meta = tempfile::Builder::new().suffix(".txt").tempfile()?;
+    std::fs::write(meta.path(), to_ffmetadata(chapters))?;
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("m4b");
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("audiobook");
+    let out: PathBuf = dir.join(format!(".{}.chapters.{}", stem, ext));
+
+    let output = Command::new(ffmpeg)
+        .args(["-v", "error", "-y"])
+        .arg("-i").arg(path)
+        .arg("-i").arg(meta.path())
+        .args(["-map", "0", "-map_metadata", "0", "-map_chapters", "1", "-c", "copy"])
+        .arg(&out)
+        .output()?;
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&out);
+        return Err(AppError::new(ErrorKind::FfmpegFailed, "Could not write chapters to the file")
+            .with_detail(String::from_utf8_lossy(&output.stderr)));
+    }
+    std::fs::rename(&out, path)?;
+    Ok(())
+}
+
+fn to_ffmetadata(chapters: &[Chapter]) -> String {
+    let mut text = String::from(";FFMETADATA1\n");
+    for c in chapters {
+        text.push_str(&format!(
+            "[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n",
+            c.start_ms,
+            c.end_ms,
+            escape_ffmetadata(&c.title)
+        ));
+    }
+    text
+}
+
+/// Check chapters are usable: titled, in order, not overlapping, and within
+/// `duration_ms` when known.
+pub fn validate_chapters(chapters: &[Chapter], duration_ms: Option<u64>) -> Result<(), AppError> {
+    if chapters.is_empty() {
+        return Err(AppError::invalid_input("No chapters to save"));
+    }
+    for (i, c) in chapters.iter().enumerate() {
+        let n = i + 1;
+        if c.end_ms <= c.start_ms {
+            return Err(AppError::invalid_input(format!("Chapter {} ends before it starts", n)));
+        }
+        if i > 0 && c.start_ms < chapters[i - 1].end_ms {
+            return Err(AppError::invalid_input(format!("Chapter {} overlaps the one before it", n)));
+        }
+        if duration_ms.is_some_and(|d| c.start_ms >= d) {
+            return Err(AppError::invalid_input(format!("Chapter {} starts after the end of the book", n)));
+        }
+    }
+    Ok(())
+}
+
+// ---- Silence detection ----
+
+/// Where each silence of at least `min_silence_secs` quieter than `noise_db`
+/// ends, in milliseconds from the start of the file: where speech resumes.
+pub fn detect_silences(path: &Path, noise_db: f32, min_silence_secs: f32) -> Result<Vec<u64>, AppError> {
+    let ffmpeg = crate::whisper_local::find_ffmpeg_binary().ok_or_else(AppError::ffmpeg_missing)?;
+    let output = Command::new(ffmpeg)
+        .args(["-hide_banner", "-nostats"])
+        .arg("-i").arg(path)
+        .arg("-af").arg(format!("silencedetect=noise={}dB:d={}", noise_db, min_silence_secs))
+        .args(["-vn", "-f", "null", "-"])
+        .output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(AppError::new(ErrorKind::FfmpegFailed, "Could not scan the file for silences").with_detail(stderr));
+    }
+    Ok(parse_silence_ends(&stderr))
+}
+
+/// `silence_end: 12.345 | silence_duration: 2.1` lines of silencedetect output.
+fn parse_silence_ends(stderr: &str) -> Vec<u64> {
+    stderr
+        .lines()
+        .filter_map(|line| line.split_once("silence_end:"))
+        .filter_map(|(_, rest)| rest.split('|').next()?.trim().parse::<f64>().ok())
+        .map(|secs| (secs.max(0.0) * 1000.0).round() as u64)
+        .collect()
+}
+
+/// Chapters starting at each boundary on a `total_ms` timeline, numbered in
+/// order. Boundaries closer than `min_chapter_ms` to the previous one are
+/// dropped, and so is a final chapter shorter than that.
+pub fn chapters_from_boundaries(boundaries: &[u64], total_ms: u64, min_chapter_ms: u64) -> Vec<Chapter> {
+    let mut sorted: Vec<u64> = boundaries.iter().copied().filter(|&b| b < total_ms).collect();
+    sorted.sort_unstable();
+    let mut starts = vec![0u64];
+    for b in sorted {
+        if b >= starts[starts.len() - 1] + min_chapter_ms && total_ms - b >= min_chapter_ms {
+            starts.push(b);
+        }
+    }
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start_ms)| Chapter {
+            title: format!("Chapter {}", i + 1),
+            start_ms,
+            end_ms: starts.get(i + 1).copied().unwrap_or(total_ms),
+        })
+        .collect()
+}
+
+// ---- Comparison ----
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChapterRow {
+    pub index: usize,
+    pub abs: Option<Chapter>,
+    pub local: Option<Chapter>,
+    /// Local start minus ABS start, when both exist.
+    pub start_delta_ms: Option<i64>,
+    pub title_differs: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChapterComparison {
+    /// Same count, starts within tolerance and same titles.
+    pub matches: bool,
+    pub rows: Vec<ChapterRow>,
+}
+
+/// Line the two lists up by position and note where they disagree.
+pub fn compare_chapters(abs: &[Chapter], local: &[Chapter], tolerance_ms: u64) -> ChapterComparison {
+    let rows: Vec<ChapterRow> = (0..abs.len().max(local.len()))
+        .map(|i| {
+            let (a, l) = (abs.get(i), local.get(i));
+            let start_delta_ms = a.zip(l).map(|(a, l)| l.start_ms as i64 - a.start_ms as i64);
+            let title_differs = a.zip(l).is_some_and(|(a, l)| a.title.trim() != l.title.trim());
+            ChapterRow { index: i, abs: a.cloned(), local: l.cloned(), start_delta_ms, title_differs }
+        })
+        .collect();
+    let matches = abs.len() == local.len()
+        && rows.iter().all(|r| !r.title_differs && r.start_delta_ms.is_some_and(|d| d.unsigned_abs() <= tolerance_ms));
+    ChapterComparison { matches, rows }
+}
+
+pub fn from_abs(chapters: &[abs::Chapter]) -> Vec<Chapter> {
+    chapters
+        .iter()
+        .map(|c| Chapter {
+            title: c.title.clone(),
+            start_ms: (c.start.max(0.0) * 1000.0).round() as u64,
+            end_ms: (c.end.max(0.0) * 1000.0).round() as u64,
+        })
+        .collect()
+}
+
+pub fn to_abs(chapters: &[Chapter]) -> Vec<abs::Chapter> {
+    chapters
+        .iter()
+        .enumerate()
+        .map(|(i, c)| abs::Chapter {
+            id: i as u32,
+            start: c.start_ms as f64 / 1000.0,
+            end: c.end_ms as f64 / 1000.0,
+            title: c.title.clone(),
+        })
+        .collect()
+}
+
+// ---- Tauri commands ----
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AbsChapters {
+    /// In ABS's own shape (seconds), as the chapters tab shows them.
+    pub chapters: Vec<abs::Chapter>,
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub async fn get_abs_chapters(abs_id: String) -> Result<AbsChapters, AppError> {
+    let chapters = AbsClient::from_config()?.chapters(&abs_id).await?;
+    Ok(AbsChapters { chapters })
+}
+
+/// Compare an ABS item's chapters with `chapters` (e.g. silence detection
+/// output) or, if none are given, with those embedded in `file_path`.
+#[cfg_attr(feature = "gui", tauri::command)]
+pub async fn compare_abs_chapters(
+    abs_id: String,
+    file_path: Option<String>,
+    chapters: Option<Vec<Chapter>>,
+    tolerance_ms: Option<u64>,
+) -> Result<ChapterComparison, AppError> {
+    let local = match (chapters, file_path) {
+        (Some(chapters), _) => chapters,
+        (None, Some(path)) => tokio::task::spawn_blocking(move || read_chapters(Path::new(&path)))
+            .await
+            .map_err(|e| AppError::from(e.to_string()))??,
+        (None, None) => return Err(AppError::invalid_input("Give chapters or a file to compare with")),
+    };
+    let abs = AbsClient::from_config()?.chapters(&abs_id).await?;
+    Ok(compare_chapters(&from_abs(&abs), &local, tolerance_ms.unwrap_or(DEFAULT_TOLERANCE_MS)))
+}
+
+/// Replace an ABS item's chapters. Returns how many were saved.
+#[cfg_attr(feature = "gui", tauri::command)]
+pub async fn push_abs_chapters(abs_id: String, chapters: Vec<Chapter>) -> Result<usize, AppError> {
+    let client = AbsClient::from_config()?;
+    let duration_ms = client.item(&abs_id).await?.media.duration.map(|d| (d * 1000.0) as u64);
+    validate_chapters(&chapters, duration_ms)?;
+    client.update_chapters(&abs_id, &to_abs(&chapters)).await?;
+    Ok(chapters.len())
+}
+
+/// Write an ABS item's chapters into the local file so both agree. Only for
+/// single-file books: ABS places chapters on the whole book's timeline.
+#[cfg_attr(feature = "gui", tauri::command)]
+pub async fn embed_abs_chapters(abs_id: String, file_path: String) -> Result<usize, AppError> {
+    let item = AbsClient::from_config()?.item(&abs_id).await?;
+    let chapters = single_file_chapters(&item)?;
+    let count = chapters.len();
+    tokio::task::spawn_blocking(move || write_chapters(Path::new(&file_path), &chapters))
+        .await
+        .map_err(|e| AppError::from(e.to_string()))??;
+    Ok(count)
+}
+
+/// An item's chapters, if they all belong to its one audio file.
+fn single_file_chapters(item: &abs::LibraryItem) -> Result<Vec<Chapter>, AppError> {
+    let files = item.media.audio_files.len();
+    if files > 1 {
+        return Err(AppError::invalid_input(format!(
+            "This book has {} audio files; ABS chapters can only be embedded in a single-file book",
+            files
+        ))
+        .with_hint("Merge the files into one M4B first, or edit each file's chapters separately"));
+    }
+    Ok(from_abs(&item.media.chapters))
+}
+
+/// Parse the `[CHAPTER]` sections of an ffmetadata document.
+fn parse_ffmetadata_chapters(text: &str) -> Vec<Chapter> {
+    struct Section {
+        timebase: (u64, u64),
+        start: u64,
+        end: u64,
+        title: String,
+    }
+
+    let mut sections: Vec<Section> = Vec::new();
+    let mut in_chapter = false;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_chapter = line.eq_ignore_ascii_case("[CHAPTER]");
+            if in_chapter {
+                sections.push(Section { timebase: (1, 1000), start: 0, end: 0, title: String::new() });
+            }
+            continue;
+        }
+        if !in_chapter {
+            continue;
+        }
+        let (Some(section), Some((key, value))) = (sections.last_mut(), line.split_once('=')) else {
+            continue;
+        };
+        match key.to_ascii_uppercase().as_str() {
+            "TIMEBASE" => {
+                if let Some((n, d)) = value.split_once('/') {
+                    if let (Ok(n), Ok(d)) = (n.parse(), d.parse()) {
+                        if d > 0 {
+                            section.timebase = (n, d);
+                        }
+                    }
+                }
+            }
+            "START" => section.start = value.parse().unwrap_or(0),
+            "END" => section.end = value.parse().unwrap_or(0),
+            "TITLE" => section.title = unescape_ffmetadata(value),
+            _ => {}
+        }
+    }
+
+    sections
+        .into_iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let to_ms = |t: u64| t * s.timebase.0 * 1000 / s.timebase.1;
+            Chapter {
+                title: if s.title.is_empty() { format!("Chapter {}", i + 1) } else { s.title },
+                start_ms: to_ms(s.start),
+                end_ms: to_ms(s.end),
+            }
+        })
+        .collect()
+}
+
+/// ffmetadata escapes `=`, `;`, `#`, `\` and newlines with a backslash.
+fn unescape_ffmetadata(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(next) = chars.next() {
+                out.push(next);
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn escape_ffmetadata(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '=' | ';' | '#' | '\\' | '\n') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_chapter_sections() {
+        let text = ";FFMETADATA1\n\
+            title=The Way of Kings\n\
+            [CHAPTER]\nTIMEBASE=1/1000\nSTART=0\nEND=61500\ntitle=Prelude\n\
+            [CHAPTER]\nTIMEBASE=1/44100\nSTART=2712150\nEND=4410000\ntitle=Chapter 1\\: Stormblessed\n\
+            [CHAPTER]\nTIMEBASE=1/1000\nSTART=100000\nEND=120000\n";
+        let chapters = parse_ffmetadata_chapters(text);
+        assert_eq!(chapters.len(), 3);
+        assert_eq!(chapters[0].title, "Prelude");
+        assert_eq!((chapters[0].start_ms, chapters[0].end_ms), (0, 61500));
+        assert_eq!(chapters[1].title, "Chapter 1: Stormblessed");
+        assert_eq!((chapters[1].start_ms, chapters[1].end_ms), (61500, 100000));
+        assert_eq!(chapters[2].title, "Chapter 3");
+    }
+
+    #[test]
+    fn ffmetadata_round_trips_escaped_titles() {
+        let chapters = vec![
+            Chapter { title: "Part 1; Chapter #1 = Start".into(), start_ms: 0, end_ms: 5_000 },
+            Chapter { title: "Back\\slash".into(), start_ms: 5_000, end_ms: 9_500 },
+        ];
+        assert_eq!(parse_ffmetadata_chapters(&to_ffmetadata(&chapters)), chapters);
+    }
+
+    #[test]
+    fn turns_silences_into_chapter_boundaries() {
+        let stderr = "[silencedetect @ 0x1] silence_start: 58.1\n\
+            [silencedetect @ 0x1] silence_end: 61.25 | silence_duration: 3.15\n\
+            size=N/A time=00:10:00.00 bitrate=N/A\n\
+            [silencedetect @ 0x1] silence_end: 90 | silence_duration: 2\n\
+            [silencedetect @ 0x1] silence_end: 400.5 | silence_duration: 2.4\n";
+        let ends = parse_silence_ends(stderr);
+        assert_eq!(ends, vec![61_250, 90_000, 400_500]);
+
+        // 90s is too soon after 61.25s; nothing new may start in the last minute
+        let chapters = chapters_from_boundaries(&[400_500, 61_250, 90_000, 470_000], 500_000, 60_000);
+        let starts: Vec<_> = chapters.iter().map(|c| (c.start_ms, c.end_ms)).collect();
+        assert_eq!(starts, vec![(0, 61_250), (61_250, 400_500), (400_500, 500_000)]);
+        assert_eq!(chapters[2].title, "Chapter 3");
+    }
+
+    #[test]
+    fn compares_by_position_within_tolerance() {
+        let abs = from_abs(&[
+            abs::Chapter { id: 0, start: 0.0, end: 60.0, title: "Opening".into() },
+            abs::Chapter { id: 1, start: 60.0, end: 120.0, title: "Chapter 1".into() },
+        ]);
+        let close = vec![
+            Chapter { title: "Opening".into(), start_ms: 0, end_ms: 61_000 },
+            Chapter { title: "Chapter 1".into(), start_ms: 61_000, end_ms: 120_000 },
+        ];
+        assert!(compare_chapters(&abs, &close, 2000).matches);
+
+        let mut renamed = close.clone();
+        renamed[1].title = "Chapter One".into();
+        renamed.push(Chapter { title: "Epilogue".into(), start_ms: 120_000, end_ms: 130_000 });
+        let cmp = compare_chapters(&abs, &renamed, 2000);
+        assert!(!cmp.matches);
+        assert!(cmp.rows[1].title_differs);
+        assert_eq!(cmp.rows[1].start_delta_ms, Some(1000));
+        assert!(cmp.rows[2].abs.is_none());
+    }
+
+    #[test]
+    fn rejects_overlapping_or_out_of_range_chapters() {
+        let ok = vec![
+            Chapter { title: "A".into(), start_ms: 0, end_ms: 10 },
+            Chapter { title: "B".into(), start_ms: 10, end_ms: 20 },
+        ];
+        assert!(validate_chapters(&ok, Some(20)).is_ok());
+        let overlap = vec![ok[0].clone(), Chapter { title: "B".into(), start_ms: 5, end_ms: 20 }];
+        assert!(validate_chapters(&overlap, None).is_err());
+        assert!(validate_chapters(&ok, Some(10)).is_err());
+        assert!(validate_chapters(&[], None).is_err());
+    }
+
+    #[test]
+    fn embeds_abs_chapters_only_in_single_file_books() {
+        let mut item = abs::LibraryItem::default();
+        item.media.chapters = vec![abs::Chapter { id: 0, start: 0.0, end: 61.5, title: "Prelude".into() }];
+        item.media.audio_files = vec![abs::AudioFile::default()];
+        assert_eq!(single_file_chapters(&item).unwrap()[0].end_ms, 61500);
+
+        item.media.audio_files.push(abs::AudioFile::default());
+        let err = single_file_chapters(&item).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::InvalidInput);
+        assert!(err.message.contains("2 audio files"));
+    }
+}
@@ -0,0 +1,443 @@
+// src-tauri/src/config.rs
+// Settings owned by the backend, stored as versioned JSON in the app data
+// dir. API keys and tokens never go in that file: they live in the OS
+// keyring (macOS Keychain, Windows Credential Manager) or, on Linux and
+// wherever the keyring is unavailable, in an AES-GCM encrypted file.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+
+use crate::error::{AppError, ErrorKind};
+use crate::whisper::IntroConcurrency;
+
+/// Bump when the stored layout changes, and teach `migrate` the old shape.
+pub const CONFIG_VERSION: u32 = 1;
+
+/// Credentials kept out of `config.json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Secret {
+    AbsApiToken,
+    OpenaiApiKey,
+    AnthropicApiKey,
+    TranscriptionApiKey,
+}
+
+impl Secret {
+    pub const ALL: [Secret; 4] =
+        [Secret::AbsApiToken, Secret::OpenaiApiKey, Secret::AnthropicApiKey, Secret::TranscriptionApiKey];
+
+    /// Field name in the frontend config, and the keyring account name.
+    pub fn key(self) -> &'static str {
+        match self {
+            Secret::AbsApiToken => "abs_api_token",
+            Secret::OpenaiApiKey => "openai_api_key",
+            Secret::AnthropicApiKey => "anthropic_api_key",
+            Secret::TranscriptionApiKey => "transcription_api_key",
+        }
+    }
+}
+
+/// Settings the backend reads. Everything else the frontend stores is kept
+/// in `extra` and handed back untouched.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abs_base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abs_library_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ollama_base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ollama_model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_local_ai: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_local_whisper: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub whisper_model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub whisper_server: Option<bool>,
+    /// OpenAI-compatible transcription endpoint, up to the API version
+    /// (e.g. `http://nas.local:8000/v1`); OpenAI when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcription_base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcription_model: Option<String>,
+    /// `verbose_json`, `json` or `text`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcription_response_format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scan_outro: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outro_minutes: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_concurrency: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cloud_concurrency: Option<usize>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl AppConfig {
+    /// The saved config, or None if nothing has been saved yet.
+    pub fn load() -> Result<Option<AppConfig>, AppError> {
+        load_from(&config_path()?, &secret_store()?)
+    }
+
+    /// The saved config, or defaults when missing or unreadable.
+    pub fn load_or_default() -> AppConfig {
+        Self::load().ok().flatten().unwrap_or_default()
+    }
+
+    pub fn concurrency(&self) -> IntroConcurrency {
+        let defaults = IntroConcurrency::default();
+        IntroConcurrency {
+            local_concurrency: self.local_concurrency.unwrap_or(defaults.local_concurrency),
+            cloud_concurrency: self.cloud_concurrency.unwrap_or(defaults.cloud_concurrency),
+        }
+    }
+
+    /// Non-empty ABS base URL, if configured.
+    pub fn abs_url(&self) -> Option<&str> {
+        self.abs_base_url.as_deref().map(str::trim).filter(|s| !s.is_empty())
+    }
+}
+
+/// Read a saved secret. Empty values count as unset.
+pub fn get_secret(secret: Secret) -> Option<String> {
+    secret_store().ok()?.get(secret).ok().flatten().filter(|s| !s.is_empty())
+}
+
+/// Secret changes from a save: None removes the secret.
+type SecretUpdates = Vec<(Secret, Option<String>)>;
+
+/// Bring a stored or frontend config up to `CONFIG_VERSION` and pull out
+/// any secrets in it. A secret set to "" or null means "remove it"; an
+/// absent one means "leave it alone".
+fn migrate(value: Value) -> Result<(AppConfig, SecretUpdates), AppError> {
+    let Value::Object(mut fields) = value else {
+        return Err(AppError::invalid_input("Config must be a JSON object"));
+    };
+
+    // Version 0 is the frontend's localStorage object with secrets inline.
+    // Secrets are split out at every version so they can never reach disk.
+    let mut secrets = Vec::new();
+    for secret in Secret::ALL {
+        if let Some(v) = fields.remove(secret.key()) {
+            let v = v.as_str().map(str::trim).filter(|s| !s.is_empty()).map(str::to_string);
+            secrets.push((secret, v));
+        }
+    }
+    fields.insert("version".to_string(), Value::from(CONFIG_VERSION));
+
+    let config = serde_json::from_value(Value::Object(fields))
+        .map_err(|e| AppError::invalid_input("Invalid settings").with_detail(e.to_string()))?;
+    Ok((config, secrets))
+}
+
+fn load_from(path: &Path, store: &SecretStore) -> Result<Option<AppConfig>, AppError> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let value: Value = serde_json::from_str(&text)?;
+    let old_version = value.get("version").and_then(Value::as_u64).unwrap_or(0);
+    let (config, secrets) = migrate(value)?;
+
+    // Rewrite older files so secrets found in them leave the plain file.
+    if old_version < CONFIG_VERSION as u64 || !secrets.is_empty() {
+        save_to(path, store, &config, &secrets)?;
+    }
+    Ok(Some(config))
+}
+
+fn save_to(
+    path: &Path,
+    store: &SecretStore,
+    config: &AppConfig,
+    secrets: &[(Secret, Option<String>)],
+) -> Result<(), AppError> {
+    for (secret, value) in secrets {
+        store.set(*secret, value.as_deref())?;
+    }
+    let json = serde_json::to_string_pretty(config)?;
+    write_private(path, json.as_bytes())
+}
+
+/// The config as the frontend sees it: settings plus secrets.
+fn to_frontend(config: &AppConfig, store: &SecretStore) -> Result<Value, AppError> {
+    let mut value = serde_json::to_value(config)?;
+    for secret in Secret::ALL {
+        if let Some(v) = store.get(secret)? {
+            value[secret.key()] = Value::String(v);
+        }
+    }
+    Ok(value)
+}
+
+// ---- Secret storage ----
+
+/// Keyring where the platform has one, with the encrypted file as fallback.
+struct SecretStore {
+    file: SecretFile,
+    use_keyring: bool,
+}
+
+impl SecretStore {
+    fn get(&self, secret: Secret) -> Result<Option<String>, AppError> {
+        if self.use_keyring {
+            if let Ok(Some(v)) = keyring_store::get(secret.key()) {
+                return Ok(Some(v));
+            }
+        }
+        Ok(self.file.load()?.remove(secret.key()))
+    }
+
+    fn set(&self, secret: Secret, value: Option<&str>) -> Result<(), AppError> {
+        if self.use_keyring && keyring_store::set(secret.key(), value).is_ok() {
+            // Don't leave an older copy behind in the file
+            let mut all = self.file.load()?;
+            if all.remove(secret.key()).is_some() {
+                self.file.save(&all)?;
+            }
+            return Ok(());
+        }
+        let mut all = self.file.load()?;
+        match value {
+            Some(v) => all.insert(secret.key().to_string(), v.to_string()),
+            None => all.remove(secret.key()),
+        };
+        self.file.save(&all)
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+mod keyring_store {
+    const SERVICE: &str = "com.audiobook.tagger.v2";
+
+    pub fn get(name: &str) -> Result<Option<String>, String> {
+        match keyring::Entry::new(SERVICE, name).and_then(|e| e.get_password()) {
+            Ok(v) => Ok(Some(v)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn set(name: &str, value: Option<&str>) -> Result<(), String> {
+        let entry = keyring::Entry::new(SERVICE, name).map_err(|e| e.to_string())?;
+        match value {
+            Some(v) => entry.set_password(v).map_err(|e| e.to_string()),
+            None => match entry.delete_credential() {
+                Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+                Err(e) => Err(e.to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod keyring_store {
+    // Linux desktops differ too much in whether a Secret Service is running;
+    // the encrypted file is used instead.
+    pub fn get(_name: &str) -> Result<Option<String>, String> {
+        Err("No keyring".to_string())
+    }
+
+    pub fn set(_name: &str, _value: Option<&str>) -> Result<(), String> {
+        Err("No keyring".to_string())
+    }
+}
+
+/// Secrets as an encrypted JSON map. The key sits beside it in a file only
+/// the user can read, which keeps tokens out of backups and shared config
+/// dumps; it is not a defence against someone logged in as the user.
+struct SecretFile {
+    path: PathBuf,
+    key_path: PathBuf,
+}
+
+const NONCE_LEN: usize = 12;
+
+impl SecretFile {
+    fn in_dir(dir: &Path) -> Self {
+        SecretFile { path: dir.join("secrets.enc"), key_path: dir.join("secrets.key") }
+    }
+
+    fn load(&self) -> Result<HashMap<String, String>, AppError> {
+        let data = match std::fs::read(&self.path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let key = std::fs::read(&self.key_path).map_err(|e| secrets_unreadable(e.to_string()))?;
+        if data.len() < NONCE_LEN || key.len() != 32 {
+            return Err(secrets_unreadable("Secret file is truncated"));
+        }
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key));
+        let plain = cipher
+            .decrypt(Nonce::from_slice(&data[..NONCE_LEN]), &data[NONCE_LEN..])
+            .map_err(|_| secrets_unreadable("Secret file could not be decrypted"))?;
+        Ok(serde_json::from_slice(&plain)?)
+    }
+
+    fn save(&self, secrets: &HashMap<String, String>) -> Result<(), AppError> {
+        let key = match std::fs::read(&self.key_path) {
+            Ok(key) if key.len() == 32 => key,
+            _ => {
+                let key = Aes256Gcm::generate_key(OsRng).to_vec();
+                write_private(&self.key_path, &key)?;
+                key
+            }
+        };
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key));
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let plain = serde_json::to_vec(secrets)?;
+        let sealed = cipher
+            .encrypt(&nonce, plain.as_slice())
+            .map_err(|_| AppError::new(ErrorKind::Internal, "Could not encrypt secrets"))?;
+
+        let mut data = nonce.to_vec();
+        data.extend_from_slice(&sealed);
+        write_private(&self.path, &data)
+    }
+}
+
+fn secrets_unreadable(detail: impl Into<String>) -> AppError {
+    AppError::new(ErrorKind::Io, "Saved API keys could not be read")
+        .with_detail(detail)
+        .with_hint("Re-enter your API keys and tokens in Settings")
+}
+
+/// Write through a temp file, readable only by the user.
+fn write_private(path: &Path, data: &[u8]) -> Result<(), AppError> {
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, data)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o600))?;
+    }
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+// ---- Paths ----
+
+fn config_dir() -> Result<PathBuf, AppError> {
+    let dir = dirs::data_dir()
+        .ok_or_else(|| AppError::new(ErrorKind::NotFound, "Cannot find data directory"))?
+        .join("Audiobook Tagger");
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn config_path() -> Result<PathBuf, AppError> {
+    Ok(config_dir()?.join("config.json"))
+}
+
+fn secret_store() -> Result<SecretStore, AppError> {
+    Ok(SecretStore {
+        file: SecretFile::in_dir(&config_dir()?),
+        use_keyring: cfg!(any(target_os = "macos", target_os = "windows")),
+    })
+}
+
+// ---- Tauri commands ----
+
+/// Settings and secrets, or null if nothing has been saved yet (the
+/// frontend then imports its localStorage config with `save_config`).
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn get_config() -> Result<Option<Value>, AppError> {
+    let store = secret_store()?;
+    match load_from(&config_path()?, &store)? {
+        Some(config) => Ok(Some(to_frontend(&config, &store)?)),
+        None => Ok(None),
+    }
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn save_config(config: Value) -> Result<(), AppError> {
+    let (config, secrets) = migrate(config)?;
+    save_to(&config_path()?, &secret_store()?, &config, &secrets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(dir: &Path) -> SecretStore {
+        SecretStore { file: SecretFile::in_dir(dir), use_keyring: false }
+    }
+
+    #[test]
+    fn frontend_config_round_trips_without_secrets_on_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        let store = store(dir.path());
+
+        let frontend = serde_json::json!({
+            "abs_base_url": "http://nas:13378",
+            "abs_api_token": "abs-secret-token",
+            "openai_api_key": "sk-test",
+            "anthropic_api_key": null,
+            "cloud_concurrency": 10,
+            "ollama_model": null,
+            "genre_enforcement": true,
+        });
+        let (config, secrets) = migrate(frontend).unwrap();
+        save_to(&path, &store, &config, &secrets).unwrap();
+
+        let on_disk = std::fs::read_to_string(&path).unwrap();
+        assert!(!on_disk.contains("abs-secret-token"));
+        assert!(!on_disk.contains("sk-test"));
+        let sealed = std::fs::read(dir.path().join("secrets.enc")).unwrap();
+        assert!(!String::from_utf8_lossy(&sealed).contains("sk-test"));
+
+        let loaded = load_from(&path, &store).unwrap().unwrap();
+        assert_eq!(loaded.version, CONFIG_VERSION);
+        assert_eq!(loaded.abs_url(), Some("http://nas:13378"));
+        assert_eq!(loaded.concurrency().cloud_concurrency, 10);
+        assert_eq!(loaded.extra["genre_enforcement"], true);
+
+        let back = to_frontend(&loaded, &store).unwrap();
+        assert_eq!(back["abs_api_token"], "abs-secret-token");
+        assert_eq!(back["openai_api_key"], "sk-test");
+        assert!(back.get("anthropic_api_key").is_none());
+    }
+
+    #[test]
+    fn empty_secret_removes_it_and_absent_secret_keeps_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = store(dir.path());
+        store.set(Secret::OpenaiApiKey, Some("sk-old")).unwrap();
+        store.set(Secret::AbsApiToken, Some("token")).unwrap();
+
+        let (_, secrets) = migrate(serde_json::json!({ "openai_api_key": "" })).unwrap();
+        for (secret, value) in secrets {
+            store.set(secret, value.as_deref()).unwrap();
+        }
+        assert_eq!(store.get(Secret::OpenaiApiKey).unwrap(), None);
+        assert_eq!(store.get(Secret::AbsApiToken).unwrap().as_deref(), Some("token"));
+    }
+
+    #[test]
+    fn unversioned_file_is_migrated_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(&path, r#"{ "abs_base_url": "http://nas", "abs_api_token": "plain" }"#).unwrap();
+        let store = store(dir.path());
+
+        let config = load_from(&path, &store).unwrap().unwrap();
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("plain"));
+        assert_eq!(store.get(Secret::AbsApiToken).unwrap().as_deref(), Some("plain"));
+    }
+}
@@ -0,0 +1,197 @@
+// src-tauri/src/error.rs
+// Error type returned by every command. Serialises as
+// { kind, message, detail?, hint? } so the UI can tell a missing FFmpeg from
+// a rejected ABS token and show what to do about it.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    FfmpegMissing,
+    FfmpegFailed,
+    WhisperNotInstalled,
+    ModelNotDownloaded,
+    TranscriptionFailed,
+    OllamaNotRunning,
+    MissingApiKey,
+    ApiUnauthorized,
+    ApiError,
+    AbsNotConfigured,
+    AbsUnauthorized,
+    AbsNotFound,
+    AbsUnreachable,
+    Network,
+    InvalidInput,
+    NotFound,
+    Io,
+    Database,
+    Parse,
+    Cancelled,
+    Internal,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppError {
+    pub kind: ErrorKind,
+    /// One line suitable for a toast or pill.
+    pub message: String,
+    /// Technical detail (stderr tail, HTTP body, OS error) for the expanded view.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// What the user can do about it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+const MAX_DETAIL_CHARS: usize = 2000;
+
+impl AppError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        AppError { kind, message: message.into(), detail: None, hint: None }
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        let detail: String = detail.into();
+        let detail = detail.trim();
+        // Keep the end of long output (FFmpeg/whisper-cpp stderr) where the actual error is.
+        let detail = match detail.char_indices().rev().nth(MAX_DETAIL_CHARS) {
+            Some((i, _)) => format!("...{}", &detail[i..]),
+            None => detail.to_string(),
+        };
+        if !detail.is_empty() {
+            self.detail = Some(detail);
+        }
+        self
+    }
+
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    pub fn ffmpeg_missing() -> Self {
+        AppError::new(ErrorKind::FfmpegMissing, "FFmpeg is not installed")
+            .with_hint("Install local Whisper from Settings (it bundles FFmpeg), or run: brew install ffmpeg")
+    }
+
+    pub fn ollama_not_running() -> Self {
+        AppError::new(ErrorKind::OllamaNotRunning, "Ollama is not running")
+            .with_hint("Start Ollama from Settings > Local AI")
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        AppError::new(ErrorKind::InvalidInput, message)
+    }
+
+    pub fn database(message: impl Into<String>) -> Self {
+        AppError::new(ErrorKind::Database, message)
+    }
+
+    /// Classify a non-success HTTP response from `service` ("ABS", "OpenAI", ...).
+    pub fn http_status(service: &str, status: u16, body: &str) -> Self {
+        let is_abs = service == "ABS";
+        let err = match (status, is_abs) {
+            (401 | 403, true) => AppError::new(ErrorKind::AbsUnauthorized, "AudiobookShelf rejected the API token")
+                .with_hint("Check the ABS URL and API token in Settings"),
+            (404, true) => AppError::new(ErrorKind::AbsNotFound, "Item not found on AudiobookShelf")
+                .with_hint("The item may have been removed or the library rescanned; refresh the library"),
+            (401 | 403, false) => AppError::new(ErrorKind::ApiUnauthorized, format!("{} rejected the API key", service))
+                .with_hint(format!("Check the {} API key in Settings", service)),
+            _ => AppError::new(
+                if is_abs { ErrorKind::AbsUnreachable } else { ErrorKind::ApiError },
+                format!("{} returned HTTP {}", service, status),
+            ),
+        };
+        err.with_detail(body)
+    }
+
+    /// Classify a transport failure talking to `service`.
+    pub fn request_failed(service: &str, e: &reqwest::Error) -> Self {
+        let err = if service == "ABS" {
+            AppError::new(ErrorKind::AbsUnreachable, "Could not reach AudiobookShelf")
+                .with_hint("Check that the ABS server is running and the URL in Settings is correct")
+        } else if e.is_timeout() {
+            AppError::new(ErrorKind::Network, format!("{} request timed out", service))
+        } else {
+            AppError::new(ErrorKind::Network, format!("Could not reach {}", service))
+                .with_hint("Check your internet connection")
+        };
+        err.with_detail(e.to_string())
+    }
+
+    /// Transient failures worth retrying without user action.
+    pub fn is_transient(&self) -> bool {
+        matches!(self.kind, ErrorKind::Network | ErrorKind::AbsUnreachable)
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+/// Internal helpers still report plain strings; those surface as `internal`
+/// with the string as the message.
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        AppError::new(ErrorKind::Internal, message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        AppError::new(ErrorKind::Internal, message)
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        let kind = if e.kind() == std::io::ErrorKind::NotFound { ErrorKind::NotFound } else { ErrorKind::Io };
+        AppError::new(kind, "File system error").with_detail(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(e: serde_json::Error) -> Self {
+        AppError::new(ErrorKind::Parse, "Invalid JSON").with_detail(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_kind_message_and_hint() {
+        let json = serde_json::to_value(AppError::ffmpeg_missing()).unwrap();
+        assert_eq!(json["kind"], "ffmpeg_missing");
+        assert_eq!(json["message"], "FFmpeg is not installed");
+        assert!(json["hint"].as_str().unwrap().contains("brew install ffmpeg"));
+        assert!(json.get("detail").is_none());
+    }
+
+    #[test]
+    fn classifies_http_status_by_service() {
+        assert_eq!(AppError::http_status("ABS", 401, "").kind, ErrorKind::AbsUnauthorized);
+        assert_eq!(AppError::http_status("ABS", 404, "").kind, ErrorKind::AbsNotFound);
+        assert_eq!(AppError::http_status("OpenAI", 401, "").kind, ErrorKind::ApiUnauthorized);
+        let e = AppError::http_status("OpenAI", 500, "upstream timeout");
+        assert_eq!(e.kind, ErrorKind::ApiError);
+        assert_eq!(e.detail.as_deref(), Some("upstream timeout"));
+    }
+
+    #[test]
+    fn long_detail_keeps_the_tail() {
+        let stderr = format!("{}\nServer returned 401 Unauthorized", "x".repeat(5000));
+        let e = AppError::new(ErrorKind::FfmpegFailed, "FFmpeg failed").with_detail(stderr);
+        let detail = e.detail.unwrap();
+        assert!(detail.starts_with("..."));
+        assert!(detail.ends_with("401 Unauthorized"));
+        assert!(detail.chars().count() <= MAX_DETAIL_CHARS + 4);
+    }
+}
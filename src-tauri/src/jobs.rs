@@ -0,0 +1,965 @@
+// src-tauri/src/jobs.rs
+// Job queue for long-running work: intro extraction, transcription,
+// installs, model pulls.
+// Jobs have ids, run under per-resource concurrency limits, can be paused,
+// resumed and cancelled one at a time, retry transient failures, and are
+// saved to disk so an interrupted queue picks up again on the next launch.
+
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{Notify, OwnedSemaphorePermit, Semaphore};
+
+use crate::abs::AbsClient;
+use crate::abs_push::{self, PushItem, PushOptions, PushStatus, Pusher};
+use crate::chapter_titles::ChapterTitleRequest;
+use crate::error::{AppError, ErrorKind};
+use crate::progress::{ByteProgress, ProgressEvent, ProgressSink, Stage};
+use crate::transcribe::TranscribeRequest;
+use crate::whisper::{self, AudioIntroRequest, IntroConcurrency, StageLimits};
+
+/// Runs of a job (or passes over a batch's failed items) before giving up on
+/// a transient error.
+const MAX_ATTEMPTS: u32 = 3;
+/// Finished jobs kept in the list, and on disk, before the oldest are dropped.
+const MAX_FINISHED: usize = 100;
+/// Batch results are saved at most this often; state changes always save.
+const SAVE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// What a job does. Stored with the job so it can be re-run after a restart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobSpec {
+    /// Whisper intro extraction over a list of books.
+    IntroExtraction {
+        items: Vec<AudioIntroRequest>,
+        #[serde(default)]
+        force: bool,
+        #[serde(default)]
+        concurrency: IntroConcurrency,
+    },
+    /// Metadata push to AudiobookShelf; see `abs_push`.
+    AbsPush {
+        items: Vec<PushItem>,
+        #[serde(default)]
+        options: PushOptions,
+    },
+    /// Full-book transcription; resumes from the chunks already saved.
+    Transcription { request: TranscribeRequest },
+    /// Chapter names read from the narration; the proposal is the job's result.
+    ChapterTitles { request: ChapterTitleRequest },
+    WhisperInstall,
+    WhisperModel { model_id: String },
+    OllamaPull {
+        model: String,
+        #[serde(default)]
+        base_url: Option<String>,
+    },
+}
+
+impl JobSpec {
+    fn label(&self) -> String {
+        match self {
+            JobSpec::IntroExtraction { items, .. } => format!("Extract intros ({} books)", items.len()),
+            JobSpec::AbsPush { items, options } if options.dry_run => {
+                format!("Preview push ({} books)", items.len())
+            }
+            JobSpec::AbsPush { items, .. } => format!("Push {} books to AudiobookShelf", items.len()),
+            JobSpec::Transcription { request } => match request.files.as_slice() {
+                [file] => format!("Transcribe {}", file_name(file)),
+                files => format!("Transcribe {} files", files.len()),
+            },
+            JobSpec::ChapterTitles { request } => match request.files.as_slice() {
+                [file] => format!("Name chapters of {}", file_name(file)),
+                files => format!("Name chapters across {} files", files.len()),
+            },
+            JobSpec::WhisperInstall => "Install local Whisper".to_string(),
+            JobSpec::WhisperModel { model_id } => format!("Download Whisper model {}", model_id),
+            JobSpec::OllamaPull { model, .. } => format!("Pull {}", model),
+        }
+    }
+
+    /// Resources held while the job runs.
+    fn resources(&self) -> Vec<Resource> {
+        match self {
+            JobSpec::IntroExtraction { items, .. } => {
+                let mut resources = vec![Resource::Whisper];
+                if items.iter().any(|i| i.source == "abs") {
+                    resources.push(Resource::Abs);
+                }
+                resources
+            }
+            JobSpec::AbsPush { .. } => vec![Resource::Abs],
+            JobSpec::Transcription { .. } | JobSpec::ChapterTitles { .. } => vec![Resource::Ffmpeg, Resource::Whisper],
+            JobSpec::WhisperInstall | JobSpec::WhisperModel { .. } | JobSpec::OllamaPull { .. } => {
+                vec![Resource::Download]
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Queued,
+    Running,
+    Paused,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl JobState {
+    pub fn is_finished(self) -> bool {
+        matches!(self, JobState::Completed | JobState::Failed | JobState::Cancelled)
+    }
+}
+
+/// Something only a few jobs should use at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resource {
+    Ffmpeg,
+    Whisper,
+    Llm,
+    Abs,
+    Download,
+}
+
+impl Resource {
+    const ALL: [Resource; 5] = [Resource::Ffmpeg, Resource::Whisper, Resource::Llm, Resource::Abs, Resource::Download];
+
+    fn default_limit(self) -> usize {
+        match self {
+            Resource::Ffmpeg => 2,
+            Resource::Whisper => 1,
+            Resource::Llm => 1,
+            Resource::Abs => 4,
+            Resource::Download => 2,
+        }
+    }
+}
+
+/// One semaphore per resource.
+pub struct ResourcePool {
+    semaphores: HashMap<Resource, Arc<Semaphore>>,
+}
+
+impl Default for ResourcePool {
+    fn default() -> Self {
+        ResourcePool {
+            semaphores: Resource::ALL
+                .iter()
+                .map(|&r| (r, Arc::new(Semaphore::new(r.default_limit()))))
+                .collect(),
+        }
+    }
+}
+
+impl ResourcePool {
+    /// Take one permit for each resource. Permits are always taken in the
+    /// same order so two jobs can't each hold what the other is waiting for.
+    pub async fn acquire(&self, resources: &[Resource]) -> Vec<OwnedSemaphorePermit> {
+        let mut wanted = resources.to_vec();
+        wanted.sort();
+        wanted.dedup();
+        let mut permits = Vec::with_capacity(wanted.len());
+        for resource in wanted {
+            if let Some(semaphore) = self.semaphores.get(&resource) {
+                if let Ok(permit) = Arc::clone(semaphore).acquire_owned().await {
+                    permits.push(permit);
+                }
+            }
+        }
+        permits
+    }
+}
+
+/// Cancel and pause flags for one job.
+#[derive(Default)]
+pub struct JobControl {
+    cancelled: AtomicBool,
+    paused: AtomicBool,
+    wake: Notify,
+}
+
+impl JobControl {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.wake.notify_waiters();
+    }
+
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+        self.wake.notify_waiters();
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+        self.wake.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Wait while paused. Returns false once the job is cancelled.
+    pub async fn checkpoint(&self) -> bool {
+        loop {
+            let woken = self.wake.notified();
+            tokio::pin!(woken);
+            woken.as_mut().enable();
+            if self.is_cancelled() {
+                return false;
+            }
+            if !self.is_paused() {
+                return true;
+            }
+            woken.await;
+        }
+    }
+
+    /// Resolves as soon as the job is paused or cancelled.
+    pub async fn interrupted(&self) {
+        loop {
+            let woken = self.wake.notified();
+            tokio::pin!(woken);
+            woken.as_mut().enable();
+            if self.is_cancelled() || self.is_paused() {
+                return;
+            }
+            woken.await;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JobProgress {
+    /// Items or bytes done, depending on the job.
+    pub current: u64,
+    pub total: u64,
+    pub message: String,
+}
+
+/// What the frontend sees of a job.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobInfo {
+    pub id: String,
+    pub label: String,
+    pub state: JobState,
+    pub progress: JobProgress,
+    /// Times the job has been started, including retries and resumes.
+    pub attempts: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<AppError>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Job {
+    #[serde(flatten)]
+    info: JobInfo,
+    spec: JobSpec,
+    /// Intro and push jobs: one entry per item, null until processed.
+    /// Other jobs: the finished message.
+    #[serde(default)]
+    results: Vec<serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct JobFile {
+    jobs: Vec<Job>,
+}
+
+pub struct JobManager {
+    jobs: Mutex<Vec<Job>>,
+    controls: Mutex<HashMap<String, Arc<JobControl>>>,
+    resources: ResourcePool,
+    path: Option<PathBuf>,
+    last_save: Mutex<Option<Instant>>,
+    started: AtomicBool,
+    sink: Arc<dyn ProgressSink>,
+}
+
+impl JobManager {
+    /// Load the queue saved at `path`, if any. Jobs that were running when
+    /// the app closed go back to the queue.
+    pub fn new(path: Option<PathBuf>, sink: Arc<dyn ProgressSink>) -> Arc<Self> {
+        let mut jobs = path.as_deref().map(load_jobs).unwrap_or_default();
+        for job in &mut jobs {
+            if job.info.state == JobState::Running {
+                job.info.state = JobState::Queued;
+                job.info.progress.message = "Interrupted, waiting to resume".to_string();
+            }
+        }
+        Arc::new(JobManager {
+            jobs: Mutex::new(jobs),
+            controls: Mutex::new(HashMap::new()),
+            resources: ResourcePool::default(),
+            path,
+            last_save: Mutex::new(None),
+            started: AtomicBool::new(false),
+            sink,
+        })
+    }
+
+    /// Start a runner for every unfinished job. Must be called from inside
+    /// the async runtime; later calls do nothing.
+    pub fn start(self: &Arc<Self>) {
+        if self.started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let pending: Vec<(String, bool)> = self
+            .lock_jobs()
+            .iter()
+            .filter(|j| !j.info.state.is_finished())
+            .map(|j| (j.info.id.clone(), j.info.state == JobState::Paused))
+            .collect();
+        for (id, paused) in pending {
+            self.spawn(id, paused);
+        }
+    }
+
+    pub fn enqueue(self: &Arc<Self>, spec: JobSpec) -> JobInfo {
+        let now = now();
+        let info = JobInfo {
+            id: uuid::Uuid::new_v4().to_string(),
+            label: spec.label(),
+            state: JobState::Queued,
+            progress: JobProgress { message: "Queued".to_string(), ..Default::default() },
+            attempts: 0,
+            error: None,
+            created_at: now,
+            updated_at: now,
+        };
+        self.lock_jobs().push(Job { info: info.clone(), spec, results: Vec::new() });
+        self.save();
+        self.emit(&info);
+        self.spawn(info.id.clone(), false);
+        info
+    }
+
+    pub fn list(&self) -> Vec<JobInfo> {
+        self.lock_jobs().iter().map(|j| j.info.clone()).collect()
+    }
+
+    pub fn get(&self, id: &str) -> Result<JobInfo, AppError> {
+        self.lock_jobs()
+            .iter()
+            .find(|j| j.info.id == id)
+            .map(|j| j.info.clone())
+            .ok_or_else(|| job_not_found(id))
+    }
+
+    pub fn results(&self, id: &str) -> Result<Vec<serde_json::Value>, AppError> {
+        self.lock_jobs()
+            .iter()
+            .find(|j| j.info.id == id)
+            .map(|j| j.results.clone())
+            .ok_or_else(|| job_not_found(id))
+    }
+
+    pub fn cancel(&self, id: &str) -> Result<JobInfo, AppError> {
+        // The runner notices and records the final state.
+        self.control(id)?.cancel();
+        self.get(id)
+    }
+
+    pub fn pause(&self, id: &str) -> Result<JobInfo, AppError> {
+        self.control(id)?.pause();
+        self.set_state(id, JobState::Paused, "Paused");
+        self.get(id)
+    }
+
+    pub fn resume(&self, id: &str) -> Result<JobInfo, AppError> {
+        self.control(id)?.resume();
+        self.set_state(id, JobState::Queued, "Waiting to resume");
+        self.get(id)
+    }
+
+    /// Drop finished jobs from the list. Returns how many were removed.
+    pub fn clear_finished(&self) -> usize {
+        let removed = {
+            let mut jobs = self.lock_jobs();
+            let before = jobs.len();
+            jobs.retain(|j| !j.info.state.is_finished());
+            before - jobs.len()
+        };
+        self.save();
+        removed
+    }
+
+    // ---- Runner ----
+
+    fn spawn(self: &Arc<Self>, id: String, paused: bool) {
+        let control = Arc::new(JobControl::default());
+        if paused {
+            control.pause();
+        }
+        if let Ok(mut controls) = self.controls.lock() {
+            controls.insert(id.clone(), Arc::clone(&control));
+        }
+        let manager = Arc::clone(self);
+        tokio::spawn(async move { manager.run(id, control).await });
+    }
+
+    async fn run(self: Arc<Self>, id: String, control: Arc<JobControl>) {
+        let Some(spec) = self.lock_jobs().iter().find(|j| j.info.id == id).map(|j| j.spec.clone()) else {
+            return;
+        };
+        let resources = spec.resources();
+        let mut failures = 0;
+
+        let state = loop {
+            if !control.checkpoint().await {
+                break JobState::Cancelled;
+            }
+            let permits = tokio::select! {
+                permits = self.resources.acquire(&resources) => permits,
+                _ = control.interrupted() => continue,
+            };
+
+            self.update(&id, |job| {
+                job.info.state = JobState::Running;
+                job.info.attempts += 1;
+                job.info.error = None;
+            });
+            self.save();
+
+            let outcome = tokio::select! {
+                result = self.execute(&id, &spec) => Some(result),
+                _ = control.interrupted() => None,
+            };
+            drop(permits);
+
+            match outcome {
+                None if control.is_cancelled() => break JobState::Cancelled,
+                None => self.set_state(&id, JobState::Paused, "Paused"),
+                Some(Ok(())) => break JobState::Completed,
+                Some(Err(e)) if e.kind == ErrorKind::Cancelled => break JobState::Cancelled,
+                Some(Err(e)) if e.is_transient() && failures + 1 < MAX_ATTEMPTS => {
+                    failures += 1;
+                    let delay = backoff(failures);
+                    let message = format!("Retrying in {}s: {}", delay.as_secs(), e.message);
+                    self.update(&id, |job| {
+                        job.info.state = JobState::Queued;
+                        job.info.error = Some(e);
+                        job.info.progress.message = message;
+                    });
+                    self.save();
+                    tokio::select! {
+                        _ = tokio::time::sleep(delay) => {}
+                        _ = control.interrupted() => {}
+                    }
+                }
+                Some(Err(e)) => {
+                    self.update(&id, |job| job.info.error = Some(e));
+                    break JobState::Failed;
+                }
+            }
+        };
+
+        if let Ok(mut controls) = self.controls.lock() {
+            controls.remove(&id);
+        }
+        let message = match state {
+            JobState::Completed => "Done",
+            JobState::Cancelled => "Cancelled",
+            _ => "Failed",
+        };
+        self.set_state(&id, state, message);
+    }
+
+    async fn execute(self: &Arc<Self>, id: &str, spec: &JobSpec) -> Result<(), AppError> {
+        let sink = JobSink { manager: Arc::clone(self), id: id.to_string() };
+        let message = match spec {
+            JobSpec::IntroExtraction { items, force, concurrency } => {
+                return self.run_intro(id, items, *force, *concurrency, &sink).await;
+            }
+            JobSpec::AbsPush { items, options } => return self.run_abs_push(id, items, *options).await,
+            JobSpec::Transcription { request } => {
+                let summary = crate::transcribe::transcribe_book(request, &sink).await?;
+                format!("Wrote {} ({} segments)", summary.outputs.iter().map(|p| file_name(p)).collect::<Vec<_>>().join(", "), summary.segments)
+            }
+            JobSpec::ChapterTitles { request } => {
+                let titles = crate::chapter_titles::propose_chapter_titles(request, &sink).await?;
+                let message = format!("Found headings for {} of {} chapters", titles.named, titles.chapters.len());
+                let value = serde_json::to_value(&titles).map_err(|e| AppError::from(e.to_string()))?;
+                self.update(id, |job| {
+                    job.info.progress.message = message;
+                    job.results = vec![value];
+                });
+                return Ok(());
+            }
+            JobSpec::WhisperInstall => crate::whisper_local::install(&sink).await?,
+            JobSpec::WhisperModel { model_id } => crate::whisper_local::download_model(model_id, &sink).await?,
+            JobSpec::OllamaPull { model, base_url } => {
+                crate::ollama::pull_model(model, base_url.as_deref(), &sink).await?
+            }
+        };
+        self.update(id, |job| {
+            job.info.progress.message = message.clone();
+            job.results = vec![serde_json::Value::String(message)];
+        });
+        Ok(())
+    }
+
+    /// Process every item without a result yet, several at a time, then retry
+    /// items that failed transiently. Results are recorded as they arrive so
+    /// a paused or interrupted job continues where it stopped.
+    async fn run_intro(
+        &self,
+        id: &str,
+        items: &[AudioIntroRequest],
+        force: bool,
+        concurrency: IntroConcurrency,
+        sink: &JobSink,
+    ) -> Result<(), AppError> {
+        if crate::whisper_local::find_ffmpeg_binary().is_none() {
+            return Err(AppError::ffmpeg_missing());
+        }
+        let items = &whisper::with_saved_config(items.to_vec());
+        let total = items.len();
+        let limits = StageLimits::for_items(items, concurrency);
+        let reference = crate::reconcile::Reference::load();
+
+        for pass in 0..MAX_ATTEMPTS {
+            let retrying = pass > 0;
+            let pending: Vec<usize> = {
+                let jobs = self.lock_jobs();
+                let results = jobs.iter().find(|j| j.info.id == id).map(|j| j.results.as_slice()).unwrap_or(&[]);
+                (0..total).filter(|&i| needs_run(results.get(i), retrying)).collect()
+            };
+            if pending.is_empty() {
+                break;
+            }
+            if retrying {
+                tokio::time::sleep(backoff(pass)).await;
+            }
+            futures::stream::iter(pending)
+                .map(|i| {
+                    let (limits, reference) = (&limits, &reference);
+                    async move { (i, whisper::extract_item(&items[i], force, reference, sink, limits, i + 1, total).await) }
+                })
+                .buffer_unordered(limits.in_flight())
+                .for_each(|(i, result)| {
+                    self.record_item(id, i, total, &result);
+                    async {}
+                })
+                .await;
+        }
+        Ok(())
+    }
+
+    /// Push every item without a result yet, one at a time under the push
+    /// throttle, then retry items that failed transiently.
+    async fn run_abs_push(&self, id: &str, items: &[PushItem], options: PushOptions) -> Result<(), AppError> {
+        let pusher = Pusher::new(AbsClient::from_config()?, options);
+        let total = items.len();
+
+        for pass in 0..MAX_ATTEMPTS {
+            let retrying = pass > 0;
+            let pending: Vec<usize> = {
+                let jobs = self.lock_jobs();
+                let results = jobs.iter().find(|j| j.info.id == id).map(|j| j.results.as_slice()).unwrap_or(&[]);
+                (0..total).filter(|&i| needs_run(results.get(i), retrying)).collect()
+            };
+            if pending.is_empty() {
+                break;
+            }
+            if retrying {
+                tokio::time::sleep(backoff(pass)).await;
+            }
+            for i in pending {
+                self.update(id, |job| {
+                    let title = items[i].metadata.title.as_deref().unwrap_or(&items[i].abs_id);
+                    job.info.progress.message = format!("Pushing {}", title);
+                });
+                let outcome = pusher.push(&items[i]).await;
+                let error = outcome.error.clone();
+                if let Err(e) = abs_push::record_in_library(&items[i], &outcome, &options) {
+                    self.sink.report(ProgressEvent::library(Stage::Error, format!("Library database error: {}", e)));
+                }
+                self.record_item(id, i, total, &outcome);
+                // A rejected token fails every remaining item the same way
+                if let Some(e) = error.filter(|e| e.kind == ErrorKind::AbsUnauthorized) {
+                    self.save();
+                    return Err(e);
+                }
+            }
+        }
+
+        let summary = {
+            let jobs = self.lock_jobs();
+            let results = jobs.iter().find(|j| j.info.id == id).map(|j| j.results.clone()).unwrap_or_default();
+            let count = |status: PushStatus| {
+                results.iter().filter(|r| r.get("status") == serde_json::to_value(status).ok().as_ref()).count()
+            };
+            match options.dry_run {
+                true => format!("{} would change, {} unchanged", count(PushStatus::WouldUpdate), count(PushStatus::Unchanged)),
+                false => format!(
+                    "{} updated, {} unchanged, {} failed",
+                    count(PushStatus::Updated),
+                    count(PushStatus::Unchanged),
+                    count(PushStatus::Failed)
+                ),
+            }
+        };
+        self.update(id, |job| job.info.progress.message = summary);
+        Ok(())
+    }
+
+    /// Store the result for one item of a batch job.
+    fn record_item(&self, id: &str, index: usize, total: usize, result: &impl Serialize) {
+        let value = serde_json::to_value(result).unwrap_or(serde_json::Value::Null);
+        self.update(id, |job| {
+            job.results.resize(total, serde_json::Value::Null);
+            job.results[index] = value;
+            job.info.progress.current = job.results.iter().filter(|r| !r.is_null()).count() as u64;
+            job.info.progress.total = total as u64;
+        });
+        let due = self
+            .last_save
+            .lock()
+            .map(|last| last.is_none_or(|t| t.elapsed() >= SAVE_INTERVAL))
+            .unwrap_or(true);
+        if due {
+            self.save();
+        }
+    }
+
+    // ---- State ----
+
+    fn lock_jobs(&self) -> std::sync::MutexGuard<'_, Vec<Job>> {
+        // A panic while holding the lock leaves plain data behind; keep going.
+        self.jobs.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn control(&self, id: &str) -> Result<Arc<JobControl>, AppError> {
+        self.controls
+            .lock()
+            .ok()
+            .and_then(|c| c.get(id).cloned())
+            .ok_or_else(|| AppError::new(ErrorKind::NotFound, "Job not found or already finished"))
+    }
+
+    /// Apply `f` to the job and emit the new state.
+    fn update(&self, id: &str, f: impl FnOnce(&mut Job)) {
+        let info = {
+            let mut jobs = self.lock_jobs();
+            let Some(job) = jobs.iter_mut().find(|j| j.info.id == id) else { return };
+            f(job);
+            job.info.updated_at = now();
+            job.info.clone()
+        };
+        self.emit(&info);
+    }
+
+    fn set_state(&self, id: &str, state: JobState, message: &str) {
+        self.update(id, |job| {
+            job.info.state = state;
+            job.info.progress.message = message.to_string();
+        });
+        self.save();
+    }
+
+    fn set_progress(&self, id: &str, message: &str, bytes: Option<ByteProgress>) {
+        let info = {
+            let mut jobs = self.lock_jobs();
+            let Some(job) = jobs.iter_mut().find(|j| j.info.id == id) else { return };
+            let progress = &mut job.info.progress;
+            let old_percent = percent(progress.current, progress.total);
+            let mut changed = progress.message != message;
+            if changed {
+                progress.message = message.to_string();
+            }
+            if let Some(bytes) = bytes {
+                progress.current = bytes.done;
+                progress.total = bytes.total;
+                changed |= percent(bytes.done, bytes.total) != old_percent;
+            }
+            if !changed {
+                return;
+            }
+            job.info.updated_at = now();
+            job.info.clone()
+        };
+        self.emit(&info);
+    }
+
+    fn emit(&self, info: &JobInfo) {
+        self.sink.report(ProgressEvent::Job(Box::new(info.clone())));
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else { return };
+        let json = {
+            let mut jobs = self.lock_jobs();
+            let finished = jobs.iter().filter(|j| j.info.state.is_finished()).count();
+            if finished > MAX_FINISHED {
+                let mut excess = finished - MAX_FINISHED;
+                jobs.retain(|j| {
+                    if excess > 0 && j.info.state.is_finished() {
+                        excess -= 1;
+                        false
+                    } else {
+                        true
+                    }
+                });
+            }
+            serde_json::to_string(&JobFile { jobs: jobs.clone() })
+        };
+        let saved = json.map_err(|e| e.to_string()).and_then(|json| save_jobs(path, &json));
+        if let Err(e) = saved {
+            eprintln!("   Job queue save error: {}", e);
+        }
+        if let Ok(mut last) = self.last_save.lock() {
+            *last = Some(Instant::now());
+        }
+    }
+}
+
+/// Forwards a job's progress to the app and mirrors it onto the job.
+struct JobSink {
+    manager: Arc<JobManager>,
+    id: String,
+}
+
+impl ProgressSink for JobSink {
+    fn report(&self, event: ProgressEvent) {
+        let bytes = match &event {
+            ProgressEvent::WhisperInstall { bytes, .. } => *bytes,
+            ProgressEvent::OllamaPull { bytes, .. } => Some(*bytes),
+            ProgressEvent::Transcription { current, total, .. } => {
+                Some(ByteProgress { done: *current as u64, total: *total as u64 })
+            }
+            _ => None,
+        };
+        self.manager.set_progress(&self.id, event.message(), bytes);
+        // Existing listeners (audio_intro_progress etc.) keep working.
+        self.manager.sink.report(event);
+    }
+}
+
+/// Whether a batch item still needs processing. Transient failures (the
+/// result's `error`) are only picked up on retry passes.
+fn needs_run(result: Option<&serde_json::Value>, retrying: bool) -> bool {
+    match result {
+        None | Some(serde_json::Value::Null) => true,
+        Some(value) => {
+            retrying
+                && value
+                    .get("error")
+                    .and_then(|e| serde_json::from_value::<AppError>(e.clone()).ok())
+                    .is_some_and(|e| e.is_transient())
+        }
+    }
+}
+
+fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs(2u64.pow(attempt.min(6)))
+}
+
+fn percent(done: u64, total: u64) -> Option<u64> {
+    (total > 0).then(|| done * 100 / total)
+}
+
+fn file_name(path: &str) -> &str {
+    Path::new(path).file_name().and_then(|n| n.to_str()).unwrap_or(path)
+}
+
+fn job_not_found(id: &str) -> AppError {
+    AppError::new(ErrorKind::NotFound, format!("Job not found: {}", id))
+}
+
+fn now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+// ---- Persistence ----
+
+fn jobs_path() -> Result<PathBuf, String> {
+    let dir = dirs::data_dir()
+        .ok_or("Cannot find data directory")?
+        .join("Audiobook Tagger");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Data dir error: {}", e))?;
+    Ok(dir.join("jobs.json"))
+}
+
+fn load_jobs(path: &Path) -> Vec<Job> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str::<JobFile>(&s).ok())
+        .map(|f| f.jobs)
+        .unwrap_or_default()
+}
+
+/// Write through a temp file so a crash never leaves half a queue behind.
+/// Jobs can carry API tokens, so the file is private to the user.
+fn save_jobs(path: &Path, json: &str) -> Result<(), String> {
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json).map_err(|e| format!("Write error: {}", e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o600));
+    }
+    std::fs::rename(&tmp, path).map_err(|e| format!("Write error: {}", e))
+}
+
+// ---- Shared instance ----
+
+static MANAGER: OnceLock<Arc<JobManager>> = OnceLock::new();
+
+/// Load the saved queue and resume unfinished jobs, reporting through `sink`.
+/// Must be called from inside the async runtime.
+pub fn init(sink: Arc<dyn ProgressSink>) -> Arc<JobManager> {
+    let manager = MANAGER.get_or_init(|| JobManager::new(jobs_path().ok(), sink));
+    manager.start();
+    Arc::clone(manager)
+}
+
+fn manager() -> Result<Arc<JobManager>, AppError> {
+    MANAGER
+        .get()
+        .cloned()
+        .ok_or_else(|| AppError::new(ErrorKind::Internal, "Job queue is not running"))
+}
+
+// ---- Tauri commands ----
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn list_jobs() -> Result<Vec<JobInfo>, AppError> {
+    Ok(manager()?.list())
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub async fn enqueue_job(spec: JobSpec) -> Result<JobInfo, AppError> {
+    Ok(manager()?.enqueue(spec))
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn cancel_job(job_id: String) -> Result<JobInfo, AppError> {
+    manager()?.cancel(&job_id)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn pause_job(job_id: String) -> Result<JobInfo, AppError> {
+    manager()?.pause(&job_id)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn resume_job(job_id: String) -> Result<JobInfo, AppError> {
+    manager()?.resume(&job_id)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn get_job_results(job_id: String) -> Result<Vec<serde_json::Value>, AppError> {
+    manager()?.results(&job_id)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn clear_finished_jobs() -> Result<usize, AppError> {
+    Ok(manager()?.clear_finished())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::progress::{ChannelProgress, NoProgress};
+
+    #[tokio::test]
+    async fn checkpoint_waits_while_paused() {
+        let control = Arc::new(JobControl::default());
+        control.pause();
+
+        let waiter = tokio::spawn({
+            let control = Arc::clone(&control);
+            async move { control.checkpoint().await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        control.resume();
+        assert!(waiter.await.unwrap());
+
+        control.cancel();
+        assert!(!control.checkpoint().await);
+    }
+
+    #[tokio::test]
+    async fn failed_job_records_error() {
+        let (sink, rx) = ChannelProgress::new();
+        let manager = JobManager::new(None, Arc::new(sink));
+        let job = manager.enqueue(JobSpec::WhisperModel { model_id: "no-such-model".into() });
+
+        let finished = tokio::task::spawn_blocking(move || {
+            rx.iter().find_map(|event| match event {
+                ProgressEvent::Job(info) if info.state.is_finished() => Some(*info),
+                _ => None,
+            })
+        })
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(finished.id, job.id);
+        assert_eq!(finished.state, JobState::Failed);
+        assert_eq!(finished.error.unwrap().kind, ErrorKind::InvalidInput);
+        assert!(manager.cancel(&job.id).is_err());
+    }
+
+    #[test]
+    fn interrupted_jobs_are_requeued_on_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jobs.json");
+        let info = JobInfo {
+            id: "job-1".into(),
+            label: "Install local Whisper".into(),
+            state: JobState::Running,
+            progress: JobProgress::default(),
+            attempts: 1,
+            error: None,
+            created_at: 0,
+            updated_at: 0,
+        };
+        let file = JobFile { jobs: vec![Job { info, spec: JobSpec::WhisperInstall, results: vec![] }] };
+        save_jobs(&path, &serde_json::to_string(&file).unwrap()).unwrap();
+
+        let manager = JobManager::new(Some(path), Arc::new(NoProgress));
+        let jobs = manager.list();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].state, JobState::Queued);
+    }
+
+    #[test]
+    fn intro_items_rerun_only_when_missing_or_transient() {
+        let failed = |kind| {
+            let mut r: crate::whisper::AudioIntroResult = serde_json::from_value(serde_json::json!({
+                "item_id": "a", "transcript": null, "title": null, "subtitle": null,
+                "narrators": [], "authors": [], "publisher": null, "audio_publisher": null,
+                "language": null, "parse_method": "none", "confidence": 0.0,
+            }))
+            .unwrap();
+            r.error = Some(AppError::new(kind, "failed"));
+            serde_json::to_value(r).unwrap()
+        };
+        assert!(needs_run(None, false));
+        assert!(needs_run(Some(&serde_json::Value::Null), false));
+        assert!(!needs_run(Some(&failed(ErrorKind::Network)), false));
+        assert!(needs_run(Some(&failed(ErrorKind::Network)), true));
+        assert!(!needs_run(Some(&failed(ErrorKind::MissingApiKey)), true));
+    }
+}
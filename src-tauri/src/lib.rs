@@ -1,14 +1,75 @@
-mod scanner;
-mod ollama;
-mod whisper;
-mod whisper_local;
+pub mod abs;
+pub mod abs_push;
+#[cfg(test)]
+mod abs_mock;
+pub mod authority;
+pub mod chapter_titles;
+pub mod chapters;
+pub mod config;
+pub mod error;
+pub mod jobs;
+pub mod library;
+pub mod names;
+pub mod progress;
+pub mod reconcile;
+pub mod scanner;
+pub mod series;
+pub mod tags;
+pub mod transcribe;
+pub mod transcript_cache;
+pub mod ollama;
+pub mod whisper;
+pub mod whisper_local;
 
+#[cfg(feature = "gui")]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_http::init())
+        .setup(|app| {
+            // Resume whatever was queued when the app last closed
+            let handle = app.handle().clone();
+            tauri::async_runtime::block_on(async move {
+                jobs::init(std::sync::Arc::new(handle));
+            });
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             scanner::scan_library,
+            abs_push::abs_push_preview,
+            authority::authority_list,
+            authority::authority_add_alias,
+            authority::authority_merge,
+            authority::authority_remove,
+            authority::authority_suggest_merges,
+            authority::authority_export,
+            authority::authority_import,
+            chapters::get_abs_chapters,
+            chapters::compare_abs_chapters,
+            chapters::push_abs_chapters,
+            chapters::embed_abs_chapters,
+            config::get_config,
+            config::save_config,
+            jobs::list_jobs,
+            jobs::enqueue_job,
+            jobs::cancel_job,
+            jobs::pause_job,
+            jobs::resume_job,
+            jobs::get_job_results,
+            jobs::clear_finished_jobs,
+            library::library_query,
+            library::library_get_group,
+            library::library_save_metadata,
+            library::library_get_history,
+            library::library_diff_versions,
+            library::library_revert_fields,
+            library::library_get_provenance,
+            library::library_set_chapters,
+            library::library_get_chapters,
+            library::library_set_cover,
+            library::library_get_cover,
+            library::library_set_push_status,
+            library::library_get_push_status,
             ollama::ollama_get_status,
             ollama::ollama_get_model_presets,
             ollama::ollama_get_disk_usage,
@@ -21,6 +82,11 @@ pub fn run() {
             whisper::extract_audio_intro,
             whisper::batch_extract_audio_intros,
             whisper::cancel_audio_extraction,
+            transcript_cache::transcript_cache_list,
+            transcript_cache::transcript_cache_get,
+            transcript_cache::transcript_cache_purge,
+            transcript_cache::transcript_cache_export,
+            transcript_cache::transcript_cache_get_disk_usage,
             whisper_local::whisper_local_get_status,
             whisper_local::whisper_local_get_model_presets,
             whisper_local::whisper_local_install,
@@ -28,11 +94,14 @@ pub fn run() {
             whisper_local::whisper_local_delete_model,
             whisper_local::whisper_local_get_disk_usage,
             whisper_local::whisper_local_uninstall,
+            whisper_local::whisper_server_start,
+            whisper_local::whisper_server_stop,
         ])
         .on_window_event(|_window, event| {
             if let tauri::WindowEvent::Destroyed = event {
                 if let Ok(rt) = tokio::runtime::Runtime::new() {
                     let _ = rt.block_on(ollama::ollama_stop());
+                    let _ = rt.block_on(whisper_local::whisper_server_stop());
                 }
             }
         })
@@ -0,0 +1,1156 @@
+// src-tauri/src/library.rs
+// Embedded SQLite library database. Persists scanned groups, files,
+// metadata versions with per-field provenance, chapters, covers and ABS
+// push status so the UI can page through large libraries instead of
+// holding every BookGroup in memory.
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::error::AppError;
+use crate::scanner::{AudioFile, BookGroup, BookMetadata};
+
+/// Schema migrations, applied in order. `PRAGMA user_version` records how
+/// many have run, so only append to this list — never edit an entry.
+const MIGRATIONS: &[&str] = &[
+    // 1: initial schema
+    r#"
+    CREATE TABLE groups (
+        id              TEXT PRIMARY KEY,
+        folder          TEXT NOT NULL UNIQUE,
+        group_name      TEXT NOT NULL,
+        group_type      TEXT NOT NULL,
+        scan_status     TEXT NOT NULL,
+        abs_id          TEXT,
+        title           TEXT NOT NULL DEFAULT '',
+        author          TEXT NOT NULL DEFAULT '',
+        narrator        TEXT NOT NULL DEFAULT '',
+        series          TEXT NOT NULL DEFAULT '',
+        series_number   TEXT NOT NULL DEFAULT '',
+        year            TEXT NOT NULL DEFAULT '',
+        metadata_json   TEXT NOT NULL,
+        current_version INTEGER NOT NULL DEFAULT 0,
+        created_at      INTEGER NOT NULL,
+        updated_at      INTEGER NOT NULL
+    );
+    CREATE INDEX idx_groups_series ON groups(series);
+    CREATE INDEX idx_groups_narrator ON groups(narrator);
+
+    CREATE TABLE group_genres (
+        group_id TEXT NOT NULL REFERENCES groups(id) ON DELETE CASCADE,
+        genre    TEXT NOT NULL,
+        PRIMARY KEY (group_id, genre)
+    );
+    CREATE INDEX idx_group_genres_genre ON group_genres(genre);
+
+    CREATE TABLE files (
+        id       TEXT PRIMARY KEY,
+        group_id TEXT NOT NULL REFERENCES groups(id) ON DELETE CASCADE,
+        path     TEXT NOT NULL UNIQUE,
+        filename TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        size     INTEGER,
+        mtime    INTEGER,
+        status   TEXT NOT NULL
+    );
+    CREATE INDEX idx_files_group ON files(group_id);
+
+    CREATE TABLE metadata_versions (
+        id            INTEGER PRIMARY KEY AUTOINCREMENT,
+        group_id      TEXT NOT NULL REFERENCES groups(id) ON DELETE CASCADE,
+        version       INTEGER NOT NULL,
+        source        TEXT NOT NULL,
+        metadata_json TEXT NOT NULL,
+        created_at    INTEGER NOT NULL,
+        UNIQUE (group_id, version)
+    );
+
+    CREATE TABLE provenance (
+        group_id   TEXT NOT NULL REFERENCES groups(id) ON DELETE CASCADE,
+        field      TEXT NOT NULL,
+        source     TEXT NOT NULL,
+        version    INTEGER NOT NULL,
+        updated_at INTEGER NOT NULL,
+        PRIMARY KEY (group_id, field)
+    );
+
+    CREATE TABLE chapters (
+        group_id TEXT NOT NULL REFERENCES groups(id) ON DELETE CASCADE,
+        idx      INTEGER NOT NULL,
+        title    TEXT NOT NULL,
+        start_ms INTEGER NOT NULL,
+        end_ms   INTEGER NOT NULL,
+        source   TEXT NOT NULL,
+        PRIMARY KEY (group_id, idx)
+    );
+
+    CREATE TABLE covers (
+        group_id   TEXT PRIMARY KEY REFERENCES groups(id) ON DELETE CASCADE,
+        mime       TEXT NOT NULL,
+        data       BLOB NOT NULL,
+        source     TEXT NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+
+    CREATE TABLE push_status (
+        group_id       TEXT PRIMARY KEY REFERENCES groups(id) ON DELETE CASCADE,
+        status         TEXT NOT NULL,
+        pushed_version INTEGER,
+        error          TEXT,
+        updated_at     INTEGER NOT NULL
+    );
+    "#,
+    // 2: the version holding the last scan as read from disk, which edits
+    // made since are merged against
+    r#"
+    ALTER TABLE groups ADD COLUMN scan_version INTEGER;
+    UPDATE groups SET scan_version = (
+        SELECT MAX(version) FROM metadata_versions v WHERE v.group_id = groups.id AND v.source = 'scan'
+    );
+    "#,
+];
+
+/// Where a metadata version came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataSource {
+    Scan,
+    Ai,
+    Whisper,
+    Manual,
+    Push,
+    Revert,
+}
+
+impl MetadataSource {
+    fn as_str(self) -> &'static str {
+        match self {
+            MetadataSource::Scan => "scan",
+            MetadataSource::Ai => "ai",
+            MetadataSource::Whisper => "whisper",
+            MetadataSource::Manual => "manual",
+            MetadataSource::Push => "push",
+            MetadataSource::Revert => "revert",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "ai" => MetadataSource::Ai,
+            "whisper" => MetadataSource::Whisper,
+            "manual" => MetadataSource::Manual,
+            "push" => MetadataSource::Push,
+            "revert" => MetadataSource::Revert,
+            _ => MetadataSource::Scan,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MetadataVersion {
+    pub version: i64,
+    pub source: MetadataSource,
+    pub created_at: i64,
+    pub metadata: BookMetadata,
+    /// Whether this is the group's current metadata. A rescan stores what it
+    /// read as a version without making it current.
+    pub current: bool,
+}
+
+/// One field that differs between two metadata versions.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: serde_json::Value,
+    pub after: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LibraryFilter {
+    pub missing_narrator: Option<bool>,
+    pub series: Option<String>,
+    pub genre: Option<String>,
+    pub search: Option<String>,
+    pub offset: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LibraryPage {
+    pub groups: Vec<BookGroup>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Chapter {
+    pub title: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cover {
+    pub mime: String,
+    pub data: Vec<u8>,
+    pub source: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PushStatus {
+    pub status: String,
+    pub pushed_version: Option<i64>,
+    pub error: Option<String>,
+}
+
+pub struct LibraryDb {
+    conn: Connection,
+}
+
+// ---- Paths ----
+
+fn library_db_path() -> Result<PathBuf, String> {
+    let dir = dirs::data_dir()
+        .ok_or("Cannot find data directory")?
+        .join("Audiobook Tagger");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Data dir error: {}", e))?;
+    Ok(dir.join("library.db"))
+}
+
+static LIBRARY: OnceLock<Mutex<LibraryDb>> = OnceLock::new();
+
+/// Run `f` against the shared library database, opening it on first use.
+pub fn with_library<T>(f: impl FnOnce(&mut LibraryDb) -> Result<T, String>) -> Result<T, String> {
+    if LIBRARY.get().is_none() {
+        let db = LibraryDb::open(&library_db_path()?)?;
+        let _ = LIBRARY.set(Mutex::new(db));
+    }
+    let lock = LIBRARY.get().ok_or("Library database unavailable")?;
+    let mut db = lock.lock().map_err(|_| "Library database lock poisoned".to_string())?;
+    f(&mut db)
+}
+
+fn now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+fn db_err(e: rusqlite::Error) -> String {
+    format!("Library database error: {}", e)
+}
+
+/// The folder a group was scanned from; stable across rescans, unlike group ids.
+fn group_folder(group: &BookGroup) -> String {
+    group
+        .files
+        .first()
+        .and_then(|f| Path::new(&f.path).parent())
+        .map(|p| p.to_string_lossy().to_string())
+        .or_else(|| group.abs_id.as_ref().map(|id| format!("abs:{}", id)))
+        .unwrap_or_else(|| format!("group:{}", group.id))
+}
+
+impl LibraryDb {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        Self::init(Connection::open(path).map_err(db_err)?)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self, String> {
+        Self::init(Connection::open_in_memory().map_err(db_err)?)
+    }
+
+    fn init(conn: Connection) -> Result<Self, String> {
+        conn.execute_batch("PRAGMA foreign_keys = ON; PRAGMA journal_mode = WAL;")
+            .map_err(db_err)?;
+        let mut db = LibraryDb { conn };
+        db.migrate()?;
+        Ok(db)
+    }
+
+    fn migrate(&mut self) -> Result<(), String> {
+        let current: usize = self
+            .conn
+            .query_row("PRAGMA user_version", [], |r| r.get::<_, i64>(0))
+            .map_err(db_err)? as usize;
+        for (i, sql) in MIGRATIONS.iter().enumerate().skip(current) {
+            let tx = self.conn.transaction().map_err(db_err)?;
+            tx.execute_batch(sql).map_err(db_err)?;
+            tx.pragma_update(None, "user_version", (i + 1) as i64).map_err(db_err)?;
+            tx.commit().map_err(db_err)?;
+        }
+        Ok(())
+    }
+
+    /// Persist a scan of the folders in `roots`. Groups already known by
+    /// folder keep their id, so the ids in `groups` are rewritten to the
+    /// stored ones. A new "scan" metadata version is only recorded when the
+    /// scanned metadata changed; only the fields that changed on disk since
+    /// the last scan are then applied to the current metadata, so edits to
+    /// the other fields survive. Files under `roots` that the scan didn't
+    /// find are dropped, along with groups left without files; returns how
+    /// many groups were dropped.
+    pub fn record_scan(&mut self, groups: &mut [BookGroup], roots: &[String]) -> Result<usize, String> {
+        let tx = self.conn.transaction().map_err(db_err)?;
+        let ts = now();
+        for group in groups.iter_mut() {
+            let folder = group_folder(group);
+            let existing: Option<String> = tx
+                .query_row("SELECT id FROM groups WHERE folder = ?1", params![folder], |r| r.get(0))
+                .optional()
+                .map_err(db_err)?;
+
+            match existing {
+                Some(id) => {
+                    group.id = id;
+                    tx.execute(
+                        "UPDATE groups SET group_name = ?2, group_type = ?3, updated_at = ?4 WHERE id = ?1",
+                        params![group.id, group.group_name, group.group_type, ts],
+                    )
+                    .map_err(db_err)?;
+                }
+                None => {
+                    tx.execute(
+                        "INSERT INTO groups (id, folder, group_name, group_type, scan_status, abs_id, metadata_json, created_at, updated_at)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, '{}', ?7, ?7)",
+                        params![group.id, folder, group.group_name, group.group_type, group.scan_status, group.abs_id, ts],
+                    )
+                    .map_err(db_err)?;
+                }
+            }
+
+            for (pos, file) in group.files.iter_mut().enumerate() {
+                let known: Option<String> = tx
+                    .query_row("SELECT id FROM files WHERE path = ?1", params![file.path], |r| r.get(0))
+                    .optional()
+                    .map_err(db_err)?;
+                if let Some(id) = known {
+                    file.id = id;
+                }
+                let meta = std::fs::metadata(&file.path).ok();
+                let size = meta.as_ref().map(|m| m.len() as i64);
+                let mtime = meta
+                    .and_then(|m| m.modified().ok())
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|d| d.as_secs() as i64);
+                tx.execute(
+                    "INSERT INTO files (id, group_id, path, filename, position, size, mtime, status)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                     ON CONFLICT(path) DO UPDATE SET group_id = ?2, filename = ?4, position = ?5, size = ?6, mtime = ?7",
+                    params![file.id, group.id, file.path, file.filename, pos as i64, size, mtime, file.status],
+                )
+                .map_err(db_err)?;
+            }
+
+            let scanned = serde_json::to_string(&group.metadata).map_err(|e| e.to_string())?;
+            let last_scan: Option<String> = tx
+                .query_row(
+                    "SELECT v.metadata_json FROM groups g
+                     JOIN metadata_versions v ON v.group_id = g.id AND v.version = g.scan_version
+                     WHERE g.id = ?1",
+                    params![group.id],
+                    |r| r.get(0),
+                )
+                .optional()
+                .map_err(db_err)?;
+            let has_version: bool = tx
+                .query_row("SELECT current_version > 0 FROM groups WHERE id = ?1", params![group.id], |r| r.get(0))
+                .map_err(db_err)?;
+            let current = if has_version { load_metadata(&tx, &group.id)? } else { None };
+
+            if last_scan.as_deref() == Some(scanned.as_str()) {
+                // Unchanged on disk: hand back the current metadata so edits
+                // made since the last scan aren't lost on rescan.
+                if let Some(current) = current {
+                    group.metadata = current;
+                }
+                continue;
+            }
+
+            let scan_version = match current {
+                None => insert_version(&tx, &group.id, &group.metadata, MetadataSource::Scan, ts)?,
+                Some(current) => {
+                    let version = append_version(&tx, &group.id, &group.metadata, MetadataSource::Scan, ts)?;
+                    let last_scan: Option<BookMetadata> = last_scan.and_then(|j| serde_json::from_str(&j).ok());
+                    let merged = apply_scan_changes(&current, last_scan.as_ref(), &group.metadata)?;
+                    if !diff_metadata(&current, &merged).is_empty() {
+                        insert_version(&tx, &group.id, &merged, MetadataSource::Scan, ts)?;
+                    }
+                    group.metadata = merged;
+                    version
+                }
+            };
+            tx.execute("UPDATE groups SET scan_version = ?2 WHERE id = ?1", params![group.id, scan_version])
+                .map_err(db_err)?;
+        }
+
+        let seen: HashSet<&str> = groups.iter().flat_map(|g| g.files.iter().map(|f| f.path.as_str())).collect();
+        let under_roots = |path: &str| roots.iter().any(|root| Path::new(path).starts_with(root));
+        let stale_files: Vec<String> = {
+            let mut stmt = tx.prepare("SELECT path FROM files").map_err(db_err)?;
+            let paths = stmt.query_map([], |r| r.get::<_, String>(0)).map_err(db_err)?;
+            paths
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(db_err)?
+                .into_iter()
+                .filter(|p| under_roots(p) && !seen.contains(p.as_str()))
+                .collect()
+        };
+        for path in &stale_files {
+            tx.execute("DELETE FROM files WHERE path = ?1", params![path]).map_err(db_err)?;
+        }
+        let empty_groups: Vec<String> = {
+            let mut stmt = tx
+                .prepare("SELECT id, folder FROM groups g WHERE NOT EXISTS (SELECT 1 FROM files f WHERE f.group_id = g.id)")
+                .map_err(db_err)?;
+            let rows = stmt.query_map([], |r| Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?))).map_err(db_err)?;
+            rows.collect::<Result<Vec<_>, _>>()
+                .map_err(db_err)?
+                .into_iter()
+                .filter(|(_, folder)| under_roots(folder))
+                .map(|(id, _)| id)
+                .collect()
+        };
+        for id in &empty_groups {
+            tx.execute("DELETE FROM groups WHERE id = ?1", params![id]).map_err(db_err)?;
+        }
+
+        tx.commit().map_err(db_err)?;
+        Ok(empty_groups.len())
+    }
+
+    /// Record a new metadata version for a group and make it current.
+    pub fn save_metadata(&mut self, group_id: &str, metadata: &BookMetadata, source: MetadataSource) -> Result<i64, String> {
+        let tx = self.conn.transaction().map_err(db_err)?;
+        let version = insert_version(&tx, group_id, metadata, source, now())?;
+        tx.commit().map_err(db_err)?;
+        Ok(version)
+    }
+
+    /// Every stored version of a group's metadata, oldest first.
+    pub fn history(&self, group_id: &str) -> Result<Vec<MetadataVersion>, String> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT v.version, v.source, v.created_at, v.metadata_json, v.version = g.current_version
+                 FROM metadata_versions v JOIN groups g ON g.id = v.group_id
+                 WHERE v.group_id = ?1 ORDER BY v.version",
+            )
+            .map_err(db_err)?;
+        let rows: Vec<(i64, String, i64, String, bool)> = stmt
+            .query_map(params![group_id], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?)))
+            .map_err(db_err)?
+            .collect::<Result<_, _>>()
+            .map_err(db_err)?;
+        rows.into_iter()
+            .map(|(version, source, created_at, json, current)| {
+                Ok(MetadataVersion {
+                    version,
+                    source: MetadataSource::parse(&source),
+                    created_at,
+                    metadata: serde_json::from_str(&json)
+                        .map_err(|e| format!("Corrupt metadata version {}: {}", version, e))?,
+                    current,
+                })
+            })
+            .collect()
+    }
+
+    fn version_metadata(&self, group_id: &str, version: i64) -> Result<BookMetadata, String> {
+        let json: String = self
+            .conn
+            .query_row(
+                "SELECT metadata_json FROM metadata_versions WHERE group_id = ?1 AND version = ?2",
+                params![group_id, version],
+                |r| r.get(0),
+            )
+            .optional()
+            .map_err(db_err)?
+            .ok_or_else(|| format!("No version {} for group {}", version, group_id))?;
+        serde_json::from_str(&json).map_err(|e| format!("Corrupt metadata version {}: {}", version, e))
+    }
+
+    /// Field-level differences going from version `from` to version `to`.
+    pub fn diff_versions(&self, group_id: &str, from: i64, to: i64) -> Result<Vec<FieldChange>, String> {
+        let a = self.version_metadata(group_id, from)?;
+        let b = self.version_metadata(group_id, to)?;
+        Ok(diff_metadata(&a, &b))
+    }
+
+    /// Copy `fields` from an older version onto the current metadata and
+    /// record the result as a new "revert" version.
+    pub fn revert_fields(&mut self, group_id: &str, version: i64, fields: &[String]) -> Result<i64, String> {
+        let old = metadata_fields(&self.version_metadata(group_id, version)?);
+        let current = load_metadata(&self.conn, group_id)?
+            .ok_or_else(|| format!("Unknown group: {}", group_id))?;
+        let mut merged = metadata_fields(&current);
+        for field in fields {
+            let value = old
+                .get(field)
+                .ok_or_else(|| format!("Unknown metadata field: {}", field))?;
+            merged.insert(field.clone(), value.clone());
+        }
+        let metadata: BookMetadata = serde_json::from_value(serde_json::Value::Object(merged))
+            .map_err(|e| format!("Revert produced invalid metadata: {}", e))?;
+        self.save_metadata(group_id, &metadata, MetadataSource::Revert)
+    }
+
+    pub fn query(&self, filter: &LibraryFilter) -> Result<LibraryPage, String> {
+        let mut clauses: Vec<&str> = Vec::new();
+        let mut args: Vec<String> = Vec::new();
+
+        if filter.missing_narrator == Some(true) {
+            clauses.push("g.narrator = ''");
+        }
+        if let Some(series) = filter.series.as_ref().filter(|s| !s.is_empty()) {
+            args.push(series.clone());
+            clauses.push("g.series = ?");
+        }
+        if let Some(genre) = filter.genre.as_ref().filter(|s| !s.is_empty()) {
+            args.push(genre.clone());
+            clauses.push("EXISTS (SELECT 1 FROM group_genres gg WHERE gg.group_id = g.id AND gg.genre = ?)");
+        }
+        if let Some(search) = filter.search.as_ref().filter(|s| !s.is_empty()) {
+            // Match "100%" and "_" literally
+            let escaped = search.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+            let like = format!("%{}%", escaped);
+            args.extend([like.clone(), like.clone(), like]);
+            clauses.push(r"(g.title LIKE ? ESCAPE '\' OR g.author LIKE ? ESCAPE '\' OR g.group_name LIKE ? ESCAPE '\')");
+        }
+
+        let where_sql = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", clauses.join(" AND "))
+        };
+
+        let total: i64 = self
+            .conn
+            .query_row(
+                &format!("SELECT COUNT(*) FROM groups g {}", where_sql),
+                rusqlite::params_from_iter(args.iter()),
+                |r| r.get(0),
+            )
+            .map_err(db_err)?;
+
+        let limit = filter.limit.unwrap_or(200) as i64;
+        let offset = filter.offset.unwrap_or(0) as i64;
+        let sql = format!(
+            "SELECT g.id FROM groups g {} ORDER BY g.series, CAST(g.series_number AS REAL), g.group_name COLLATE NOCASE
+             LIMIT {} OFFSET {}",
+            where_sql, limit, offset
+        );
+        let mut stmt = self.conn.prepare(&sql).map_err(db_err)?;
+        let ids: Vec<String> = stmt
+            .query_map(rusqlite::params_from_iter(args.iter()), |r| r.get(0))
+            .map_err(db_err)?
+            .collect::<Result<_, _>>()
+            .map_err(db_err)?;
+
+        let mut groups = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(g) = self.get_group(&id)? {
+                groups.push(g);
+            }
+        }
+        Ok(LibraryPage { groups, total: total as usize })
+    }
+
+    pub fn get_group(&self, id: &str) -> Result<Option<BookGroup>, String> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT group_name, group_type, scan_status, abs_id, metadata_json FROM groups WHERE id = ?1",
+                params![id],
+                |r| {
+                    Ok((
+                        r.get::<_, String>(0)?,
+                        r.get::<_, String>(1)?,
+                        r.get::<_, String>(2)?,
+                        r.get::<_, Option<String>>(3)?,
+                        r.get::<_, String>(4)?,
+                    ))
+                },
+            )
+            .optional()
+            .map_err(db_err)?;
+        let Some((group_name, group_type, scan_status, abs_id, metadata_json)) = row else {
+            return Ok(None);
+        };
+        let metadata: BookMetadata =
+            serde_json::from_str(&metadata_json).map_err(|e| format!("Corrupt metadata for {}: {}", id, e))?;
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, path, filename, status FROM files WHERE group_id = ?1 ORDER BY position")
+            .map_err(db_err)?;
+        let files: Vec<AudioFile> = stmt
+            .query_map(params![id], |r| {
+                Ok(AudioFile {
+                    id: r.get(0)?,
+                    path: r.get(1)?,
+                    filename: r.get(2)?,
+                    changes: HashMap::new(),
+                    status: r.get(3)?,
+                })
+            })
+            .map_err(db_err)?
+            .collect::<Result<_, _>>()
+            .map_err(db_err)?;
+
+        Ok(Some(BookGroup {
+            id: id.to_string(),
+            group_name,
+            group_type,
+            metadata,
+            files,
+            total_changes: 0,
+            scan_status,
+            abs_id,
+        }))
+    }
+
+    /// Current metadata of every group, skipping rows that no longer parse.
+    pub fn all_metadata(&self) -> Result<Vec<BookMetadata>, String> {
+        let mut stmt = self.conn.prepare("SELECT metadata_json FROM groups").map_err(db_err)?;
+        let rows = stmt.query_map([], |r| r.get::<_, String>(0)).map_err(db_err)?;
+        Ok(rows.filter_map(|json| serde_json::from_str(&json.ok()?).ok()).collect())
+    }
+
+    pub fn set_chapters(&mut self, group_id: &str, chapters: &[Chapter], source: &str) -> Result<(), String> {
+        let tx = self.conn.transaction().map_err(db_err)?;
+        tx.execute("DELETE FROM chapters WHERE group_id = ?1", params![group_id])
+            .map_err(db_err)?;
+        for (i, ch) in chapters.iter().enumerate() {
+            tx.execute(
+                "INSERT INTO chapters (group_id, idx, title, start_ms, end_ms, source) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![group_id, i as i64, ch.title, ch.start_ms as i64, ch.end_ms as i64, source],
+            )
+            .map_err(db_err)?;
+        }
+        tx.commit().map_err(db_err)
+    }
+
+    pub fn get_chapters(&self, group_id: &str) -> Result<Vec<Chapter>, String> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT title, start_ms, end_ms FROM chapters WHERE group_id = ?1 ORDER BY idx")
+            .map_err(db_err)?;
+        let rows = stmt
+            .query_map(params![group_id], |r| {
+                Ok(Chapter {
+                    title: r.get(0)?,
+                    start_ms: r.get::<_, i64>(1)? as u64,
+                    end_ms: r.get::<_, i64>(2)? as u64,
+                })
+            })
+            .map_err(db_err)?
+            .collect::<Result<_, _>>()
+            .map_err(db_err)?;
+        Ok(rows)
+    }
+
+    pub fn set_cover(&mut self, group_id: &str, cover: &Cover) -> Result<(), String> {
+        self.conn
+            .execute(
+                "INSERT INTO covers (group_id, mime, data, source, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(group_id) DO UPDATE SET mime = ?2, data = ?3, source = ?4, updated_at = ?5",
+                params![group_id, cover.mime, cover.data, cover.source, now()],
+            )
+            .map_err(db_err)?;
+        Ok(())
+    }
+
+    pub fn get_cover(&self, group_id: &str) -> Result<Option<Cover>, String> {
+        self.conn
+            .query_row(
+                "SELECT mime, data, source FROM covers WHERE group_id = ?1",
+                params![group_id],
+                |r| Ok(Cover { mime: r.get(0)?, data: r.get(1)?, source: r.get(2)? }),
+            )
+            .optional()
+            .map_err(db_err)
+    }
+
+    pub fn set_push_status(&mut self, group_id: &str, status: &PushStatus) -> Result<(), String> {
+        self.conn
+            .execute(
+                "INSERT INTO push_status (group_id, status, pushed_version, error, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(group_id) DO UPDATE SET status = ?2, pushed_version = ?3, error = ?4, updated_at = ?5",
+                params![group_id, status.status, status.pushed_version, status.error, now()],
+            )
+            .map_err(db_err)?;
+        Ok(())
+    }
+
+    pub fn get_push_status(&self, group_id: &str) -> Result<Option<PushStatus>, String> {
+        self.conn
+            .query_row(
+                "SELECT status, pushed_version, error FROM push_status WHERE group_id = ?1",
+                params![group_id],
+                |r| Ok(PushStatus { status: r.get(0)?, pushed_version: r.get(1)?, error: r.get(2)? }),
+            )
+            .optional()
+            .map_err(db_err)
+    }
+
+    /// Record the outcome of pushing a group to ABS. `pushed` is the metadata
+    /// ABS now holds; it becomes a `push` version when it differs from the
+    /// current one. A failed push keeps the version last pushed.
+    pub fn record_push(&mut self, group_id: &str, pushed: Option<&BookMetadata>, error: Option<String>) -> Result<(), String> {
+        let tx = self.conn.transaction().map_err(db_err)?;
+        let pushed_version = match pushed {
+            Some(metadata) => {
+                let current = load_metadata(&tx, group_id)?;
+                let changed = current.is_none_or(|c| !diff_metadata(&c, metadata).is_empty());
+                if changed {
+                    Some(insert_version(&tx, group_id, metadata, MetadataSource::Push, now())?)
+                } else {
+                    tx.query_row(
+                        "SELECT NULLIF(current_version, 0) FROM groups WHERE id = ?1",
+                        params![group_id],
+                        |r| r.get::<_, Option<i64>>(0),
+                    )
+                    .optional()
+                    .map_err(db_err)?
+                    .flatten()
+                }
+            }
+            None => None,
+        };
+        let status = if error.is_some() { "failed" } else { "pushed" };
+        tx.execute(
+            "INSERT INTO push_status (group_id, status, pushed_version, error, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(group_id) DO UPDATE SET status = ?2, pushed_version = COALESCE(?3, pushed_version), error = ?4, updated_at = ?5",
+            params![group_id, status, pushed_version, error, now()],
+        )
+        .map_err(db_err)?;
+        tx.commit().map_err(db_err)
+    }
+
+    /// Which source last set each metadata field of a group.
+    pub fn provenance(&self, group_id: &str) -> Result<HashMap<String, String>, String> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT field, source FROM provenance WHERE group_id = ?1")
+            .map_err(db_err)?;
+        let rows = stmt
+            .query_map(params![group_id], |r| Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?)))
+            .map_err(db_err)?
+            .collect::<Result<_, _>>()
+            .map_err(db_err)?;
+        Ok(rows)
+    }
+}
+
+fn load_metadata(conn: &Connection, group_id: &str) -> Result<Option<BookMetadata>, String> {
+    let json: Option<String> = conn
+        .query_row("SELECT metadata_json FROM groups WHERE id = ?1", params![group_id], |r| r.get(0))
+        .optional()
+        .map_err(db_err)?;
+    Ok(json.and_then(|j| serde_json::from_str(&j).ok()))
+}
+
+/// Top-level metadata fields as JSON values, for provenance and diffing.
+pub(crate) fn metadata_fields(metadata: &BookMetadata) -> serde_json::Map<String, serde_json::Value> {
+    match serde_json::to_value(metadata) {
+        Ok(serde_json::Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    }
+}
+
+/// Fields whose values differ between `before` and `after`, sorted by field name.
+pub fn diff_metadata(before: &BookMetadata, after: &BookMetadata) -> Vec<FieldChange> {
+    let a = metadata_fields(before);
+    let b = metadata_fields(after);
+    b.into_iter()
+        .filter_map(|(field, after)| {
+            let before = a.get(&field).cloned().unwrap_or(serde_json::Value::Null);
+            (before != after).then_some(FieldChange { field, before, after })
+        })
+        .collect()
+}
+
+/// Move onto `current` the fields whose scanned value changed since
+/// `last_scan`; every other field keeps its current value. Without an
+/// earlier scan to compare against, every scanned field counts as changed.
+fn apply_scan_changes(
+    current: &BookMetadata,
+    last_scan: Option<&BookMetadata>,
+    scanned: &BookMetadata,
+) -> Result<BookMetadata, String> {
+    let before = last_scan.map(metadata_fields).unwrap_or_default();
+    let mut merged = metadata_fields(current);
+    for (field, value) in metadata_fields(scanned) {
+        if before.get(&field) != Some(&value) {
+            merged.insert(field, value);
+        }
+    }
+    serde_json::from_value(serde_json::Value::Object(merged))
+        .map_err(|e| format!("Rescan produced invalid metadata: {}", e))
+}
+
+/// Append a metadata version without touching the group's current
+/// metadata. Returns the new version number.
+fn append_version(
+    conn: &Connection,
+    group_id: &str,
+    metadata: &BookMetadata,
+    source: MetadataSource,
+    ts: i64,
+) -> Result<i64, String> {
+    let json = serde_json::to_string(metadata).map_err(|e| e.to_string())?;
+    let version: i64 = conn
+        .query_row(
+            "SELECT COALESCE(MAX(version), 0) + 1 FROM metadata_versions WHERE group_id = ?1",
+            params![group_id],
+            |r| r.get(0),
+        )
+        .map_err(db_err)?;
+    conn.execute(
+        "INSERT INTO metadata_versions (group_id, version, source, metadata_json, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![group_id, version, source.as_str(), json, ts],
+    )
+    .map_err(db_err)?;
+    Ok(version)
+}
+
+/// Append a metadata version, update the group's current metadata, genre
+/// index and per-field provenance. Returns the new version number.
+fn insert_version(
+    conn: &Connection,
+    group_id: &str,
+    metadata: &BookMetadata,
+    source: MetadataSource,
+    ts: i64,
+) -> Result<i64, String> {
+    let previous = load_metadata(conn, group_id)?;
+    let json = serde_json::to_string(metadata).map_err(|e| e.to_string())?;
+    let version = append_version(conn, group_id, metadata, source, ts)?;
+    let source = source.as_str();
+
+    let changed = conn
+        .execute(
+            "UPDATE groups SET title = ?2, author = ?3, narrator = ?4, series = ?5, series_number = ?6, year = ?7,
+             metadata_json = ?8, current_version = ?9, updated_at = ?10 WHERE id = ?1",
+            params![
+                group_id, metadata.title, metadata.author, metadata.narrator, metadata.series,
+                metadata.series_number, metadata.year, json, version, ts
+            ],
+        )
+        .map_err(db_err)?;
+    if changed == 0 {
+        return Err(format!("Unknown group: {}", group_id));
+    }
+
+    conn.execute("DELETE FROM group_genres WHERE group_id = ?1", params![group_id])
+        .map_err(db_err)?;
+    for genre in &metadata.genres {
+        conn.execute(
+            "INSERT OR IGNORE INTO group_genres (group_id, genre) VALUES (?1, ?2)",
+            params![group_id, genre],
+        )
+        .map_err(db_err)?;
+    }
+
+    let before = previous.as_ref().map(metadata_fields).unwrap_or_default();
+    for (field, value) in metadata_fields(metadata) {
+        if before.get(&field) != Some(&value) {
+            conn.execute(
+                "INSERT INTO provenance (group_id, field, source, version, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(group_id, field) DO UPDATE SET source = ?3, version = ?4, updated_at = ?5",
+                params![group_id, field, source, version, ts],
+            )
+            .map_err(db_err)?;
+        }
+    }
+
+    Ok(version)
+}
+
+// ---- Tauri commands ----
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn library_query(filter: LibraryFilter) -> Result<LibraryPage, AppError> {
+    with_library(|db| db.query(&filter)).map_err(AppError::database)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn library_get_group(group_id: String) -> Result<Option<BookGroup>, AppError> {
+    with_library(|db| db.get_group(&group_id)).map_err(AppError::database)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn library_save_metadata(group_id: String, metadata: serde_json::Value, source: MetadataSource) -> Result<i64, AppError> {
+    let metadata = BookMetadata::from_frontend(metadata)?;
+    with_library(|db| db.save_metadata(&group_id, &metadata, source)).map_err(AppError::database)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn library_get_history(group_id: String) -> Result<Vec<MetadataVersion>, AppError> {
+    with_library(|db| db.history(&group_id)).map_err(AppError::database)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn library_diff_versions(group_id: String, from: i64, to: i64) -> Result<Vec<FieldChange>, AppError> {
+    with_library(|db| db.diff_versions(&group_id, from, to)).map_err(AppError::database)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn library_revert_fields(group_id: String, version: i64, fields: Vec<String>) -> Result<i64, AppError> {
+    with_library(|db| db.revert_fields(&group_id, version, &fields)).map_err(AppError::database)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn library_get_provenance(group_id: String) -> Result<HashMap<String, String>, AppError> {
+    with_library(|db| db.provenance(&group_id)).map_err(AppError::database)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn library_set_chapters(group_id: String, chapters: Vec<Chapter>, source: String) -> Result<(), AppError> {
+    with_library(|db| db.set_chapters(&group_id, &chapters, &source)).map_err(AppError::database)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn library_get_chapters(group_id: String) -> Result<Vec<Chapter>, AppError> {
+    with_library(|db| db.get_chapters(&group_id)).map_err(AppError::database)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn library_set_cover(group_id: String, cover: Cover) -> Result<(), AppError> {
+    with_library(|db| db.set_cover(&group_id, &cover)).map_err(AppError::database)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn library_get_cover(group_id: String) -> Result<Option<Cover>, AppError> {
+    with_library(|db| db.get_cover(&group_id)).map_err(AppError::database)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn library_set_push_status(group_id: String, status: PushStatus) -> Result<(), AppError> {
+    with_library(|db| db.set_push_status(&group_id, &status)).map_err(AppError::database)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn library_get_push_status(group_id: String) -> Result<Option<PushStatus>, AppError> {
+    with_library(|db| db.get_push_status(&group_id)).map_err(AppError::database)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(folder: &str, title: &str, narrator: &str, series: &str, genre: &str) -> BookGroup {
+        BookGroup {
+            id: uuid::Uuid::new_v4().to_string(),
+            group_name: title.to_string(),
+            group_type: "single".to_string(),
+            metadata: BookMetadata {
+                title: title.to_string(),
+                author: "Brandon Sanderson".to_string(),
+                narrator: narrator.to_string(),
+                series: series.to_string(),
+                series_number: "1".to_string(),
+                year: String::new(),
+                genres: vec![genre.to_string()],
+                tags: vec![],
+                description: String::new(),
+                age_rating: String::new(),
+                series_list: vec![],
+                authors: vec![],
+                narrators: vec![],
+            },
+            files: vec![AudioFile {
+                id: uuid::Uuid::new_v4().to_string(),
+                path: format!("{}/book.m4b", folder),
+                filename: "book.m4b".to_string(),
+                changes: HashMap::new(),
+                status: "unchanged".to_string(),
+            }],
+            total_changes: 0,
+            scan_status: "not_scanned".to_string(),
+            abs_id: None,
+        }
+    }
+
+    #[test]
+    fn rescan_keeps_group_ids_and_edits() {
+        let mut db = LibraryDb::open_in_memory().unwrap();
+        let mut first = vec![group("/lib/Mistborn", "Mistborn", "", "Mistborn", "Fantasy")];
+        db.record_scan(&mut first, &[]).unwrap();
+        let id = first[0].id.clone();
+
+        let mut edited = first[0].metadata.clone();
+        edited.narrator = "Michael Kramer".to_string();
+        db.save_metadata(&id, &edited, MetadataSource::Manual).unwrap();
+
+        let mut second = vec![group("/lib/Mistborn", "Mistborn", "", "Mistborn", "Fantasy")];
+        db.record_scan(&mut second, &[]).unwrap();
+        assert_eq!(second[0].id, id);
+        assert_eq!(second[0].metadata.narrator, "Michael Kramer");
+        assert_eq!(db.provenance(&id).unwrap().get("narrator").map(String::as_str), Some("manual"));
+    }
+
+    #[test]
+    fn rescan_applies_only_fields_changed_on_disk() {
+        let mut db = LibraryDb::open_in_memory().unwrap();
+        let mut first = vec![group("/lib/Mistborn", "Mistborn", "", "Mistborn", "Fantasy")];
+        db.record_scan(&mut first, &[]).unwrap();
+        let id = first[0].id.clone();
+
+        let mut edited = first[0].metadata.clone();
+        edited.narrator = "Michael Kramer".to_string();
+        edited.genres = vec!["Epic Fantasy".to_string()];
+        db.save_metadata(&id, &edited, MetadataSource::Manual).unwrap();
+
+        // The year tag was set on disk outside the app
+        let mut second = vec![group("/lib/Mistborn", "Mistborn", "", "Mistborn", "Fantasy")];
+        second[0].metadata.year = "2006".to_string();
+        db.record_scan(&mut second, &[]).unwrap();
+
+        let current = db.get_group(&id).unwrap().unwrap().metadata;
+        assert_eq!(current.year, "2006");
+        assert_eq!(current.narrator, "Michael Kramer");
+        assert_eq!(current.genres, ["Epic Fantasy"]);
+        assert!(diff_metadata(&second[0].metadata, &current).is_empty());
+        let provenance = db.provenance(&id).unwrap();
+        assert_eq!(provenance.get("year").map(String::as_str), Some("scan"));
+        assert_eq!(provenance.get("narrator").map(String::as_str), Some("manual"));
+
+        // The scan as read is kept in the history, but isn't current
+        let history = db.history(&id).unwrap();
+        let raw = &history[history.len() - 2];
+        assert_eq!((raw.source, raw.current), (MetadataSource::Scan, false));
+        assert_eq!(raw.metadata.narrator, "");
+        assert!(history.last().unwrap().current);
+
+        // Rescanning the same files changes nothing
+        let mut third = vec![group("/lib/Mistborn", "Mistborn", "", "Mistborn", "Fantasy")];
+        third[0].metadata.year = "2006".to_string();
+        db.record_scan(&mut third, &[]).unwrap();
+        assert_eq!(db.history(&id).unwrap().len(), history.len());
+        assert_eq!(third[0].metadata.narrator, "Michael Kramer");
+    }
+
+    #[test]
+    fn rescan_drops_books_no_longer_on_disk() {
+        let mut db = LibraryDb::open_in_memory().unwrap();
+        let roots = ["/lib".to_string()];
+        let mut groups = vec![
+            group("/lib/a", "Elantris", "", "", "Fantasy"),
+            group("/lib/b", "Warbreaker", "", "", "Fantasy"),
+            group("/other/c", "Skyward", "", "", "Science Fiction"),
+        ];
+        assert_eq!(db.record_scan(&mut groups, &roots).unwrap(), 0);
+
+        // /lib/b was deleted; /other wasn't part of this scan
+        let mut rescan = vec![group("/lib/a", "Elantris", "", "", "Fantasy")];
+        assert_eq!(db.record_scan(&mut rescan, &roots).unwrap(), 1);
+        let titles: Vec<String> = db.all_metadata().unwrap().into_iter().map(|m| m.title).collect();
+        assert_eq!(titles.len(), 2);
+        assert!(!titles.contains(&"Warbreaker".to_string()));
+    }
+
+    #[test]
+    fn history_diff_and_field_revert() {
+        let mut db = LibraryDb::open_in_memory().unwrap();
+        let mut groups = vec![group("/lib/a", "Elantris", "", "", "Fantasy")];
+        db.record_scan(&mut groups, &[]).unwrap();
+        let id = groups[0].id.clone();
+
+        let mut ai = groups[0].metadata.clone();
+        ai.narrator = "Jack Garrett".to_string();
+        ai.genres = vec!["Epic Fantasy".to_string()];
+        let v2 = db.save_metadata(&id, &ai, MetadataSource::Ai).unwrap();
+
+        let history = db.history(&id).unwrap();
+        assert_eq!(history.iter().map(|v| v.source).collect::<Vec<_>>(), [MetadataSource::Scan, MetadataSource::Ai]);
+
+        let diff = db.diff_versions(&id, 1, v2).unwrap();
+        let fields: Vec<&str> = diff.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, ["genres", "narrator"]);
+        assert_eq!(diff[1].after, serde_json::json!("Jack Garrett"));
+
+        // Undo only the AI genre change, keep its narrator
+        let v3 = db.revert_fields(&id, 1, &["genres".to_string()]).unwrap();
+        let current = db.get_group(&id).unwrap().unwrap().metadata;
+        assert_eq!(current.genres, ["Fantasy"]);
+        assert_eq!(current.narrator, "Jack Garrett");
+        assert_eq!(db.provenance(&id).unwrap().get("genres").map(String::as_str), Some("revert"));
+        assert_eq!(v3, 3);
+    }
+
+    #[test]
+    fn push_status_tracks_the_pushed_version() {
+        let mut db = LibraryDb::open_in_memory().unwrap();
+        let mut groups = vec![group("/lib/a", "Elantris", "", "", "Fantasy")];
+        db.record_scan(&mut groups, &[]).unwrap();
+        let id = groups[0].id.clone();
+
+        let mut pushed = groups[0].metadata.clone();
+        pushed.narrator = "Jack Garrett".to_string();
+        db.record_push(&id, Some(&pushed), None).unwrap();
+        let status = db.get_push_status(&id).unwrap().unwrap();
+        assert_eq!((status.status.as_str(), status.pushed_version), ("pushed", Some(2)));
+        assert_eq!(db.history(&id).unwrap()[1].source, MetadataSource::Push);
+
+        // Pushing the same metadata again adds no version
+        db.record_push(&id, Some(&pushed), None).unwrap();
+        assert_eq!(db.history(&id).unwrap().len(), 2);
+
+        db.record_push(&id, None, Some("ABS unreachable".to_string())).unwrap();
+        let status = db.get_push_status(&id).unwrap().unwrap();
+        assert_eq!(status.status, "failed");
+        assert_eq!(status.pushed_version, Some(2));
+        assert_eq!(status.error.as_deref(), Some("ABS unreachable"));
+    }
+
+    #[test]
+    fn query_filters_by_narrator_series_and_genre() {
+        let mut db = LibraryDb::open_in_memory().unwrap();
+        let mut groups = vec![
+            group("/lib/a", "The Way of Kings", "Kate Reading", "Stormlight Archive", "Fantasy"),
+            group("/lib/b", "Elantris", "", "", "Fantasy"),
+            group("/lib/c", "Skyward", "", "Skyward", "Science Fiction"),
+        ];
+        db.record_scan(&mut groups, &[]).unwrap();
+
+        let missing = db.query(&LibraryFilter { missing_narrator: Some(true), ..Default::default() }).unwrap();
+        assert_eq!(missing.total, 2);
+
+        let series = db.query(&LibraryFilter { series: Some("Stormlight Archive".into()), ..Default::default() }).unwrap();
+        assert_eq!(series.groups[0].metadata.title, "The Way of Kings");
+
+        let genre = db.query(&LibraryFilter { genre: Some("Fantasy".into()), limit: Some(1), ..Default::default() }).unwrap();
+        assert_eq!((genre.total, genre.groups.len()), (2, 1));
+
+        // LIKE wildcards in the search text match literally
+        let search = |text: &str| db.query(&LibraryFilter { search: Some(text.into()), ..Default::default() }).unwrap().total;
+        assert_eq!(search("Kings"), 1);
+        assert_eq!(search("%"), 0);
+        assert_eq!(search("_"), 0);
+        assert_eq!(db.all_metadata().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn chapters_and_covers_round_trip() {
+        let mut db = LibraryDb::open_in_memory().unwrap();
+        let mut groups = vec![group("/lib/a", "Elantris", "", "", "Fantasy")];
+        db.record_scan(&mut groups, &[]).unwrap();
+        let id = &groups[0].id;
+
+        let chapters = vec![
+            Chapter { title: "Prologue".into(), start_ms: 0, end_ms: 60_000 },
+            Chapter { title: "Chapter 1".into(), start_ms: 60_000, end_ms: 120_000 },
+        ];
+        db.set_chapters(id, &chapters, "file").unwrap();
+        assert_eq!(db.get_chapters(id).unwrap()[1].title, "Chapter 1");
+
+        db.set_cover(id, &Cover { mime: "image/jpeg".into(), data: vec![1, 2, 3], source: "embedded".into() }).unwrap();
+        assert_eq!(db.get_cover(id).unwrap().unwrap().data, vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,288 @@
+// src-tauri/src/names.rs
+// Person name handling: co-author splitting, "Last, First" inversion,
+// initials normalisation and role stripping ("(Translator)", "- editor").
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PersonRole {
+    Author,
+    Narrator,
+    Translator,
+    Editor,
+    Illustrator,
+    Introduction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Person {
+    pub name: String,
+    pub role: PersonRole,
+}
+
+const NAME_PARTICLES: &[&str] = &["de", "van", "von", "le", "la", "del", "da", "di", "el", "al", "du", "der", "den", "st.", "mac"];
+const NAME_SUFFIXES: &[&str] = &["jr", "jr.", "sr", "sr.", "ii", "iii", "iv", "phd", "ph.d.", "md", "m.d."];
+
+/// Map a role word ("Translator", "ed.", "foreword") to a role.
+fn parse_role(word: &str) -> Option<PersonRole> {
+    let w = word.trim().trim_end_matches('.').to_lowercase();
+    match w.as_str() {
+        "author" | "writer" => Some(PersonRole::Author),
+        "narrator" | "reader" | "read by" | "narrated by" | "performer" => Some(PersonRole::Narrator),
+        "translator" | "translated by" | "trans" | "tr" => Some(PersonRole::Translator),
+        "editor" | "editors" | "ed" | "eds" | "edited by" => Some(PersonRole::Editor),
+        "illustrator" | "illustrated by" | "illustrations" => Some(PersonRole::Illustrator),
+        "foreword" | "introduction" | "afterword" | "foreword by" | "introduction by" => Some(PersonRole::Introduction),
+        _ => None,
+    }
+}
+
+/// Split a raw author/narrator field into people. `default_role` applies to
+/// names that don't carry an explicit role marker.
+///
+/// Handles `A & B`, `A and B`, `A; B`, `A / B`, `Last, First`,
+/// `Last, First & Last, First`, `First Last, First Last` and trailing roles.
+pub fn split_people(raw: &str, default_role: PersonRole) -> Vec<Person> {
+    use std::sync::OnceLock;
+    static SEPARATORS: OnceLock<regex::Regex> = OnceLock::new();
+    let sep = SEPARATORS.get_or_init(|| regex::Regex::new(r"(?i)\s*(?:;|/|&|\band\b)\s*").unwrap());
+
+    let mut people: Vec<Person> = Vec::new();
+    for chunk in sep.split(raw) {
+        let chunk = chunk.trim();
+        if chunk.is_empty() {
+            continue;
+        }
+        for (name, role) in split_comma_chunk(chunk) {
+            let (name, stripped_role) = strip_role(&name);
+            let name = normalize_name(&name);
+            if name.is_empty() {
+                continue;
+            }
+            let role = role.or(stripped_role).unwrap_or(default_role);
+            if !people.iter().any(|p| p.name == name && p.role == role) {
+                people.push(Person { name, role });
+            }
+        }
+    }
+    people
+}
+
+/// Resolve commas inside one `&`/`and`-free chunk. Returns names in reading
+/// order together with any role given as its own comma part ("X, editor").
+fn split_comma_chunk(chunk: &str) -> Vec<(String, Option<PersonRole>)> {
+    let parts: Vec<&str> = chunk.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()).collect();
+    let mut out: Vec<(String, Option<PersonRole>)> = Vec::new();
+
+    let mut i = 0;
+    while i < parts.len() {
+        let part = parts[i];
+
+        // Role or suffix given as its own comma part attaches to the previous name.
+        if let Some(last) = out.last_mut() {
+            if let Some(role) = parse_role(part) {
+                last.1 = Some(role);
+                i += 1;
+                continue;
+            }
+            if NAME_SUFFIXES.contains(&part.to_lowercase().as_str()) {
+                last.0 = format!("{} {}", last.0, part);
+                i += 1;
+                continue;
+            }
+        }
+
+        if let Some(next) = parts.get(i + 1) {
+            if is_inverted_pair(part, next) {
+                out.push((format!("{} {}", next, part), None));
+                i += 2;
+                continue;
+            }
+        }
+
+        out.push((part.to_string(), None));
+        i += 1;
+    }
+    out
+}
+
+/// `Sanderson, Brandon` / `Le Guin, Ursula K.` / `Tolkien, J.R.R.` are one
+/// inverted name; `Brandon Sanderson, Dan Wells` is a list.
+fn is_inverted_pair(surname: &str, given: &str) -> bool {
+    if parse_role(given).is_some() || NAME_SUFFIXES.contains(&given.to_lowercase().as_str()) {
+        return false;
+    }
+    let surname_words: Vec<&str> = surname.split_whitespace().collect();
+    let given_words = given.split_whitespace().count();
+    if surname_words.is_empty() || given_words == 0 || given_words > 3 {
+        return false;
+    }
+    if surname.contains('.') {
+        return false; // initials belong to given names, so this is already "First Last"
+    }
+    match surname_words.len() {
+        1 => true,
+        2 => NAME_PARTICLES.contains(&surname_words[0].to_lowercase().as_str()),
+        _ => false,
+    }
+}
+
+/// Strip a role marker: "Name (Translator)", "Name - editor", "translated by Name".
+fn strip_role(raw: &str) -> (String, Option<PersonRole>) {
+    let mut name = raw.trim().to_string();
+    let mut role = None;
+
+    if let (Some(open), true) = (name.rfind('('), name.ends_with(')')) {
+        let inner = &name[open + 1..name.len() - 1];
+        if let Some(r) = parse_role(inner) {
+            role = Some(r);
+            name = name[..open].trim().to_string();
+        }
+    }
+
+    for dash in [" - ", " – ", " — "] {
+        if let Some(pos) = name.rfind(dash) {
+            if let Some(r) = parse_role(&name[pos + dash.len()..]) {
+                role = Some(r);
+                name = name[..pos].trim().to_string();
+            }
+        }
+    }
+
+    let lower = name.to_lowercase();
+    for prefix in ["translated by ", "edited by ", "read by ", "narrated by ", "illustrated by ", "foreword by ", "introduction by "] {
+        if lower.starts_with(prefix) {
+            role = parse_role(prefix.trim());
+            name = name[prefix.len()..].trim().to_string();
+            break;
+        }
+    }
+
+    (name, role)
+}
+
+/// Collapse whitespace and space out initials: "J.R.R.  Tolkien" and
+/// "J R R Tolkien" both become "J. R. R. Tolkien".
+pub fn normalize_name(raw: &str) -> String {
+    let mut words: Vec<String> = Vec::new();
+    let tokens: Vec<&str> = raw.split_whitespace().collect();
+    for token in &tokens {
+        let letters: Vec<&str> = token.split('.').filter(|s| !s.is_empty()).collect();
+        let is_initials = token.contains('.')
+            && letters.len() > 1
+            && letters.iter().all(|l| l.chars().count() == 1 && l.chars().all(char::is_uppercase));
+        if is_initials {
+            words.extend(letters.iter().map(|l| format!("{}.", l)));
+        } else if tokens.len() > 1 && token.chars().count() == 1 && token.chars().all(char::is_uppercase) {
+            words.push(format!("{}.", token));
+        } else {
+            words.push(token.to_string());
+        }
+    }
+    words.join(" ").trim_end_matches([',', ';', ':']).to_string()
+}
+
+/// Clean a person name extracted from transcript.
+/// Strips trailing non-name words (book text that got captured),
+/// sentence boundaries, and common false matches. `stop_words` are the
+/// transcript language's words that never belong to a name.
+pub fn clean_person_name(raw: &str, stop_words: &[&str]) -> String {
+    let words: Vec<&str> = raw.split_whitespace().collect();
+    let mut clean_words = Vec::new();
+
+    for word in &words {
+        let full = word.to_lowercase();
+        let lower = full.trim_end_matches(['.', ',', ';', ':']);
+
+        // Stop at common words (not part of a name)
+        if stop_words.contains(&lower) {
+            break;
+        }
+
+        // Stop at lowercase words that aren't initials (names are capitalized)
+        // Allow name particles like "de", "van", "von"
+        if word.chars().next().map(|c| c.is_lowercase()).unwrap_or(false)
+            && !NAME_PARTICLES.contains(&lower)
+            && !NAME_PARTICLES.contains(&full.as_str())
+        {
+            break;
+        }
+
+        clean_words.push(*word);
+
+        // A full stop ends the sentence, unless it ends an initial or "St."
+        let abbreviated = NAME_PARTICLES.contains(&full.as_str())
+            || lower.split('.').all(|part| part.chars().count() == 1);
+        if word.ends_with('.') && !abbreviated {
+            break;
+        }
+    }
+
+    // A person name should be 1-5 words
+    if clean_words.len() > 5 {
+        clean_words.truncate(5);
+    }
+
+    let result = clean_words.join(" ");
+    result.trim_end_matches(['.', ',', ';', ':']).trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(raw: &str) -> Vec<String> {
+        split_people(raw, PersonRole::Author).into_iter().map(|p| p.name).collect()
+    }
+
+    #[test]
+    fn splits_co_authors_on_separators() {
+        assert_eq!(names("Brandon Sanderson, Dan Wells"), ["Brandon Sanderson", "Dan Wells"]);
+        assert_eq!(names("Preston & Child"), ["Preston", "Child"]);
+        assert_eq!(names("Terry Pratchett and Neil Gaiman"), ["Terry Pratchett", "Neil Gaiman"]);
+        assert_eq!(names("A. Author; B. Writer / C. Scribe"), ["A. Author", "B. Writer", "C. Scribe"]);
+    }
+
+    #[test]
+    fn resolves_last_first_inversion() {
+        assert_eq!(names("Sanderson, Brandon"), ["Brandon Sanderson"]);
+        assert_eq!(names("Le Guin, Ursula K."), ["Ursula K. Le Guin"]);
+        assert_eq!(names("Preston, Douglas & Child, Lincoln"), ["Douglas Preston", "Lincoln Child"]);
+        assert_eq!(names("Tolkien, J.R.R."), ["J. R. R. Tolkien"]);
+        assert_eq!(names("Martin Luther King, Jr."), ["Martin Luther King Jr."]);
+    }
+
+    #[test]
+    fn normalizes_initials() {
+        assert_eq!(normalize_name("J.R.R. Tolkien"), "J. R. R. Tolkien");
+        assert_eq!(normalize_name("J. R. R.  Tolkien"), "J. R. R. Tolkien");
+        assert_eq!(normalize_name("J R R Tolkien"), "J. R. R. Tolkien");
+    }
+
+    #[test]
+    fn strips_roles() {
+        let people = split_people("Gregory Rabassa (Translator)", PersonRole::Author);
+        assert_eq!(people, [Person { name: "Gregory Rabassa".into(), role: PersonRole::Translator }]);
+
+        let people = split_people("Neil Gaiman - editor", PersonRole::Author);
+        assert_eq!(people[0].role, PersonRole::Editor);
+
+        let people = split_people("Gabriel García Márquez, Gregory Rabassa, translator", PersonRole::Author);
+        assert_eq!(people.len(), 2);
+        assert_eq!(people[0].role, PersonRole::Author);
+        assert_eq!(people[1], Person { name: "Gregory Rabassa".into(), role: PersonRole::Translator });
+    }
+
+    #[test]
+    fn clean_person_name_stops_at_book_text() {
+        let stop_words = &["and", "chapter", "was"];
+        assert_eq!(clean_person_name("Michael Kramer and Kate Reading", stop_words), "Michael Kramer");
+        assert_eq!(clean_person_name("Kate Reading. Chapter One", stop_words), "Kate Reading");
+        assert_eq!(clean_person_name("Ludwig van Beethoven was", stop_words), "Ludwig van Beethoven");
+        assert_eq!(clean_person_name("Kate Reading Chapter One", stop_words), "Kate Reading");
+        assert_eq!(clean_person_name("Ann St. John. Chapter One", stop_words), "Ann St. John");
+        assert_eq!(clean_person_name("Jean st. Clair", stop_words), "Jean st. Clair");
+        assert_eq!(clean_person_name("J. R. R. Tolkien. Chapter One", stop_words), "J. R. R. Tolkien");
+    }
+}
@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::Mutex;
-use tauri::Emitter;
+
+use crate::error::{AppError, ErrorKind};
+use crate::progress::{ProgressEvent, ProgressSink};
 
 const OLLAMA_PORT: u16 = 11434;
 const OLLAMA_DEFAULT_BASE: &str = "http://127.0.0.1:11434";
@@ -126,8 +128,8 @@ fn find_best_binary() -> Option<PathBuf> {
     find_system_ollama()
 }
 
-#[tauri::command]
-pub async fn ollama_get_status(base_url: Option<String>) -> Result<OllamaStatus, String> {
+#[cfg_attr(feature = "gui", tauri::command)]
+pub async fn ollama_get_status(base_url: Option<String>) -> Result<OllamaStatus, AppError> {
     let base = effective_base(base_url.as_deref().unwrap_or(""));
     let installed = find_best_binary().is_some();
     let running = is_running(&base).await;
@@ -156,13 +158,13 @@ pub async fn ollama_get_status(base_url: Option<String>) -> Result<OllamaStatus,
     Ok(OllamaStatus { installed: installed || running, running, models, version })
 }
 
-#[tauri::command]
+#[cfg_attr(feature = "gui", tauri::command)]
 pub fn ollama_get_model_presets() -> Vec<ModelPreset> {
     MODEL_PRESETS.to_vec()
 }
 
-#[tauri::command]
-pub async fn ollama_get_disk_usage() -> Result<u64, String> {
+#[cfg_attr(feature = "gui", tauri::command)]
+pub async fn ollama_get_disk_usage() -> Result<u64, AppError> {
     let models_dir = ollama_models_dir()?;
     if !models_dir.exists() { return Ok(0); }
     let mut total: u64 = 0;
@@ -174,14 +176,16 @@ pub async fn ollama_get_disk_usage() -> Result<u64, String> {
     Ok(total)
 }
 
-#[tauri::command]
-pub async fn ollama_start(base_url: Option<String>) -> Result<String, String> {
+#[cfg_attr(feature = "gui", tauri::command)]
+pub async fn ollama_start(base_url: Option<String>) -> Result<String, AppError> {
     let base = effective_base(base_url.as_deref().unwrap_or(""));
     if is_running(&base).await {
         return Ok("Ollama is already running".to_string());
     }
-    let binary = find_best_binary()
-        .ok_or_else(|| "Ollama is not installed. Install it via the button above, or install Ollama system-wide from https://ollama.com/download".to_string())?;
+    let binary = find_best_binary().ok_or_else(|| {
+        AppError::new(ErrorKind::NotFound, "Ollama is not installed")
+            .with_hint("Install it via the button above, or install Ollama system-wide from https://ollama.com/download")
+    })?;
     let models_dir = ollama_models_dir()?;
     std::fs::create_dir_all(&models_dir).map_err(|e| format!("Failed to create models dir: {}", e))?;
 
@@ -218,11 +222,11 @@ pub async fn ollama_start(base_url: Option<String>) -> Result<String, String> {
             return Ok(format!("Ollama started (PID {})", pid));
         }
     }
-    Err("Ollama started but didn't become responsive within 15 seconds".to_string())
+    Err(AppError::ollama_not_running().with_detail("Started but didn't become responsive within 15 seconds"))
 }
 
-#[tauri::command]
-pub async fn ollama_stop() -> Result<String, String> {
+#[cfg_attr(feature = "gui", tauri::command)]
+pub async fn ollama_stop() -> Result<String, AppError> {
     let pid = OLLAMA_PID.lock().ok().and_then(|mut guard| guard.take());
     if let Some(pid) = pid {
         #[cfg(unix)]
@@ -251,12 +255,13 @@ pub async fn ollama_stop() -> Result<String, String> {
     Ok("Ollama stop signal sent".to_string())
 }
 
-#[tauri::command]
-pub async fn ollama_install() -> Result<String, String> {
+#[cfg_attr(feature = "gui", tauri::command)]
+pub async fn ollama_install() -> Result<String, AppError> {
     let dir = ollama_dir()?;
     // Windows: bundled install not supported — tell user to install manually
     #[cfg(target_os = "windows")]
-    return Err("Windows: Please download Ollama from https://ollama.com/download and install manually.".to_string());
+    return Err(AppError::new(ErrorKind::NotFound, "Bundled Ollama install is not available on Windows")
+        .with_hint("Download Ollama from https://ollama.com/download and install it manually"));
 
     #[cfg(not(target_os = "windows"))]
     {
@@ -276,7 +281,7 @@ pub async fn ollama_install() -> Result<String, String> {
         .map_err(|e| format!("HTTP client error: {}", e))?;
     let resp = client.get(url).send().await.map_err(|e| format!("Download failed: {}", e))?;
     if !resp.status().is_success() {
-        return Err(format!("Download failed with status {}", resp.status()));
+        return Err(AppError::http_status("Ollama download", resp.status().as_u16(), ""));
     }
     let bytes = resp.bytes().await.map_err(|e| format!("Failed to read download: {}", e))?;
 
@@ -358,8 +363,8 @@ fn install_from_bytes(_bytes: &[u8], _binary_path: &PathBuf) -> Result<(), Strin
     Err("Windows: Please download Ollama from https://ollama.com/download and install manually.".to_string())
 }
 
-#[tauri::command]
-pub async fn ollama_uninstall() -> Result<String, String> {
+#[cfg_attr(feature = "gui", tauri::command)]
+pub async fn ollama_uninstall() -> Result<String, AppError> {
     let _ = ollama_stop().await;
     let dir = ollama_dir()?;
     if dir.exists() {
@@ -368,15 +373,21 @@ pub async fn ollama_uninstall() -> Result<String, String> {
     Ok("Ollama uninstalled".to_string())
 }
 
+#[cfg(feature = "gui")]
 #[tauri::command]
-pub async fn ollama_pull_model(app_handle: tauri::AppHandle, model_name: String, base_url: Option<String>) -> Result<String, String> {
-    let base = effective_base(base_url.as_deref().unwrap_or(""));
+pub async fn ollama_pull_model(app_handle: tauri::AppHandle, model_name: String, base_url: Option<String>) -> Result<String, AppError> {
+    pull_model(&model_name, base_url.as_deref(), &app_handle).await
+}
+
+/// Pull a model through the running Ollama server, streaming its progress.
+pub async fn pull_model(model_name: &str, base_url: Option<&str>, progress: &dyn ProgressSink) -> Result<String, AppError> {
+    let base = effective_base(base_url.unwrap_or(""));
     let model_name = model_name.trim().to_string();
     if model_name.is_empty() || model_name.len() > 200 || model_name.contains("..") || model_name.contains("/") {
-        return Err("Invalid model name".to_string());
+        return Err(AppError::invalid_input(format!("Invalid model name: '{}'", model_name)));
     }
     if !is_running(&base).await {
-        return Err("Ollama is not running. Start it first.".to_string());
+        return Err(AppError::ollama_not_running());
     }
     let client = reqwest::Client::new();
     let resp = client
@@ -384,11 +395,11 @@ pub async fn ollama_pull_model(app_handle: tauri::AppHandle, model_name: String,
         .json(&serde_json::json!({ "name": model_name, "stream": true }))
         .timeout(std::time::Duration::from_secs(1200))
         .send().await
-        .map_err(|e| format!("Pull failed: {}", e))?;
+        .map_err(|e| AppError::request_failed("Ollama", &e))?;
 
     if !resp.status().is_success() {
         let text = resp.text().await.unwrap_or_default();
-        return Err(format!("Pull failed: {}", text));
+        return Err(AppError::new(ErrorKind::ApiError, format!("Ollama could not pull '{}'", model_name)).with_detail(text));
     }
 
     // Stream the response line by line, emit progress events
@@ -399,7 +410,7 @@ pub async fn ollama_pull_model(app_handle: tauri::AppHandle, model_name: String,
 
     tokio::pin!(stream);
     while let Some(chunk) = stream.next().await {
-        let chunk = chunk.map_err(|e| format!("Stream error: {}", e))?;
+        let chunk = chunk.map_err(|e| AppError::request_failed("Ollama", &e))?;
         buffer.push_str(&String::from_utf8_lossy(&chunk));
 
         if buffer.len() > 1_000_000 {
@@ -417,12 +428,7 @@ pub async fn ollama_pull_model(app_handle: tauri::AppHandle, model_name: String,
                 let total = json["total"].as_u64().unwrap_or(0);
                 let status = json["status"].as_str().unwrap_or("").to_string();
 
-                let _ = app_handle.emit("ollama-pull-progress", serde_json::json!({
-                    "completed": completed,
-                    "total": total,
-                    "status": status,
-                    "model": model_name,
-                }));
+                progress.report(ProgressEvent::pull(&model_name, completed, total, status));
             }
         }
     }
@@ -430,22 +436,22 @@ pub async fn ollama_pull_model(app_handle: tauri::AppHandle, model_name: String,
     Ok(format!("Model '{}' pulled successfully", model_name))
 }
 
-#[tauri::command]
-pub async fn ollama_delete_model(model_name: String, base_url: Option<String>) -> Result<String, String> {
+#[cfg_attr(feature = "gui", tauri::command)]
+pub async fn ollama_delete_model(model_name: String, base_url: Option<String>) -> Result<String, AppError> {
     let base = effective_base(base_url.as_deref().unwrap_or(""));
     if !is_running(&base).await {
-        return Err("Ollama is not running".to_string());
+        return Err(AppError::ollama_not_running());
     }
     let client = reqwest::Client::new();
     let resp = client
         .delete(format!("{}/api/delete", base))
         .json(&serde_json::json!({ "name": model_name }))
         .send().await
-        .map_err(|e| format!("Delete failed: {}", e))?;
+        .map_err(|e| AppError::request_failed("Ollama", &e))?;
 
     if !resp.status().is_success() {
         let text = resp.text().await.unwrap_or_default();
-        return Err(format!("Delete failed: {}", text));
+        return Err(AppError::new(ErrorKind::ApiError, format!("Ollama could not delete '{}'", model_name)).with_detail(text));
     }
     Ok(format!("Model '{}' deleted", model_name))
 }
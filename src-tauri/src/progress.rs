@@ -0,0 +1,325 @@
+// src-tauri/src/progress.rs
+// Progress reporting that doesn't depend on a Tauri window, so the same
+// pipelines can run from the desktop app, the headless CLI and unit tests.
+
+use serde::Serialize;
+use std::sync::mpsc;
+
+use crate::jobs::JobInfo;
+
+/// Where a long-running operation currently is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    Downloading,
+    Installing,
+    Extracting,
+    DeepScan,
+    Transcribing,
+    Parsing,
+    Cached,
+    FfmpegDone,
+    Complete,
+    Cancelled,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ByteProgress {
+    pub done: u64,
+    pub total: u64,
+}
+
+impl ByteProgress {
+    /// Whole percent, or None when the total size is unknown.
+    pub fn percent(&self) -> Option<u32> {
+        if self.total == 0 {
+            None
+        } else {
+            Some((self.done as f64 / self.total as f64 * 100.0) as u32)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    /// Whisper intro extraction for book `current` of `total`. `found` and
+    /// `cached` are running batch counts, only set by the batch loop.
+    AudioIntro {
+        stage: Stage,
+        current: usize,
+        total: usize,
+        found: Option<usize>,
+        cached: Option<usize>,
+        message: String,
+    },
+    /// whisper-cpp, FFmpeg or Whisper model install.
+    WhisperInstall {
+        stage: Stage,
+        bytes: Option<ByteProgress>,
+        message: String,
+    },
+    /// Ollama model pull; `message` is Ollama's own status line.
+    OllamaPull {
+        model: String,
+        bytes: ByteProgress,
+        message: String,
+    },
+    /// Full-book transcription: `current` of `total` chunks done.
+    Transcription {
+        stage: Stage,
+        current: usize,
+        total: usize,
+        message: String,
+    },
+    /// Library database bookkeeping that isn't part of another pipeline,
+    /// e.g. a scan that couldn't be recorded.
+    Library {
+        stage: Stage,
+        message: String,
+    },
+    /// A queued job changed state or made progress.
+    Job(Box<JobInfo>),
+}
+
+impl ProgressEvent {
+    pub fn intro(stage: Stage, current: usize, total: usize, message: impl Into<String>) -> Self {
+        ProgressEvent::AudioIntro { stage, current, total, found: None, cached: None, message: message.into() }
+    }
+
+    pub fn install(stage: Stage, message: impl Into<String>) -> Self {
+        ProgressEvent::WhisperInstall { stage, bytes: None, message: message.into() }
+    }
+
+    pub fn transcription(stage: Stage, current: usize, total: usize, message: impl Into<String>) -> Self {
+        ProgressEvent::Transcription { stage, current, total, message: message.into() }
+    }
+
+    pub fn library(stage: Stage, message: impl Into<String>) -> Self {
+        ProgressEvent::Library { stage, message: message.into() }
+    }
+
+    pub fn pull(model: impl Into<String>, done: u64, total: u64, message: impl Into<String>) -> Self {
+        ProgressEvent::OllamaPull { model: model.into(), bytes: ByteProgress { done, total }, message: message.into() }
+    }
+
+    /// Attach batch counts to an `AudioIntro` event.
+    pub fn with_counts(mut self, found_count: usize, cached_count: usize) -> Self {
+        if let ProgressEvent::AudioIntro { found, cached, .. } = &mut self {
+            *found = Some(found_count);
+            *cached = Some(cached_count);
+        }
+        self
+    }
+
+    /// Attach download sizes to a `WhisperInstall` event.
+    pub fn with_bytes(mut self, done: u64, total: u64) -> Self {
+        if let ProgressEvent::WhisperInstall { bytes, .. } = &mut self {
+            *bytes = Some(ByteProgress { done, total });
+        }
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            ProgressEvent::AudioIntro { message, .. }
+            | ProgressEvent::WhisperInstall { message, .. }
+            | ProgressEvent::OllamaPull { message, .. }
+            | ProgressEvent::Transcription { message, .. }
+            | ProgressEvent::Library { message, .. } => message,
+            ProgressEvent::Job(job) => &job.progress.message,
+        }
+    }
+
+    pub fn stage(&self) -> Option<Stage> {
+        match self {
+            ProgressEvent::AudioIntro { stage, .. }
+            | ProgressEvent::WhisperInstall { stage, .. }
+            | ProgressEvent::Transcription { stage, .. }
+            | ProgressEvent::Library { stage, .. } => Some(*stage),
+            ProgressEvent::OllamaPull { .. } | ProgressEvent::Job(_) => None,
+        }
+    }
+
+    /// Name of the window event the frontend listens for.
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            ProgressEvent::AudioIntro { .. } => "audio_intro_progress",
+            ProgressEvent::WhisperInstall { .. } => "whisper_install_progress",
+            ProgressEvent::OllamaPull { .. } => "ollama-pull-progress",
+            ProgressEvent::Transcription { .. } => "transcription_progress",
+            ProgressEvent::Library { .. } => "library_progress",
+            ProgressEvent::Job(_) => "job_progress",
+        }
+    }
+
+    /// Payload in the shape the frontend listeners already read.
+    pub fn to_payload(&self) -> serde_json::Value {
+        match self {
+            ProgressEvent::AudioIntro { stage, current, total, found, cached, message } => {
+                let mut payload = serde_json::json!({
+                    "current": current, "total": total,
+                    "stage": stage,
+                    "status": message,
+                });
+                if let (Some(found), Some(cached)) = (found, cached) {
+                    payload["found"] = serde_json::json!(found);
+                    payload["cached"] = serde_json::json!(cached);
+                }
+                payload
+            }
+            ProgressEvent::WhisperInstall { stage, bytes, message } => {
+                let mut payload = serde_json::json!({ "stage": stage, "status": message });
+                if let Some(b) = bytes {
+                    payload["percent"] = serde_json::json!(b.percent().unwrap_or(0));
+                    payload["downloaded"] = serde_json::json!(b.done);
+                    payload["total"] = serde_json::json!(b.total);
+                }
+                payload
+            }
+            ProgressEvent::OllamaPull { model, bytes, message } => serde_json::json!({
+                "completed": bytes.done,
+                "total": bytes.total,
+                "status": message,
+                "model": model,
+            }),
+            ProgressEvent::Transcription { stage, current, total, message } => serde_json::json!({
+                "current": current, "total": total,
+                "stage": stage,
+                "status": message,
+            }),
+            ProgressEvent::Library { stage, message } => serde_json::json!({ "stage": stage, "status": message }),
+            ProgressEvent::Job(job) => serde_json::to_value(job).unwrap_or_default(),
+        }
+    }
+}
+
+/// Receiver for progress events.
+pub trait ProgressSink: Send + Sync {
+    fn report(&self, event: ProgressEvent);
+}
+
+#[cfg(feature = "gui")]
+impl ProgressSink for tauri::Window {
+    fn report(&self, event: ProgressEvent) {
+        let _ = tauri::Emitter::emit(self, event.event_name(), event.to_payload());
+    }
+}
+
+#[cfg(feature = "gui")]
+impl ProgressSink for tauri::AppHandle {
+    fn report(&self, event: ProgressEvent) {
+        let _ = tauri::Emitter::emit(self, event.event_name(), event.to_payload());
+    }
+}
+
+/// Discards everything.
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+    fn report(&self, _event: ProgressEvent) {}
+}
+
+/// Forwards events to a channel, for tests and front ends that poll.
+pub struct ChannelProgress {
+    sender: mpsc::Sender<ProgressEvent>,
+}
+
+impl ChannelProgress {
+    pub fn new() -> (Self, mpsc::Receiver<ProgressEvent>) {
+        let (sender, receiver) = mpsc::channel();
+        (ChannelProgress { sender }, receiver)
+    }
+}
+
+impl ProgressSink for ChannelProgress {
+    fn report(&self, event: ProgressEvent) {
+        // The receiver going away just means nobody is watching any more.
+        let _ = self.sender.send(event);
+    }
+}
+
+/// Prints one line per event to stderr, keeping stdout free for JSON output.
+/// Repeated lines (download chunks within the same percent) are dropped.
+#[derive(Default)]
+pub struct TerminalProgress {
+    last_line: std::sync::Mutex<String>,
+}
+
+impl TerminalProgress {
+    fn render(event: &ProgressEvent) -> String {
+        match event {
+            ProgressEvent::AudioIntro { current, total, message, .. }
+            | ProgressEvent::Transcription { current, total, message, .. } => {
+                format!("[{}/{}] {}", current, total, message)
+            }
+            ProgressEvent::WhisperInstall { message, .. } | ProgressEvent::Library { message, .. } => message.clone(),
+            ProgressEvent::OllamaPull { bytes, message, .. } => match bytes.percent() {
+                Some(pct) => format!("{} {}%", message, pct),
+                None => message.clone(),
+            },
+            ProgressEvent::Job(job) => format!("{}: {}", job.label, job.progress.message),
+        }
+    }
+}
+
+impl ProgressSink for TerminalProgress {
+    fn report(&self, event: ProgressEvent) {
+        let line = Self::render(&event);
+        if let Ok(mut last) = self.last_line.lock() {
+            if *last == line {
+                return;
+            }
+            last.clone_from(&line);
+        }
+        eprintln!("{}", line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intro_payload_matches_frontend_shape() {
+        let event = ProgressEvent::intro(Stage::DeepScan, 2, 5, "Deep scan: Dune").with_counts(1, 0);
+        assert_eq!(event.event_name(), "audio_intro_progress");
+        assert_eq!(
+            event.to_payload(),
+            serde_json::json!({
+                "current": 2, "total": 5, "found": 1, "cached": 0,
+                "stage": "deep_scan", "status": "Deep scan: Dune",
+            })
+        );
+
+        let event = ProgressEvent::intro(Stage::Parsing, 1, 1, "Parsing transcript: Dune");
+        assert!(event.to_payload().get("found").is_none());
+    }
+
+    #[test]
+    fn download_payload_reports_percent() {
+        let event = ProgressEvent::install(Stage::Downloading, "Downloading Base: 25%").with_bytes(25, 100);
+        let payload = event.to_payload();
+        assert_eq!(payload["percent"], 25);
+        assert_eq!(payload["downloaded"], 25);
+        assert_eq!(payload["total"], 100);
+
+        let pull = ProgressEvent::pull("llama3.2:3b", 0, 0, "pulling manifest");
+        assert_eq!(pull.event_name(), "ollama-pull-progress");
+        assert_eq!(pull.to_payload()["model"], "llama3.2:3b");
+        assert_eq!(TerminalProgress::render(&pull), "pulling manifest");
+    }
+
+    #[test]
+    fn channel_sink_delivers_events_in_order() {
+        let (sink, rx) = ChannelProgress::new();
+        let progress: &dyn ProgressSink = &sink;
+        progress.report(ProgressEvent::install(Stage::Downloading, "Downloading FFmpeg..."));
+        progress.report(ProgressEvent::install(Stage::Complete, "FFmpeg installed"));
+        drop(sink);
+
+        let stages: Vec<_> = rx.iter().filter_map(|e| e.stage()).collect();
+        assert_eq!(stages, [Stage::Downloading, Stage::Complete]);
+    }
+}
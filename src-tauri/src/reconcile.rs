@@ -0,0 +1,384 @@
+// src-tauri/src/reconcile.rs
+// Checks what the intro pipeline heard against what is already known: the
+// book's current metadata, the authority file and the rest of the library.
+// Near-misses ("Brandon Sandersen", "Condeed") are corrected to the known
+// spelling, each field is marked confirmed, new or conflicting, and fields
+// nothing agrees with lose confidence.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::authority::{levenshtein, name_similarity, AuthorityStore};
+use crate::names::{split_people, PersonRole};
+use crate::scanner::BookMetadata;
+use crate::whisper::AudioIntroResult;
+
+/// Similarity at or above which a heard value is taken for a known one.
+const NEAR_MISS: f32 = 0.8;
+/// Confidence kept by a field that contradicts the book's metadata.
+const CONFLICT_PENALTY: f32 = 0.5;
+/// Confidence kept by a new field that nothing known agrees with.
+const UNVERIFIED_PENALTY: f32 = 0.8;
+/// Words a title can add without naming another book ("Circe: A Novel",
+/// "Dune (Unabridged)").
+const EDITION_WORDS: &[&str] = &[
+    "unabridged", "abridged", "a", "novel", "audiobook", "audio", "edition",
+    "dramatized", "dramatised", "full", "cast", "version",
+];
+/// Words that, with a number, end a series suffix ("Stormlight Archive, Book 1").
+const SERIES_WORDS: &[&str] = &["book", "volume", "vol", "part"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldStatus {
+    /// Agrees with the book's current metadata.
+    Confirmed,
+    /// The book has no value for this field yet.
+    New,
+    /// Differs from the book's current metadata.
+    Conflicting,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldCheck {
+    pub status: FieldStatus,
+    /// Whether the authority file or a book in the library knows the value.
+    pub known: bool,
+    /// What the transcript said, when it was corrected to a known spelling.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heard: Option<String>,
+    /// The book's current value, when the transcript disagrees with it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current: Option<String>,
+}
+
+/// Names and titles to check findings against, loaded once per batch.
+#[derive(Debug, Clone, Default)]
+pub struct Reference {
+    authority: AuthorityStore,
+    people: Vec<String>,
+    titles: Vec<String>,
+}
+
+impl Reference {
+    /// The authority file and every book in the local library.
+    pub fn load() -> Reference {
+        let library = crate::library::with_library(|db| db.all_metadata()).unwrap_or_default();
+        Reference::new(AuthorityStore::load(), &library)
+    }
+
+    pub fn new(authority: AuthorityStore, library: &[BookMetadata]) -> Reference {
+        let mut people = Vec::new();
+        let mut titles = Vec::new();
+        for book in library {
+            people.extend(book_people(book, PersonRole::Author));
+            people.extend(book_people(book, PersonRole::Narrator));
+            if !book.title.trim().is_empty() {
+                titles.push(book.title.trim().to_string());
+            }
+        }
+        people.sort();
+        people.dedup();
+        titles.sort();
+        titles.dedup();
+        Reference { authority, people, titles }
+    }
+
+    /// Canonical spelling of a person the authority file or library knows.
+    fn known_person(&self, name: &str) -> Option<String> {
+        if let Some(entry) = self.authority.resolve(name) {
+            return Some(entry.name.clone());
+        }
+        let authority = self.authority.people.iter().flat_map(|e| {
+            std::iter::once((&e.name, &e.name)).chain(e.aliases.iter().map(move |a| (a, &e.name)))
+        });
+        let library = self.people.iter().map(|p| (p, p));
+        best_match(authority.chain(library), |(variant, _)| person_similarity(name, variant))
+            .map(|(_, canonical)| self.authority.canonicalize(canonical))
+    }
+
+    fn known_title(&self, title: &str) -> Option<String> {
+        best_match(self.titles.iter(), |t| title_similarity(title, t)).cloned()
+    }
+}
+
+/// Correct near-misses in `result` and record a status for its title,
+/// authors and narrators. `current` is the book's metadata before the scan.
+pub fn reconcile(result: &mut AudioIntroResult, current: &BookMetadata, reference: &Reference) {
+    let mut checks = BTreeMap::new();
+    if let Some(heard) = result.title.take() {
+        let (title, check) = check_title(&heard, &current.title, reference);
+        result.title = Some(title);
+        checks.insert("title".to_string(), check);
+    }
+    for (field, role) in [("authors", PersonRole::Author), ("narrators", PersonRole::Narrator)] {
+        let heard = if role == PersonRole::Author { &mut result.authors } else { &mut result.narrators };
+        if heard.is_empty() {
+            continue;
+        }
+        let (names, check) = check_people(heard, &book_people(current, role), reference);
+        *heard = names;
+        checks.insert(field.to_string(), check);
+    }
+
+    let mut overall = 1.0f32;
+    for (field, check) in &checks {
+        let factor = match check.status {
+            FieldStatus::Conflicting => CONFLICT_PENALTY,
+            FieldStatus::New if !check.known => UNVERIFIED_PENALTY,
+            _ => 1.0,
+        };
+        if let Some(score) = result.field_confidence.get_mut(field) {
+            score.confidence *= factor;
+        }
+        overall = overall.min(factor);
+    }
+    result.confidence *= overall;
+    result.field_status = checks;
+}
+
+fn check_title(heard: &str, current: &str, reference: &Reference) -> (String, FieldCheck) {
+    let current = current.trim();
+    let known = reference.known_title(heard);
+    let agrees = !current.is_empty()
+        && (title_similarity(heard, current) >= NEAR_MISS || same_book_title(&title_key(heard), &title_key(current)));
+
+    let (title, status) = if agrees {
+        (current.to_string(), FieldStatus::Confirmed)
+    } else if current.is_empty() {
+        (known.clone().unwrap_or_else(|| heard.to_string()), FieldStatus::New)
+    } else {
+        (heard.to_string(), FieldStatus::Conflicting)
+    };
+    let check = FieldCheck {
+        status,
+        known: known.is_some(),
+        heard: (title != heard).then(|| heard.to_string()),
+        current: (status == FieldStatus::Conflicting).then(|| current.to_string()),
+    };
+    (title, check)
+}
+
+/// Confirmed when the transcript names anyone the book already lists.
+fn check_people(heard: &[String], current: &[String], reference: &Reference) -> (Vec<String>, FieldCheck) {
+    let mut names: Vec<String> = Vec::new();
+    let (mut agreed, mut known, mut corrected) = (0, 0, false);
+    for name in heard {
+        let known_as = reference.known_person(name);
+        known += usize::from(known_as.is_some());
+        let spelled = match best_match(current.iter(), |c| person_similarity(name, c)) {
+            Some(listed) => {
+                agreed += 1;
+                listed.clone()
+            }
+            None => known_as.unwrap_or_else(|| name.clone()),
+        };
+        corrected |= spelled != *name;
+        if !names.contains(&spelled) {
+            names.push(spelled);
+        }
+    }
+
+    let status = if current.is_empty() {
+        FieldStatus::New
+    } else if agreed > 0 {
+        FieldStatus::Confirmed
+    } else {
+        FieldStatus::Conflicting
+    };
+    let check = FieldCheck {
+        status,
+        known: known == heard.len(),
+        heard: corrected.then(|| heard.join(", ")),
+        current: (status == FieldStatus::Conflicting).then(|| current.join(", ")),
+    };
+    (names, check)
+}
+
+/// Authors (or narrators) of a book, from the split list or the display string.
+fn book_people(book: &BookMetadata, role: PersonRole) -> Vec<String> {
+    let (people, display) = match role {
+        PersonRole::Narrator => (&book.narrators, &book.narrator),
+        _ => (&book.authors, &book.author),
+    };
+    if !people.is_empty() {
+        return people.iter().filter(|p| p.role == role).map(|p| p.name.clone()).collect();
+    }
+    split_people(display, role).into_iter().filter(|p| p.role == role).map(|p| p.name).collect()
+}
+
+/// The candidate scoring highest at or above `NEAR_MISS`.
+fn best_match<T>(candidates: impl Iterator<Item = T>, score: impl Fn(&T) -> f32) -> Option<T> {
+    candidates
+        .map(|c| (score(&c), c))
+        .filter(|(s, _)| *s >= NEAR_MISS)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, c)| c)
+}
+
+fn person_similarity(a: &str, b: &str) -> f32 {
+    let score = name_similarity(a, b);
+    if sounds_alike(a, b) { score.max(NEAR_MISS) } else { score }
+}
+
+/// Similarity in 0..=1 of two titles, ignoring case, punctuation and a
+/// leading article.
+fn title_similarity(a: &str, b: &str) -> f32 {
+    let (ka, kb) = (title_key(a), title_key(b));
+    if ka.is_empty() || kb.is_empty() {
+        return 0.0;
+    }
+    let max_len = ka.chars().count().max(kb.chars().count()) as f32;
+    let score = 1.0 - levenshtein(&ka, &kb) as f32 / max_len;
+    if sounds_alike(&ka, &kb) { score.max(NEAR_MISS) } else { score }
+}
+
+/// Whether one title key is the other plus only edition markers or a series
+/// suffix: "Dune" and "Dune (Unabridged)" are the same book, "Dune Messiah"
+/// is not.
+fn same_book_title(a: &str, b: &str) -> bool {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if short.chars().count() < 4 {
+        return false;
+    }
+    let long = format!(" {} ", long);
+    let Some(pos) = long.find(&format!(" {} ", short)) else {
+        return false;
+    };
+    let leftover = format!("{}{}", &long[..pos], &long[pos + short.len() + 2..]);
+    let words: Vec<&str> = leftover.split_whitespace().collect();
+    let series_suffix = matches!(
+        words.as_slice(),
+        [.., word, number] if SERIES_WORDS.contains(word) && number.chars().all(|c| c.is_ascii_digit())
+    );
+    series_suffix || words.iter().all(|w| EDITION_WORDS.contains(w))
+}
+
+fn title_key(title: &str) -> String {
+    let key = fold(title);
+    ["the ", "a ", "an "]
+        .iter()
+        .find_map(|article| key.strip_prefix(article))
+        .map(str::to_string)
+        .unwrap_or(key)
+}
+
+fn fold(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Same consonants word for word: "Condeed" and "Candide" both come out as
+/// "cnd". Catches misheard vowels, which edit distance scores too low in
+/// short words.
+fn sounds_alike(a: &str, b: &str) -> bool {
+    let (a, b) = (fold(a), fold(b));
+    let (wa, wb): (Vec<&str>, Vec<&str>) = (a.split_whitespace().collect(), b.split_whitespace().collect());
+    if wa.len() != wb.len() || wa.is_empty() {
+        return false;
+    }
+    let skeletons: Vec<(String, String)> = wa.iter().zip(&wb).map(|(x, y)| (skeleton(x), skeleton(y))).collect();
+    skeletons.iter().all(|(x, y)| x == y) && skeletons.iter().map(|(x, _)| x.len()).sum::<usize>() >= 3
+}
+
+/// First letter, then the consonants with repeats collapsed.
+fn skeleton(word: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in word.chars().enumerate() {
+        if i > 0 && "aeiouy".contains(c) {
+            continue;
+        }
+        if !out.ends_with(c) {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authority::AuthorityEntry;
+    use crate::whisper::{FieldConfidence, FieldSource};
+
+    fn book(title: &str, author: &str, narrator: &str) -> BookMetadata {
+        BookMetadata { title: title.into(), author: author.into(), narrator: narrator.into(), ..Default::default() }
+    }
+
+    fn heard(title: &str, authors: &[&str], narrators: &[&str]) -> AudioIntroResult {
+        let mut result: AudioIntroResult = serde_json::from_value(serde_json::json!({
+            "item_id": "a", "transcript": "...", "title": title, "subtitle": null,
+            "authors": authors, "narrators": narrators, "publisher": null, "audio_publisher": null,
+            "language": "en", "parse_method": "llm", "confidence": 1.0,
+        }))
+        .unwrap();
+        for field in ["title", "authors", "narrators"] {
+            result.field_confidence.insert(field.into(), FieldConfidence { source: FieldSource::Intro, confidence: 1.0 });
+        }
+        result
+    }
+
+    #[test]
+    fn corrects_near_misses_and_marks_each_field() {
+        let authority = AuthorityStore {
+            people: vec![AuthorityEntry { id: "1".into(), name: "Michael Kramer".into(), aliases: vec!["Kramer, Michael".into()] }],
+        };
+        let library = [book("Candide", "Voltaire", ""), book("Mistborn", "Brandon Sanderson", "Michael Kramer")];
+        let reference = Reference::new(authority, &library);
+
+        // Untagged book: corrected against the library, nothing to contradict
+        let mut result = heard("Condeed", &["Voltair"], &["Michael Kramer", "Kate Reading"]);
+        reconcile(&mut result, &BookMetadata::default(), &reference);
+        assert_eq!(result.title.as_deref(), Some("Candide"));
+        assert_eq!(result.authors, vec!["Voltaire"]);
+        let title = &result.field_status["title"];
+        assert_eq!((title.status, title.known, title.heard.as_deref()), (FieldStatus::New, true, Some("Condeed")));
+        // Kate Reading is unknown to the library, so the narrators lose some confidence
+        assert!(!result.field_status["narrators"].known);
+        assert!((result.field_confidence["narrators"].confidence - UNVERIFIED_PENALTY).abs() < 1e-6);
+        assert!((result.field_confidence["authors"].confidence - 1.0).abs() < 1e-6);
+
+        // Tagged book: the transcript confirms the title and author, misspelled,
+        // and contradicts the narrator
+        let current = book("The Way of Kings (Unabridged)", "Brandon Sanderson", "Kate Reading");
+        let mut result = heard("The Way of Kings", &["Brandon Sandersen"], &["Tim Gerard Reynolds"]);
+        reconcile(&mut result, &current, &reference);
+        assert_eq!(result.title.as_deref(), Some("The Way of Kings (Unabridged)"));
+        assert_eq!(result.authors, vec!["Brandon Sanderson"]);
+        assert_eq!(result.field_status["authors"].status, FieldStatus::Confirmed);
+        assert_eq!(result.field_status["authors"].heard.as_deref(), Some("Brandon Sandersen"));
+        let narrators = &result.field_status["narrators"];
+        assert_eq!((narrators.status, narrators.current.as_deref()), (FieldStatus::Conflicting, Some("Kate Reading")));
+        assert_eq!(result.narrators, vec!["Tim Gerard Reynolds"]);
+        assert!((result.confidence - CONFLICT_PENALTY).abs() < 1e-6);
+    }
+
+    #[test]
+    fn sequels_conflict_with_the_first_book() {
+        let reference = Reference::new(AuthorityStore::default(), &[]);
+        for (heard_title, current) in [("Dune Messiah", "Dune"), ("Mistborn: The Well of Ascension", "Mistborn")] {
+            let mut result = heard(heard_title, &[], &[]);
+            reconcile(&mut result, &book(current, "", ""), &reference);
+            assert_eq!(result.title.as_deref(), Some(heard_title));
+            let title = &result.field_status["title"];
+            assert_eq!((title.status, title.current.as_deref()), (FieldStatus::Conflicting, Some(current)));
+        }
+    }
+
+    #[test]
+    fn matches_titles_and_names_by_sound() {
+        assert!(sounds_alike("Condeed", "Candide"));
+        assert!(!sounds_alike("Dune", "Dan"));
+        assert!(title_similarity("The Hobbit", "Hobbit") > 0.99);
+        assert!(title_similarity("Dune", "Emma") < NEAR_MISS);
+        assert!(same_book_title(&title_key("Circe: A Novel"), &title_key("Circe")));
+        assert!(same_book_title(&title_key("The Way of Kings"), &title_key("The Way of Kings: The Stormlight Archive, Book 1")));
+        assert!(person_similarity("Brandon Sandersen", "Brandon Sanderson") >= NEAR_MISS);
+        assert!(person_similarity("Scott Brick", "Kate Reading") < NEAR_MISS);
+    }
+}
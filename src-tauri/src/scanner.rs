@@ -1,4 +1,4 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
 use walkdir::WalkDir;
@@ -8,7 +8,13 @@ use lofty::prelude::{Accessor, ItemKey};
 use lofty::probe::Probe;
 use lofty::tag::ItemValue;
 
-const AUDIO_EXTENSIONS: &[&str] = &["m4b", "m4a", "mp3", "flac", "ogg", "opus", "aac"];
+use crate::authority::AuthorityStore;
+use crate::error::AppError;
+use crate::names::{split_people, Person, PersonRole};
+use crate::progress::{ProgressEvent, ProgressSink, Stage};
+use crate::series::{split_series_value, SeriesEntry, SeriesPosition};
+
+pub const AUDIO_EXTENSIONS: &[&str] = &["m4b", "m4a", "mp3", "flac", "ogg", "opus", "aac"];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioFile {
@@ -19,18 +25,81 @@ pub struct AudioFile {
     pub status: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Metadata as scanned, and as the frontend sends it back after edits. Missing
+/// fields default so a partially edited book still deserializes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct BookMetadata {
     pub title: String,
     pub author: String,
     pub narrator: String,
     pub series: String,
+    #[serde(deserialize_with = "text_or_number")]
     pub series_number: String,
+    #[serde(deserialize_with = "text_or_number")]
     pub year: String,
     pub genres: Vec<String>,
     pub tags: Vec<String>,
     pub description: String,
     pub age_rating: String,
+    /// Every series the book belongs to. `series`/`series_number` mirror the first entry.
+    #[serde(default)]
+    pub series_list: Vec<SeriesEntry>,
+    /// `author` split into individual people, with roles such as translator or editor.
+    #[serde(deserialize_with = "authors_list")]
+    pub authors: Vec<Person>,
+    #[serde(deserialize_with = "narrators_list")]
+    pub narrators: Vec<Person>,
+}
+
+/// Years and series positions are numbers once edited in the UI.
+fn text_or_number<'de, D: Deserializer<'de>>(d: D) -> Result<String, D::Error> {
+    Ok(match serde_json::Value::deserialize(d)? {
+        serde_json::Value::String(s) => s,
+        serde_json::Value::Number(n) => n.to_string(),
+        _ => String::new(),
+    })
+}
+
+/// The frontend keeps people as plain names; the scanner as `Person` records.
+fn people_list<'de, D: Deserializer<'de>>(d: D, role: PersonRole) -> Result<Vec<Person>, D::Error> {
+    let values = Option::<Vec<serde_json::Value>>::deserialize(d)?.unwrap_or_default();
+    Ok(values
+        .into_iter()
+        .filter_map(|v| match v {
+            serde_json::Value::String(name) if !name.trim().is_empty() => Some(Person { name: name.trim().to_string(), role }),
+            other => serde_json::from_value(other).ok(),
+        })
+        .collect())
+}
+
+fn authors_list<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<Person>, D::Error> {
+    people_list(d, PersonRole::Author)
+}
+
+fn narrators_list<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<Person>, D::Error> {
+    people_list(d, PersonRole::Narrator)
+}
+
+impl BookMetadata {
+    /// Read metadata as the frontend holds it: cleared fields are null, the
+    /// series position is edited as `sequence`, and UI-only fields (sources,
+    /// subtitle) are ignored.
+    pub fn from_frontend(value: serde_json::Value) -> Result<Self, AppError> {
+        let value = match value {
+            serde_json::Value::Object(map) => {
+                let mut map: serde_json::Map<String, serde_json::Value> =
+                    map.into_iter().filter(|(_, v)| !v.is_null()).collect();
+                if let Some(sequence) = map.remove("sequence") {
+                    map.insert("series_number".to_string(), sequence);
+                }
+                serde_json::Value::Object(map)
+            }
+            other => other,
+        };
+        serde_json::from_value(value)
+            .map_err(|e| AppError::invalid_input(format!("Invalid book metadata: {}", e)))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,8 +127,8 @@ struct EmbeddedTags {
     author: Option<String>,
     album: Option<String>,
     narrator: Option<String>,
-    series: Option<String>,
-    series_number: Option<String>,
+    series: Vec<SeriesEntry>,
+    series_part: Option<SeriesPosition>,
     year: Option<String>,
     genre: Option<String>,
 }
@@ -108,27 +177,63 @@ fn read_embedded_tags(path: &Path) -> EmbeddedTags {
         .and_then(|s| non_empty(s));
 
     // Series / series number from TXXX frames (written by v1 and common tagging tools)
-    out.series = lookup_txxx(tag, &["SERIES", "MVNM", "Series"]);
-    out.series_number = lookup_txxx(tag, &["SERIES-PART", "MVIN", "Series-Part", "Series Part"]);
+    // and MP4 freeform atoms. Books in several series carry one SERIES value each;
+    // SERIES-PART values pair up with them by position.
+    let names = lookup_txxx_all(
+        tag,
+        &["SERIES", "MVNM", "Series", "----:com.apple.iTunes:SERIES", "----:com.apple.iTunes:series"],
+    );
+    let parts = lookup_txxx_all(
+        tag,
+        &[
+            "SERIES-PART", "MVIN", "Series-Part", "Series Part",
+            "----:com.apple.iTunes:SERIES-PART", "----:com.apple.iTunes:series-part",
+        ],
+    );
+    out.series = pair_series(&names, &parts);
+    out.series_part = parts.first().and_then(|p| SeriesPosition::parse(p));
 
     out
 }
 
-fn lookup_txxx(tag: &lofty::tag::Tag, keys: &[&str]) -> Option<String> {
+/// Pair each SERIES value with the SERIES-PART value at the same position.
+/// Tools that write the same series under several keys repeat whole pairs,
+/// so duplicates collapse on name and position together.
+fn pair_series(names: &[String], parts: &[String]) -> Vec<SeriesEntry> {
+    let mut entries: Vec<SeriesEntry> = Vec::new();
+    for (i, name) in names.iter().enumerate() {
+        let mut entry = split_series_value(name);
+        if entry.position.is_none() {
+            entry.position = parts.get(i).and_then(|p| SeriesPosition::parse(p));
+        }
+        if !entry.name.is_empty() && !entries.contains(&entry) {
+            entries.push(entry);
+        }
+    }
+    entries
+}
+
+/// All non-empty text values for the given keys, in key order. ID3v2.4 packs
+/// multiple values into one frame separated by NUL, so those are split too.
+/// Repeats are kept: SERIES-PART values are matched to SERIES values by index.
+fn lookup_txxx_all(tag: &lofty::tag::Tag, keys: &[&str]) -> Vec<String> {
+    let mut values = Vec::new();
     for key in keys {
         let ik = ItemKey::Unknown(key.to_string());
         for item in tag.items() {
             if item.key() == &ik {
                 if let ItemValue::Text(s) = item.value() {
-                    let s = s.trim().to_string();
-                    if !s.is_empty() {
-                        return Some(s);
+                    for v in s.split('\0') {
+                        let v = v.trim().to_string();
+                        if !v.is_empty() {
+                            values.push(v);
+                        }
                     }
                 }
             }
         }
     }
-    None
+    values
 }
 
 fn collect_audio_files(paths: &[String]) -> Vec<RawFile> {
@@ -250,7 +355,7 @@ fn natord_cmp(a: &str, b: &str) -> std::cmp::Ordering {
 struct FolderHierarchy {
     author: Option<String>,
     series: Option<String>,
-    sequence: Option<String>,
+    sequence: Option<SeriesPosition>,
 }
 
 fn parse_folder_hierarchy(path: &str) -> FolderHierarchy {
@@ -348,106 +453,142 @@ fn folder_looks_like_author_name(name: &str) -> bool {
     name.chars().next().map(|c| c.is_uppercase()).unwrap_or(false)
 }
 
-fn extract_sequence_from_folder_name(folder: &str) -> Option<String> {
-    if let Ok(re) = regex::Regex::new(r"^(\d{1,3})\s*[-–—\.]\s*") {
+fn extract_sequence_from_folder_name(folder: &str) -> Option<SeriesPosition> {
+    if let Ok(re) = regex::Regex::new(r"^(\d{1,3}(?:\.\d{1,2})?)\s*[-–—\.]\s*") {
         if let Some(caps) = re.captures(folder) {
             if let Some(num) = caps.get(1) {
-                let n = num.as_str().trim_start_matches('0');
-                if !n.is_empty() {
-                    return Some(n.to_string());
-                }
+                return SeriesPosition::parse(num.as_str());
             }
         }
     }
-    if let Ok(re) = regex::Regex::new(r"^\[(\d+)\]") {
+    if let Ok(re) = regex::Regex::new(r"^\[(\d+(?:\.\d+)?)\]") {
         if let Some(caps) = re.captures(folder) {
             if let Some(num) = caps.get(1) {
-                return Some(num.as_str().trim_start_matches('0').to_string());
+                return SeriesPosition::parse(num.as_str());
             }
         }
     }
-    if let Ok(re) = regex::Regex::new(r"(?i)book\s*[#]?(\d+)") {
+    // "Book 2.5", "Books 1-3"
+    if let Ok(re) = regex::Regex::new(&format!(r"(?i)\bbooks?\s*[#]?({})", POSITION_PATTERN)) {
         if let Some(caps) = re.captures(folder) {
             if let Some(num) = caps.get(1) {
-                return Some(num.as_str().trim_start_matches('0').to_string());
+                return folder_position(num.as_str());
             }
         }
     }
-    if let Ok(re) = regex::Regex::new(r"#(\d+)") {
+    if let Ok(re) = regex::Regex::new(&format!(r"#({})", POSITION_PATTERN)) {
         if let Some(caps) = re.captures(folder) {
             if let Some(num) = caps.get(1) {
-                return Some(num.as_str().trim_start_matches('0').to_string());
+                return folder_position(num.as_str());
+            }
+        }
+    }
+    // "Vol. IV", "Book XII", "Part II" (uppercase only, so words like "Mix" don't match)
+    if let Ok(re) = regex::Regex::new(r"(?:(?i)\b(?:vol(?:ume)?|book|part)\.?)\s*([IVXLCDM]+)\b") {
+        if let Some(caps) = re.captures(folder) {
+            if let Some(num) = caps.get(1) {
+                return SeriesPosition::parse(num.as_str());
             }
         }
     }
     None
 }
 
-fn extract_series_from_folder(folder_name: &str) -> (Option<String>, Option<String>) {
+/// A series position inside a folder name: integer, decimal or omnibus range.
+/// A range never ends in a four-digit number, which is a year ("Book 1 - 1984").
+const POSITION_PATTERN: &str = r"\d{1,4}(?:\.\d{1,2})?(?:\s*[-–]\s*\d{1,3}(?:\.\d{1,2})?\b)?";
+/// Most books an omnibus range can span.
+const MAX_RANGE_SPAN: f64 = 20.0;
+
+/// Parse a `POSITION_PATTERN` match. A "range" that runs backwards or too
+/// far ("Book 1 - 30 Days") ends in a number from the title, so only its
+/// start is the position.
+fn folder_position(text: &str) -> Option<SeriesPosition> {
+    if let Some((start, end)) = text.split_once(['-', '–']) {
+        let span = match (start.trim().parse::<f64>(), end.trim().parse::<f64>()) {
+            (Ok(s), Ok(e)) => e - s,
+            _ => 0.0,
+        };
+        if span <= 0.0 || span > MAX_RANGE_SPAN {
+            return SeriesPosition::parse(start);
+        }
+    }
+    SeriesPosition::parse(text)
+}
+
+fn extract_series_from_folder(folder_name: &str) -> (Option<String>, Option<SeriesPosition>) {
     // "[Series Name #N]" at start
-    if let Ok(re) = regex::Regex::new(r"^\[(.+?)\s*[#]?(\d+)\]") {
+    if let Ok(re) = regex::Regex::new(&format!(r"^\[(.+?)\s*[#]?({})\]", POSITION_PATTERN)) {
+        if let Some(caps) = re.captures(folder_name) {
+            if let (Some(series), Some(num)) = (caps.get(1), caps.get(2)) {
+                let name = series.as_str().trim();
+                if let Some(pos) = folder_position(num.as_str()).filter(|_| name.len() >= 3) {
+                    return (Some(normalize_series_name(name)), Some(pos));
+                }
+            }
+        }
+    }
+    // "Series Name Book N", "Series Name Books 1-3"
+    if let Ok(re) = regex::Regex::new(&format!(r"(?i)^(.+?)\s+Books?\s*[#]?({})", POSITION_PATTERN)) {
         if let Some(caps) = re.captures(folder_name) {
             if let (Some(series), Some(num)) = (caps.get(1), caps.get(2)) {
                 let name = series.as_str().trim();
-                let n = num.as_str().trim_start_matches('0');
-                if name.len() >= 3 && !n.is_empty() {
-                    return (Some(normalize_series_name(name)), Some(n.to_string()));
+                if let Some(pos) = folder_position(num.as_str()).filter(|_| name.len() >= 3) {
+                    return (Some(normalize_series_name(name)), Some(pos));
                 }
             }
         }
     }
-    // "Series Name Book N"
-    if let Ok(re) = regex::Regex::new(r"(?i)^(.+?)\s+Book\s*[#]?(\d+)") {
+    // "Series Name #N" (including #0 prequels)
+    if let Ok(re) = regex::Regex::new(&format!(r"^(.+?)\s*#({})", POSITION_PATTERN)) {
         if let Some(caps) = re.captures(folder_name) {
             if let (Some(series), Some(num)) = (caps.get(1), caps.get(2)) {
                 let name = series.as_str().trim();
-                let n = num.as_str().trim_start_matches('0');
-                if name.len() >= 3 && !n.is_empty() {
-                    return (Some(normalize_series_name(name)), Some(n.to_string()));
+                if let Some(pos) = folder_position(num.as_str()).filter(|_| name.len() >= 3) {
+                    return (Some(normalize_series_name(name)), Some(pos));
                 }
             }
         }
     }
-    // "Series Name #N"
-    if let Ok(re) = regex::Regex::new(r"^(.+?)\s*#(\d+)") {
+    // "Series Name, Vol. IV"
+    if let Ok(re) = regex::Regex::new(r"^(.+?),?\s+(?:(?i)vol(?:ume)?\.?|book|part)\s*([IVXLCDM]+)\b") {
         if let Some(caps) = re.captures(folder_name) {
             if let (Some(series), Some(num)) = (caps.get(1), caps.get(2)) {
                 let name = series.as_str().trim();
-                let n = num.as_str().trim_start_matches('0');
-                if name.len() >= 3 && !n.is_empty() {
-                    return (Some(normalize_series_name(name)), Some(n.to_string()));
+                if let Some(pos) = SeriesPosition::parse(num.as_str()).filter(|_| name.len() >= 3) {
+                    return (Some(normalize_series_name(name)), Some(pos));
                 }
             }
         }
     }
     // "Series ## - Title"
-    if let Ok(re) = regex::Regex::new(r"^(.+?)\s+(\d{1,2})\s*[-–—]\s*.+$") {
+    if let Ok(re) = regex::Regex::new(r"^(.+?)\s+(\d{1,2}(?:\.\d{1,2})?)\s*[-–—]\s*.+$") {
         if let Some(caps) = re.captures(folder_name) {
             if let (Some(series), Some(num)) = (caps.get(1), caps.get(2)) {
                 let name = series.as_str().trim();
-                let n = num.as_str().trim_start_matches('0');
                 if name.len() >= 3
                     && !name.chars().all(|c| c.is_ascii_digit())
                     && !name.to_lowercase().ends_with(" book")
-                    && !n.is_empty()
                 {
-                    return (Some(normalize_series_name(name)), Some(n.to_string()));
+                    if let Some(pos) = SeriesPosition::parse(num.as_str()) {
+                        return (Some(normalize_series_name(name)), Some(pos));
+                    }
                 }
             }
         }
     }
     // "Series ##" at end
-    if let Ok(re) = regex::Regex::new(r"^(.+?)\s+(\d{1,2})$") {
+    if let Ok(re) = regex::Regex::new(r"^(.+?)\s+(\d{1,2}(?:\.\d{1,2})?)$") {
         if let Some(caps) = re.captures(folder_name) {
             if let (Some(series), Some(num)) = (caps.get(1), caps.get(2)) {
                 let name = series.as_str().trim();
-                let n = num.as_str().trim_start_matches('0');
                 if name.len() >= 3
                     && !name.chars().all(|c| c.is_ascii_digit())
                     && !name.to_lowercase().ends_with(" book")
-                    && !n.is_empty()
                 {
-                    return (Some(normalize_series_name(name)), Some(n.to_string()));
+                    if let Some(pos) = SeriesPosition::parse(num.as_str()) {
+                        return (Some(normalize_series_name(name)), Some(pos));
+                    }
                 }
             }
         }
@@ -483,7 +624,12 @@ fn normalize_series_name(name: &str) -> String {
 // Grouping
 // ---------------------------------------------------------------------------
 
-fn pick_metadata(raw_files: &[RawFile], parent_dir: &str, group_name: &str) -> BookMetadata {
+fn pick_metadata(
+    raw_files: &[RawFile],
+    parent_dir: &str,
+    group_name: &str,
+    authority: &AuthorityStore,
+) -> BookMetadata {
     // Use the first file's embedded tags as the base.
     let first_tags = raw_files.first().map(|f| f.tags.clone()).unwrap_or_default();
 
@@ -506,16 +652,27 @@ fn pick_metadata(raw_files: &[RawFile], parent_dir: &str, group_name: &str) -> B
 
     let narrator = first_tags.narrator.clone().unwrap_or_default();
 
-    let series = first_tags
-        .series
-        .clone()
-        .or(hierarchy.series)
-        .unwrap_or_default();
+    // Embedded SERIES tags win; the folder hierarchy supplies at most one entry.
+    let sequence = first_tags.series_part.clone().or(hierarchy.sequence);
+    let mut series_list = if !first_tags.series.is_empty() {
+        first_tags.series.clone()
+    } else if let Some(name) = hierarchy.series {
+        vec![SeriesEntry { name, position: None }]
+    } else {
+        Vec::new()
+    };
+    if let Some(primary) = series_list.first_mut() {
+        if primary.position.is_none() {
+            primary.position = sequence.clone();
+        }
+    }
 
-    let series_number = first_tags
-        .series_number
-        .clone()
-        .or(hierarchy.sequence)
+    let series = series_list.first().map(|e| e.name.clone()).unwrap_or_default();
+    let series_number = series_list
+        .first()
+        .and_then(|e| e.position.clone())
+        .or(sequence)
+        .map(|p| p.to_string())
         .unwrap_or_default();
 
     let year = first_tags.year.clone().unwrap_or_default();
@@ -525,6 +682,9 @@ fn pick_metadata(raw_files: &[RawFile], parent_dir: &str, group_name: &str) -> B
         .map(|g| vec![g])
         .unwrap_or_default();
 
+    let (authors, author) = canonicalize_people(author, PersonRole::Author, authority);
+    let (narrators, narrator) = canonicalize_people(narrator, PersonRole::Narrator, authority);
+
     BookMetadata {
         title,
         author,
@@ -536,10 +696,39 @@ fn pick_metadata(raw_files: &[RawFile], parent_dir: &str, group_name: &str) -> B
         tags: Vec::new(),
         description: String::new(),
         age_rating: String::new(),
+        series_list,
+        authors,
+        narrators,
     }
 }
 
-fn group_files(files: Vec<RawFile>) -> Vec<BookGroup> {
+/// Split a raw author/narrator field and map each variant ("Kramer, Michael",
+/// "M. Kramer") to its canonical spelling from the local authority file. The
+/// display string is only rewritten when the authority knows one of the names.
+fn canonicalize_people(raw: String, role: PersonRole, authority: &AuthorityStore) -> (Vec<Person>, String) {
+    let mut changed = false;
+    let people: Vec<Person> = split_people(&raw, role)
+        .into_iter()
+        .map(|p| {
+            let name = match authority.resolve(&p.name) {
+                Some(entry) => {
+                    changed = true;
+                    entry.name.clone()
+                }
+                None => p.name,
+            };
+            Person { name, ..p }
+        })
+        .collect();
+    let display = if changed {
+        people.iter().map(|p| p.name.as_str()).collect::<Vec<_>>().join(", ")
+    } else {
+        raw
+    };
+    (people, display)
+}
+
+fn group_files(files: Vec<RawFile>, authority: &AuthorityStore) -> Vec<BookGroup> {
     let mut map: HashMap<String, Vec<RawFile>> = HashMap::new();
     for f in files {
         map.entry(f.parent_dir.clone()).or_default().push(f);
@@ -574,7 +763,7 @@ fn group_files(files: Vec<RawFile>) -> Vec<BookGroup> {
             }
             .to_string();
 
-            let metadata = pick_metadata(&raw_files, &parent_dir, &group_name);
+            let metadata = pick_metadata(&raw_files, &parent_dir, &group_name, authority);
 
             let audio_files: Vec<AudioFile> = raw_files
                 .iter()
@@ -604,11 +793,30 @@ fn group_files(files: Vec<RawFile>) -> Vec<BookGroup> {
     groups
 }
 
+#[cfg(feature = "gui")]
 #[tauri::command]
-pub async fn scan_library(paths: Vec<String>) -> Result<ScanResult, String> {
+pub async fn scan_library(paths: Vec<String>, window: tauri::Window) -> Result<ScanResult, AppError> {
+    scan_paths(paths, &window).await
+}
+
+/// Scan `paths` for books and record them in the library database.
+pub async fn scan_paths(paths: Vec<String>, progress: &dyn ProgressSink) -> Result<ScanResult, AppError> {
     let files = collect_audio_files(&paths);
     let total_files = files.len();
-    let groups = group_files(files);
+    let authority = AuthorityStore::load();
+    let mut groups = group_files(files, &authority);
+
+    // Persisting also swaps in the stable ids of groups seen in earlier
+    // scans. A database failure shouldn't fail the scan, but the user needs
+    // to know history and edits for these books aren't being kept.
+    match crate::library::with_library(|db| db.record_scan(&mut groups, &paths)) {
+        Ok(0) => {}
+        Ok(removed) => progress.report(ProgressEvent::library(
+            Stage::Complete,
+            format!("Removed {} books no longer on disk from the library", removed),
+        )),
+        Err(e) => progress.report(ProgressEvent::library(Stage::Error, format!("Library database error: {}", e))),
+    }
     Ok(ScanResult { groups, total_files })
 }
 
@@ -623,7 +831,22 @@ mod tests {
         );
         assert_eq!(h.author.as_deref(), Some("Brandon Sanderson"));
         assert_eq!(h.series.as_deref(), Some("Stormlight Archive"));
-        assert_eq!(h.sequence.as_deref(), Some("1"));
+        assert_eq!(h.sequence.map(|p| p.to_string()).as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn pairs_series_with_repeated_parts() {
+        let strings = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let names = strings(&["Cosmere", "Stormlight Archive", "Wax and Wayne", "Cosmere"]);
+        let parts = strings(&["1", "1", "3", "1"]);
+        let series: Vec<(String, String)> = pair_series(&names, &parts)
+            .into_iter()
+            .map(|e| (e.name, e.position.map(|p| p.to_string()).unwrap_or_default()))
+            .collect();
+        assert_eq!(
+            series,
+            [("Cosmere".into(), "1".into()), ("Stormlight Archive".into(), "1".into()), ("Wax and Wayne".into(), "3".into())]
+        );
     }
 
     #[test]
@@ -664,15 +887,90 @@ mod tests {
     fn series_extraction_patterns() {
         let (s, n) = extract_series_from_folder("Discworld 01 - The Colour of Magic");
         assert_eq!(s.as_deref(), Some("Discworld"));
-        assert_eq!(n.as_deref(), Some("1"));
+        assert_eq!(n.map(|p| p.to_string()).as_deref(), Some("1"));
 
         let (s, n) = extract_series_from_folder("Harry Potter Book 3");
         assert_eq!(s.as_deref(), Some("Harry Potter"));
-        assert_eq!(n.as_deref(), Some("3"));
+        assert_eq!(n.map(|p| p.to_string()).as_deref(), Some("3"));
 
         let (s, n) = extract_series_from_folder("[Stormlight 2] Words of Radiance");
         assert_eq!(s.as_deref(), Some("Stormlight"));
-        assert_eq!(n.as_deref(), Some("2"));
+        assert_eq!(n.map(|p| p.to_string()).as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn series_extraction_fractional_ranged_roman_and_zero() {
+        let (s, n) = extract_series_from_folder("Stormlight Archive Book 2.5");
+        assert_eq!(s.as_deref(), Some("Stormlight Archive"));
+        assert_eq!(n.map(|p| p.to_string()).as_deref(), Some("2.5"));
+
+        let (s, n) = extract_series_from_folder("Wheel of Time Books 1-3");
+        assert_eq!(s.as_deref(), Some("Wheel of Time"));
+        assert_eq!(n.map(|p| p.to_string()).as_deref(), Some("1-3"));
+
+        // A year or a number from the subtitle isn't the end of a range
+        for (folder, series, position) in [
+            ("Series Book 1 - 1984", "Series", "1"),
+            ("Dune #3 - 2011", "Dune", "3"),
+            ("Hitchhiker Book 1 - 30 Days", "Hitchhiker", "1"),
+        ] {
+            let (s, n) = extract_series_from_folder(folder);
+            assert_eq!(s.as_deref(), Some(series));
+            assert_eq!(n.map(|p| p.to_string()).as_deref(), Some(position));
+        }
+        assert_eq!(extract_sequence_from_folder_name("Book 1 - 1984").map(|p| p.to_string()).as_deref(), Some("1"));
+
+        let (s, n) = extract_series_from_folder("Dune Chronicles, Vol. IV");
+        assert_eq!(s.as_deref(), Some("Dune"));
+        assert_eq!(n.map(|p| p.to_string()).as_deref(), Some("4"));
+
+        let (s, n) = extract_series_from_folder("Mistborn #0 - The Eleventh Metal");
+        assert_eq!(s.as_deref(), Some("Mistborn"));
+        assert_eq!(n.map(|p| p.to_string()).as_deref(), Some("0"));
+    }
+
+    #[test]
+    fn sequence_from_folder_name_keeps_zero_and_decimals() {
+        let seq = |f: &str| extract_sequence_from_folder_name(f).map(|p| p.to_string());
+        assert_eq!(seq("00 - New Spring").as_deref(), Some("0"));
+        assert_eq!(seq("2.5 - Edgedancer").as_deref(), Some("2.5"));
+        assert_eq!(seq("01. The Eye of the World").as_deref(), Some("1"));
+        assert_eq!(seq("Foundation Vol. II").as_deref(), Some("2"));
+        assert_eq!(seq("The Remix"), None);
+    }
+
+    #[test]
+    fn canonicalize_people_rewrites_only_known_variants() {
+        let mut authority = AuthorityStore::default();
+        authority.add_alias("Michael Kramer", "M. Kramer");
+
+        let (people, display) =
+            canonicalize_people("Kramer, Michael & Kate Reading".to_string(), PersonRole::Narrator, &authority);
+        assert_eq!(display, "Michael Kramer, Kate Reading");
+        assert_eq!(people.len(), 2);
+
+        let (_, display) = canonicalize_people("Reading, Kate".to_string(), PersonRole::Narrator, &authority);
+        assert_eq!(display, "Reading, Kate");
+    }
+
+    #[test]
+    fn metadata_from_frontend_accepts_edited_shapes() {
+        let meta = BookMetadata::from_frontend(serde_json::json!({
+            "title": "Mistborn",
+            "narrator": null,
+            "year": 2006,
+            "series_number": "1",
+            "sequence": 2,
+            "narrators": ["Michael Kramer"],
+            "authors": [{ "name": "Brandon Sanderson", "role": "author" }],
+            "sources": { "title": "gpt" },
+        }))
+        .unwrap();
+        assert_eq!(meta.year, "2006");
+        assert_eq!(meta.series_number, "2");
+        assert_eq!(meta.narrator, "");
+        assert_eq!(meta.narrators, vec![Person { name: "Michael Kramer".into(), role: PersonRole::Narrator }]);
+        assert_eq!(meta.authors[0].name, "Brandon Sanderson");
     }
 
     #[test]
@@ -0,0 +1,226 @@
+// src-tauri/src/series.rs
+// Series positions: decimals (2.5), omnibus ranges (1-3), roman numerals (IV)
+// and zero for prequels. Serialised as the normalised display string so the
+// frontend keeps seeing `series_number` as plain text.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Position of a book within a series. `end` is set for omnibus editions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct SeriesPosition {
+    start: String,
+    end: Option<String>,
+}
+
+/// One series membership. A book can belong to several (e.g. `Cosmere` and
+/// `Stormlight Archive #1`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeriesEntry {
+    pub name: String,
+    pub position: Option<SeriesPosition>,
+}
+
+impl SeriesPosition {
+    /// Parse "2", "02", "2.5", "1-3", "IV" or "0". Returns None for anything else.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let s = raw.trim().trim_start_matches('#').trim();
+        if s.is_empty() {
+            return None;
+        }
+
+        if let Some(start) = normalize_number(s) {
+            return Some(SeriesPosition { start, end: None });
+        }
+
+        // Omnibus range: "1-3", "1 – 3"
+        if let Some((a, b)) = s.split_once(['-', '–', '—']) {
+            let start = normalize_number(a.trim())?;
+            let end = normalize_number(b.trim())?;
+            let (sa, sb) = (start.parse::<f64>().ok()?, end.parse::<f64>().ok()?);
+            if sb > sa {
+                return Some(SeriesPosition { start, end: Some(end) });
+            }
+            return None;
+        }
+
+        roman_to_int(s).map(|n| SeriesPosition { start: n.to_string(), end: None })
+    }
+}
+
+impl fmt::Display for SeriesPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.end {
+            Some(end) => write!(f, "{}-{}", self.start, end),
+            None => write!(f, "{}", self.start),
+        }
+    }
+}
+
+impl From<SeriesPosition> for String {
+    fn from(p: SeriesPosition) -> String {
+        p.to_string()
+    }
+}
+
+impl TryFrom<String> for SeriesPosition {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        SeriesPosition::parse(&s).ok_or_else(|| format!("Invalid series position: {}", s))
+    }
+}
+
+/// Strip leading zeros from the integer part and trailing zeros from the
+/// fraction: "007" -> "7", "2.50" -> "2.5", "0" -> "0".
+fn normalize_number(s: &str) -> Option<String> {
+    let (int, frac) = match s.split_once('.') {
+        Some((i, f)) => (i, Some(f)),
+        None => (s, None),
+    };
+    if int.is_empty() || !int.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let int = int.trim_start_matches('0');
+    let int = if int.is_empty() { "0" } else { int };
+
+    match frac {
+        Some(f) if f.is_empty() || !f.chars().all(|c| c.is_ascii_digit()) => None,
+        Some(f) => {
+            let f = f.trim_end_matches('0');
+            if f.is_empty() {
+                Some(int.to_string())
+            } else {
+                Some(format!("{}.{}", int, f))
+            }
+        }
+        None => Some(int.to_string()),
+    }
+}
+
+/// Convert an uppercase roman numeral. Only canonical forms are accepted,
+/// so "IIII" or "VX" are rejected rather than guessed at.
+fn roman_to_int(s: &str) -> Option<u32> {
+    if s.is_empty() || s.len() > 15 {
+        return None;
+    }
+    let value = |c: char| match c {
+        'I' => Some(1),
+        'V' => Some(5),
+        'X' => Some(10),
+        'L' => Some(50),
+        'C' => Some(100),
+        'D' => Some(500),
+        'M' => Some(1000),
+        _ => None,
+    };
+    let digits: Vec<u32> = s.chars().map(value).collect::<Option<_>>()?;
+    // Subtractive sum tolerates junk like "IIM"; the round-trip below rejects it.
+    let total = digits
+        .iter()
+        .enumerate()
+        .fold(0i64, |acc, (i, &d)| match digits.get(i + 1) {
+            Some(&next) if next > d => acc - d as i64,
+            _ => acc + d as i64,
+        });
+    if total <= 0 || total > 3999 {
+        return None;
+    }
+    let total = total as u32;
+    if int_to_roman(total) == s {
+        Some(total)
+    } else {
+        None
+    }
+}
+
+fn int_to_roman(mut n: u32) -> String {
+    const TABLE: &[(u32, &str)] = &[
+        (1000, "M"), (900, "CM"), (500, "D"), (400, "CD"),
+        (100, "C"), (90, "XC"), (50, "L"), (40, "XL"),
+        (10, "X"), (9, "IX"), (5, "V"), (4, "IV"), (1, "I"),
+    ];
+    let mut out = String::new();
+    for &(v, sym) in TABLE {
+        while n >= v {
+            out.push_str(sym);
+            n -= v;
+        }
+    }
+    out
+}
+
+/// Split a SERIES tag value that carries its own position, e.g.
+/// "Stormlight Archive #1" or "Mistborn, Book 2.5". Values without a
+/// recognisable position come back unchanged with `None`.
+pub fn split_series_value(value: &str) -> SeriesEntry {
+    let value = value.trim();
+    if let Ok(re) = regex::Regex::new(r"(?i)^(.+?)\s*(?:#|,?\s+book\s+|,?\s+vol(?:ume)?\.?\s*)(\d[\d.\s\-–]*|[IVXLCDM]+)$") {
+        if let Some(caps) = re.captures(value) {
+            if let (Some(name), Some(pos)) = (caps.get(1), caps.get(2)) {
+                if let Some(position) = SeriesPosition::parse(pos.as_str()) {
+                    return SeriesEntry {
+                        name: name.as_str().trim().to_string(),
+                        position: Some(position),
+                    };
+                }
+            }
+        }
+    }
+    SeriesEntry { name: value.to_string(), position: None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(s: &str) -> Option<String> {
+        SeriesPosition::parse(s).map(|p| p.to_string())
+    }
+
+    #[test]
+    fn parses_plain_decimal_and_zero() {
+        assert_eq!(pos("02").as_deref(), Some("2"));
+        assert_eq!(pos("2.5").as_deref(), Some("2.5"));
+        assert_eq!(pos("2.50").as_deref(), Some("2.5"));
+        assert_eq!(pos("0").as_deref(), Some("0"));
+        assert_eq!(pos("#0").as_deref(), Some("0"));
+        assert_eq!(pos("00").as_deref(), Some("0"));
+    }
+
+    #[test]
+    fn parses_ranges_and_roman_numerals() {
+        assert_eq!(pos("1-3").as_deref(), Some("1-3"));
+        assert_eq!(pos("01 – 03").as_deref(), Some("1-3"));
+        assert_eq!(pos("3-1"), None);
+        assert_eq!(pos("IV").as_deref(), Some("4"));
+        assert_eq!(pos("XII").as_deref(), Some("12"));
+        assert_eq!(pos("IIII"), None);
+        assert_eq!(pos("iv"), None);
+        assert_eq!(pos("abc"), None);
+    }
+
+    #[test]
+    fn serializes_as_display_string() {
+        let p = SeriesPosition::parse("1-3").unwrap();
+        assert_eq!(serde_json::to_string(&p).unwrap(), "\"1-3\"");
+        let back: SeriesPosition = serde_json::from_str("\"2.5\"").unwrap();
+        assert_eq!(back.to_string(), "2.5");
+    }
+
+    #[test]
+    fn splits_series_values_with_embedded_position() {
+        let e = split_series_value("Stormlight Archive #1");
+        assert_eq!(e.name, "Stormlight Archive");
+        assert_eq!(e.position.map(|p| p.to_string()).as_deref(), Some("1"));
+
+        let e = split_series_value("Mistborn, Book 2.5");
+        assert_eq!(e.name, "Mistborn");
+        assert_eq!(e.position.map(|p| p.to_string()).as_deref(), Some("2.5"));
+
+        let e = split_series_value("Cosmere");
+        assert_eq!(e.name, "Cosmere");
+        assert_eq!(e.position, None);
+    }
+}
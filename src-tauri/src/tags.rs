@@ -0,0 +1,220 @@
+// src-tauri/src/tags.rs
+// Raw tag dumps and metadata write-back for a single audio file.
+// Uses the same conventions the scanner reads: AlbumArtist = author,
+// Composer = narrator, SERIES / SERIES-PART as TXXX or MP4 freeform atoms.
+
+use serde::Serialize;
+use std::path::Path;
+
+use lofty::config::WriteOptions;
+use lofty::file::{AudioFile, TaggedFileExt};
+use lofty::prelude::{Accessor, ItemKey, TagExt};
+use lofty::probe::Probe;
+use lofty::tag::{ItemValue, Tag, TagItem, TagType};
+
+use crate::scanner::BookMetadata;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RawTagItem {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileTags {
+    pub path: String,
+    pub tag_type: Option<String>,
+    pub duration_secs: u64,
+    pub bitrate_kbps: Option<u32>,
+    pub pictures: usize,
+    pub items: Vec<RawTagItem>,
+}
+
+/// Playing time in milliseconds, for placing multi-file books on one timeline.
+pub fn audio_duration_ms(path: &Path) -> Result<u64, String> {
+    let tagged = Probe::open(path)
+        .and_then(|p| p.read())
+        .map_err(|e| format!("Tag read error: {}", e))?;
+    Ok(tagged.properties().duration().as_millis() as u64)
+}
+
+/// Every item in the file's primary tag, keyed by the format's own key names
+/// (TIT2, ©nam, TITLE, ...).
+pub fn read_file_tags(path: &Path) -> Result<FileTags, String> {
+    let tagged = Probe::open(path)
+        .and_then(|p| p.read())
+        .map_err(|e| format!("Tag read error: {}", e))?;
+
+    let properties = tagged.properties();
+    let mut out = FileTags {
+        path: path.to_string_lossy().into_owned(),
+        tag_type: None,
+        duration_secs: properties.duration().as_secs(),
+        bitrate_kbps: properties.audio_bitrate(),
+        pictures: 0,
+        items: Vec::new(),
+    };
+
+    if let Some(tag) = tagged.primary_tag().or_else(|| tagged.first_tag()) {
+        out.tag_type = Some(format!("{:?}", tag.tag_type()));
+        out.pictures = tag.picture_count() as usize;
+        for item in tag.items() {
+            let key = item.key();
+            let value = match item.value() {
+                ItemValue::Text(s) | ItemValue::Locator(s) => s.clone(),
+                ItemValue::Binary(b) => format!("<{} bytes>", b.len()),
+            };
+            out.items.push(RawTagItem {
+                key: key
+                    .map_key(tag.tag_type(), true)
+                    .map(|k| k.to_string())
+                    .unwrap_or_else(|| format!("{:?}", key)),
+                value,
+            });
+        }
+    }
+
+    Ok(out)
+}
+
+/// Write book metadata into the file's primary tag, creating one if the file
+/// has none. Empty fields are left untouched.
+pub fn write_book_metadata(path: &Path, metadata: &BookMetadata) -> Result<(), String> {
+    let mut tagged = Probe::open(path)
+        .and_then(|p| p.read())
+        .map_err(|e| format!("Tag read error: {}", e))?;
+
+    let tag_type = tagged.primary_tag_type();
+    if tagged.primary_tag().is_none() {
+        tagged.insert_tag(Tag::new(tag_type));
+    }
+    let tag = tagged
+        .primary_tag_mut()
+        .ok_or_else(|| format!("Cannot create a {:?} tag", tag_type))?;
+
+    apply_metadata(tag, metadata);
+
+    tag.save_to_path(path, WriteOptions::default())
+        .map_err(|e| format!("Tag write error: {}", e))
+}
+
+fn apply_metadata(tag: &mut Tag, m: &BookMetadata) {
+    if !m.title.is_empty() {
+        tag.set_title(m.title.clone());
+        tag.set_album(m.title.clone());
+    }
+    if !m.author.is_empty() {
+        tag.set_artist(m.author.clone());
+        tag.insert_text(ItemKey::AlbumArtist, m.author.clone());
+    }
+    if !m.narrator.is_empty() {
+        tag.insert_text(ItemKey::Composer, m.narrator.clone());
+    }
+    if let Ok(year) = m.year.trim().parse::<u32>() {
+        tag.set_year(year);
+    }
+    if !m.genres.is_empty() {
+        tag.remove_key(&ItemKey::Genre);
+        if tag.tag_type() == TagType::Id3v2 {
+            // ID3v2 allows one TCON frame; v2.4 separates its values with NUL.
+            tag.insert_text(ItemKey::Genre, m.genres.join("\0"));
+        } else {
+            for genre in &m.genres {
+                tag.push(TagItem::new(ItemKey::Genre, ItemValue::Text(genre.clone())));
+            }
+        }
+    }
+    if !m.description.is_empty() {
+        tag.set_comment(m.description.clone());
+    }
+
+    // Series: one SERIES / SERIES-PART pair per membership, in order.
+    let series: Vec<(String, String)> = if m.series_list.is_empty() {
+        if m.series.is_empty() {
+            vec![]
+        } else {
+            vec![(m.series.clone(), m.series_number.clone())]
+        }
+    } else {
+        m.series_list
+            .iter()
+            .map(|e| (e.name.clone(), e.position.as_ref().map(|p| p.to_string()).unwrap_or_default()))
+            .collect()
+    };
+    if !series.is_empty() {
+        let (name_key, part_key) = series_keys(tag.tag_type());
+        tag.remove_key(&name_key);
+        tag.remove_key(&part_key);
+        // Custom keys have no mapping lofty can verify, so push them unchecked.
+        for (name, part) in series {
+            tag.push_unchecked(TagItem::new(name_key.clone(), ItemValue::Text(name)));
+            if !part.is_empty() {
+                tag.push_unchecked(TagItem::new(part_key.clone(), ItemValue::Text(part)));
+            }
+        }
+    }
+}
+
+fn series_keys(tag_type: TagType) -> (ItemKey, ItemKey) {
+    match tag_type {
+        TagType::Mp4Ilst => (
+            ItemKey::Unknown("----:com.apple.iTunes:SERIES".to_string()),
+            ItemKey::Unknown("----:com.apple.iTunes:SERIES-PART".to_string()),
+        ),
+        _ => (
+            ItemKey::Unknown("SERIES".to_string()),
+            ItemKey::Unknown("SERIES-PART".to_string()),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::series::{SeriesEntry, SeriesPosition};
+
+    fn metadata() -> BookMetadata {
+        BookMetadata {
+            title: "The Way of Kings".into(),
+            author: "Brandon Sanderson".into(),
+            narrator: "Michael Kramer".into(),
+            series: "Stormlight Archive".into(),
+            series_number: "1".into(),
+            year: "2010".into(),
+            genres: vec!["Fantasy".into(), "Epic".into()],
+            tags: vec![],
+            description: String::new(),
+            age_rating: String::new(),
+            series_list: vec![SeriesEntry {
+                name: "Stormlight Archive".into(),
+                position: SeriesPosition::parse("1"),
+            }],
+            authors: vec![],
+            narrators: vec![],
+        }
+    }
+
+    #[test]
+    fn applies_metadata_to_vorbis_tag() {
+        let mut tag = Tag::new(TagType::VorbisComments);
+        apply_metadata(&mut tag, &metadata());
+
+        assert_eq!(tag.title().as_deref(), Some("The Way of Kings"));
+        assert_eq!(tag.get_string(&ItemKey::AlbumArtist), Some("Brandon Sanderson"));
+        assert_eq!(tag.get_string(&ItemKey::Composer), Some("Michael Kramer"));
+        assert_eq!(tag.year(), Some(2010));
+        assert_eq!(tag.get_strings(&ItemKey::Genre).collect::<Vec<_>>(), ["Fantasy", "Epic"]);
+        assert_eq!(tag.get_string(&ItemKey::Unknown("SERIES".into())), Some("Stormlight Archive"));
+        assert_eq!(tag.get_string(&ItemKey::Unknown("SERIES-PART".into())), Some("1"));
+    }
+
+    #[test]
+    fn empty_fields_leave_existing_values() {
+        let mut tag = Tag::new(TagType::VorbisComments);
+        tag.insert_text(ItemKey::Composer, "Kate Reading".into());
+        let mut m = metadata();
+        m.narrator.clear();
+        apply_metadata(&mut tag, &m);
+        assert_eq!(tag.get_string(&ItemKey::Composer), Some("Kate Reading"));
+    }
+}
@@ -0,0 +1,429 @@
+// src-tauri/src/transcribe.rs
+// Full-book transcription with segment timestamps, written as SRT, WebVTT
+// and JSON next to the audio. Books split across files share one timeline.
+// Finished chunks are saved as they complete, so a long book can be
+// transcribed over several sessions.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+
+use crate::config::{get_secret, AppConfig, Secret};
+use crate::error::{AppError, ErrorKind};
+use crate::progress::{ProgressEvent, ProgressSink, Stage};
+use crate::whisper;
+
+const DEFAULT_CHUNK_SECS: u32 = 600;
+/// A file's last chunk absorbs a remainder shorter than this instead of
+/// sending Whisper a few seconds of audio on its own.
+const MIN_TAIL_SECS: u32 = 30;
+
+/// Text spoken between two points on the book's timeline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Segment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptFormat {
+    Srt,
+    Vtt,
+    Json,
+}
+
+impl TranscriptFormat {
+    pub const ALL: [TranscriptFormat; 3] = [TranscriptFormat::Srt, TranscriptFormat::Vtt, TranscriptFormat::Json];
+
+    fn extension(self) -> &'static str {
+        match self {
+            TranscriptFormat::Srt => "srt",
+            TranscriptFormat::Vtt => "vtt",
+            // Plain .json next to the audio is easily mistaken for metadata
+            TranscriptFormat::Json => "transcript.json",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TranscribeRequest {
+    /// Audio files in playback order.
+    pub files: Vec<String>,
+    /// Range on the book's timeline in seconds; the whole book when unset.
+    #[serde(default)]
+    pub start_secs: Option<u32>,
+    #[serde(default)]
+    pub end_secs: Option<u32>,
+    /// Formats to write; all of them when empty.
+    #[serde(default)]
+    pub formats: Vec<TranscriptFormat>,
+    /// Defaults to the first file's folder.
+    #[serde(default)]
+    pub output_dir: Option<String>,
+    /// File name without extension. Defaults to the file's name for a
+    /// single-file book, the folder's name otherwise.
+    #[serde(default)]
+    pub output_name: Option<String>,
+    #[serde(default)]
+    pub chunk_secs: Option<u32>,
+    #[serde(default)]
+    pub use_local_whisper: Option<bool>,
+    #[serde(default)]
+    pub whisper_model: Option<String>,
+}
+
+/// A stretch of one file transcribed in one go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Chunk {
+    /// Index into the request's files.
+    pub file: usize,
+    /// Seconds into the file.
+    pub start_secs: u32,
+    pub duration_secs: u32,
+    /// Where the chunk starts on the book's timeline.
+    pub offset_ms: u64,
+}
+
+/// Saved next to the outputs while a transcription is unfinished. Only
+/// reused when the files, model and chunk plan still match.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct PartialTranscript {
+    files: Vec<String>,
+    model: String,
+    chunks: Vec<Chunk>,
+    language: Option<String>,
+    /// Book-time segments of each finished chunk, by chunk index.
+    done: BTreeMap<usize, Vec<Segment>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscribeSummary {
+    pub outputs: Vec<String>,
+    pub segments: usize,
+    pub chunks: usize,
+    /// Chunks carried over from an earlier session.
+    pub resumed: usize,
+    pub language: Option<String>,
+}
+
+/// Transcribe the request's range, resuming from any saved chunks, and
+/// write the requested formats.
+pub async fn transcribe_book(request: &TranscribeRequest, progress: &dyn ProgressSink) -> Result<TranscribeSummary, AppError> {
+    let (request, openai_api_key) = with_saved_config(request.clone());
+    if request.files.is_empty() {
+        return Err(AppError::invalid_input("No audio files to transcribe"));
+    }
+    if !whisper::check_ffmpeg_available() {
+        return Err(AppError::ffmpeg_missing());
+    }
+
+    let mut durations = Vec::with_capacity(request.files.len());
+    for file in &request.files {
+        let ms = crate::tags::audio_duration_ms(Path::new(file)).map_err(|e| {
+            AppError::new(ErrorKind::Io, format!("Could not read the length of {}", file)).with_detail(e)
+        })?;
+        durations.push(ms);
+    }
+    let chunk_secs = request.chunk_secs.unwrap_or(DEFAULT_CHUNK_SECS).max(MIN_TAIL_SECS);
+    let chunks = plan_chunks(&durations, request.start_secs, request.end_secs, chunk_secs);
+    if chunks.is_empty() {
+        return Err(AppError::invalid_input("The requested range is outside the book"));
+    }
+
+    let (dir, name) = output_location(&request);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| AppError::new(ErrorKind::Io, "Could not create the output folder").with_detail(e.to_string()))?;
+    let partial_path = dir.join(format!("{}.transcript.partial.json", name));
+    let model = model_label(&request, openai_api_key.as_deref());
+    let mut partial = load_partial(&partial_path)
+        .filter(|p| p.files == request.files && p.model == model && p.chunks == chunks)
+        .unwrap_or_else(|| PartialTranscript {
+            files: request.files.clone(),
+            model: model.clone(),
+            chunks: chunks.clone(),
+            ..Default::default()
+        });
+    let resumed = partial.done.len();
+    let total = chunks.len();
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        if partial.done.contains_key(&i) {
+            continue;
+        }
+        progress.report(ProgressEvent::transcription(
+            Stage::Transcribing,
+            partial.done.len(),
+            total,
+            format!("Transcribing {} ({} of {})", clock(chunk.offset_ms, '.'), i + 1, total),
+        ));
+        let (segments, language) = transcribe_chunk(&request, chunk, openai_api_key.as_deref()).await?;
+        partial.language = partial.language.or(language);
+        partial.done.insert(i, to_book_time(segments, chunk));
+        save_partial(&partial_path, &partial)?;
+    }
+
+    let language = partial.language.clone();
+    let segments: Vec<Segment> = partial.done.into_values().flatten().collect();
+    let offsets = file_offsets(&durations);
+    let formats = if request.formats.is_empty() { TranscriptFormat::ALL.to_vec() } else { request.formats.clone() };
+    let mut outputs = Vec::new();
+    for format in formats {
+        let contents = match format {
+            TranscriptFormat::Srt => to_srt(&segments),
+            TranscriptFormat::Vtt => to_vtt(&segments),
+            TranscriptFormat::Json => to_json(&segments, language.as_deref(), &model, &request.files, &offsets),
+        };
+        let path = dir.join(format!("{}.{}", name, format.extension()));
+        std::fs::write(&path, contents)
+            .map_err(|e| AppError::new(ErrorKind::Io, "Could not write the transcript").with_detail(e.to_string()))?;
+        outputs.push(path.to_string_lossy().into_owned());
+    }
+    let _ = std::fs::remove_file(&partial_path);
+
+    progress.report(ProgressEvent::transcription(
+        Stage::Complete, total, total,
+        format!("Transcribed {} segments into {} files", segments.len(), outputs.len()),
+    ));
+    Ok(TranscribeSummary { outputs, segments: segments.len(), chunks: total, resumed, language })
+}
+
+/// Fill transcription settings the caller left out from the saved config.
+/// The OpenAI key comes from the secret store only: requests are saved
+/// with their jobs, so they never carry credentials.
+fn with_saved_config(mut request: TranscribeRequest) -> (TranscribeRequest, Option<String>) {
+    let config = AppConfig::load_or_default();
+    request.use_local_whisper = request.use_local_whisper.or(config.use_local_whisper);
+    request.whisper_model = request.whisper_model.take().or(config.whisper_model);
+    (request, get_secret(Secret::OpenaiApiKey))
+}
+
+fn model_label(request: &TranscribeRequest, openai_api_key: Option<&str>) -> String {
+    if request.use_local_whisper.unwrap_or(false) {
+        format!("whisper.cpp/{}", request.whisper_model.as_deref().unwrap_or("base"))
+    } else {
+        whisper::TranscriptionProvider::from_config(openai_api_key).label()
+    }
+}
+
+/// Extract one chunk with FFmpeg and transcribe it. Segments are relative
+/// to the chunk's start.
+pub(crate) async fn transcribe_chunk(
+    request: &TranscribeRequest,
+    chunk: &Chunk,
+    openai_api_key: Option<&str>,
+) -> Result<(Vec<Segment>, Option<String>), AppError> {
+    let local = request.use_local_whisper.unwrap_or(false);
+    let (suffix, format) = if local { (".wav", "wav") } else { (".mp3", "mp3") };
+    let temp_audio = NamedTempFile::with_suffix(suffix)
+        .map_err(|e| AppError::new(ErrorKind::Io, "Could not create a temp file").with_detail(e.to_string()))?;
+    let out_path = temp_audio.path().to_string_lossy().to_string();
+
+    let (input, output, start, duration) = (request.files[chunk.file].clone(), out_path.clone(), chunk.start_secs, chunk.duration_secs);
+    whisper::run_blocking(move || whisper::extract_audio_segment(&input, &output, start, duration, format)).await?;
+
+    if local {
+        let model = request.whisper_model.clone().unwrap_or_else(|| "base".to_string());
+        crate::whisper_local::transcribe_segments(out_path, model).await
+    } else {
+        let audio = std::fs::read(&out_path)
+            .map_err(|e| AppError::new(ErrorKind::Io, "Could not read the extracted audio").with_detail(e.to_string()))?;
+        let provider = whisper::TranscriptionProvider::from_config(openai_api_key);
+        whisper::transcribe_cloud_segments(&audio, &provider).await
+    }
+}
+
+/// Split the range into chunks of at most `chunk_secs` (plus a short tail).
+/// Chunks never span files; each is placed on the book's timeline.
+pub fn plan_chunks(durations_ms: &[u64], start_secs: Option<u32>, end_secs: Option<u32>, chunk_secs: u32) -> Vec<Chunk> {
+    let range_start = u64::from(start_secs.unwrap_or(0)) * 1000;
+    let range_end = end_secs.map_or(u64::MAX, |s| u64::from(s) * 1000);
+    let mut chunks = Vec::new();
+
+    for (file, (&duration, file_offset)) in durations_ms.iter().zip(file_offsets(durations_ms)).enumerate() {
+        let file_end = file_offset + duration;
+        if range_start >= file_end || range_end <= file_offset {
+            continue;
+        }
+        // Whole seconds from the file's start, rounded outwards
+        let mut start = ((range_start.max(file_offset) - file_offset) / 1000) as u32;
+        let end = (range_end.min(file_end) - file_offset).div_ceil(1000) as u32;
+        while start < end {
+            let left = end - start;
+            let len = if left < chunk_secs + MIN_TAIL_SECS { left } else { chunk_secs };
+            chunks.push(Chunk { file, start_secs: start, duration_secs: len, offset_ms: file_offset + u64::from(start) * 1000 });
+            start += len;
+        }
+    }
+    chunks
+}
+
+/// Start of each file on the book's timeline.
+pub(crate) fn file_offsets(durations_ms: &[u64]) -> Vec<u64> {
+    durations_ms
+        .iter()
+        .scan(0u64, |offset, &d| {
+            let start = *offset;
+            *offset += d;
+            Some(start)
+        })
+        .collect()
+}
+
+/// Move chunk-relative segments onto the book's timeline, clamped to the chunk.
+fn to_book_time(segments: Vec<Segment>, chunk: &Chunk) -> Vec<Segment> {
+    let length = u64::from(chunk.duration_secs) * 1000;
+    segments
+        .into_iter()
+        .map(|s| Segment {
+            start_ms: chunk.offset_ms + s.start_ms.min(length),
+            end_ms: chunk.offset_ms + s.end_ms.min(length),
+            text: s.text,
+        })
+        .collect()
+}
+
+fn output_location(request: &TranscribeRequest) -> (PathBuf, String) {
+    let first = Path::new(&request.files[0]);
+    let dir = request
+        .output_dir
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| first.parent().map(Path::to_path_buf).unwrap_or_default());
+    let name = request.output_name.clone().unwrap_or_else(|| {
+        let source = if request.files.len() == 1 { first.file_stem() } else { first.parent().and_then(Path::file_name) };
+        source.map_or_else(|| "transcript".to_string(), |s| s.to_string_lossy().into_owned())
+    });
+    (dir, name)
+}
+
+fn load_partial(path: &Path) -> Option<PartialTranscript> {
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+fn save_partial(path: &Path, partial: &PartialTranscript) -> Result<(), AppError> {
+    let json = serde_json::to_string(partial).map_err(|e| AppError::from(e.to_string()))?;
+    std::fs::write(path, json)
+        .map_err(|e| AppError::new(ErrorKind::Io, "Could not save transcription progress").with_detail(e.to_string()))
+}
+
+// ---- Output formats ----
+
+/// `HH:MM:SS<sep>mmm`: a comma for SRT, a dot for WebVTT.
+fn clock(ms: u64, sep: char) -> String {
+    let secs = ms / 1000;
+    format!("{:02}:{:02}:{:02}{}{:03}", secs / 3600, secs / 60 % 60, secs % 60, sep, ms % 1000)
+}
+
+pub fn to_srt(segments: &[Segment]) -> String {
+    segments
+        .iter()
+        .enumerate()
+        .map(|(i, s)| format!("{}\n{} --> {}\n{}\n\n", i + 1, clock(s.start_ms, ','), clock(s.end_ms, ','), s.text))
+        .collect()
+}
+
+pub fn to_vtt(segments: &[Segment]) -> String {
+    let cues: String = segments
+        .iter()
+        .map(|s| format!("{} --> {}\n{}\n\n", clock(s.start_ms, '.'), clock(s.end_ms, '.'), s.text))
+        .collect();
+    format!("WEBVTT\n\n{}", cues)
+}
+
+fn to_json(segments: &[Segment], language: Option<&str>, model: &str, files: &[String], offsets: &[u64]) -> String {
+    let files: Vec<_> = files
+        .iter()
+        .zip(offsets)
+        .map(|(path, offset_ms)| serde_json::json!({ "path": path, "offset_ms": offset_ms }))
+        .collect();
+    let doc = serde_json::json!({ "language": language, "model": model, "files": files, "segments": segments });
+    serde_json::to_string_pretty(&doc).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start_ms: u64, end_ms: u64, text: &str) -> Segment {
+        Segment { start_ms, end_ms, text: text.into() }
+    }
+
+    #[test]
+    fn plans_chunks_across_files_on_one_timeline() {
+        // Two files: 25m00.5s and 10m00s
+        let durations = [1_500_500, 600_000];
+        let chunks = plan_chunks(&durations, None, None, 600);
+        let shape: Vec<_> = chunks.iter().map(|c| (c.file, c.start_secs, c.duration_secs, c.offset_ms)).collect();
+        assert_eq!(shape, vec![
+            (0, 0, 600, 0),
+            (0, 600, 600, 600_000),
+            (0, 1_200, 301, 1_200_000),
+            // A 10-minute file plus nothing: no 0-second tail chunk
+            (1, 0, 600, 1_500_500),
+        ]);
+
+        // A range that starts in the first file and ends in the second
+        let chunks = plan_chunks(&durations, Some(1_400), Some(1_600), 600);
+        let shape: Vec<_> = chunks.iter().map(|c| (c.file, c.start_secs, c.duration_secs, c.offset_ms)).collect();
+        assert_eq!(shape, vec![(0, 1_400, 101, 1_400_000), (1, 0, 100, 1_500_500)]);
+
+        assert!(plan_chunks(&durations, Some(5_000), None, 600).is_empty());
+    }
+
+    #[test]
+    fn shifts_segments_onto_the_book_timeline() {
+        let chunk = Chunk { file: 1, start_secs: 0, duration_secs: 10, offset_ms: 1_500_500 };
+        let shifted = to_book_time(vec![segment(0, 4_000, "Part Two"), segment(9_000, 12_000, "The")], &chunk);
+        assert_eq!(shifted, vec![segment(1_500_500, 1_504_500, "Part Two"), segment(1_509_500, 1_510_500, "The")]);
+    }
+
+    #[test]
+    fn writes_srt_vtt_and_json() {
+        let segments = [segment(0, 2_500, "Chapter One."), segment(3_725_004, 3_727_000, "The Storm")];
+        assert_eq!(
+            to_srt(&segments),
+            "1\n00:00:00,000 --> 00:00:02,500\nChapter One.\n\n2\n01:02:05,004 --> 01:02:07,000\nThe Storm\n\n"
+        );
+        assert_eq!(
+            to_vtt(&segments),
+            "WEBVTT\n\n00:00:00.000 --> 00:00:02.500\nChapter One.\n\n01:02:05.004 --> 01:02:07.000\nThe Storm\n\n"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&to_json(&segments, Some("en"), "whisper.cpp/base", &["a.mp3".into(), "b.mp3".into()], &[0, 60_000]))
+                .unwrap();
+        assert_eq!(json["files"][1], serde_json::json!({ "path": "b.mp3", "offset_ms": 60_000 }));
+        assert_eq!(json["segments"][1]["start_ms"], 3_725_004);
+    }
+
+    #[test]
+    fn names_outputs_after_the_file_or_folder() {
+        let request = |files: &[&str]| TranscribeRequest { files: files.iter().map(|f| f.to_string()).collect(), ..Default::default() };
+        let (dir, name) = output_location(&request(&["/books/Dune/dune.m4b"]));
+        assert_eq!((dir, name.as_str()), (PathBuf::from("/books/Dune"), "dune"));
+        let (_, name) = output_location(&request(&["/books/Dune/01.mp3", "/books/Dune/02.mp3"]));
+        assert_eq!(name, "Dune");
+    }
+
+    #[test]
+    fn resumes_only_a_matching_plan() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Dune.transcript.partial.json");
+        let chunks = plan_chunks(&[1_200_000], None, None, 600);
+        let mut partial = PartialTranscript {
+            files: vec!["dune.m4b".into()],
+            model: "whisper.cpp/base".into(),
+            chunks: chunks.clone(),
+            ..Default::default()
+        };
+        partial.done.insert(0, vec![segment(0, 1_000, "Dune")]);
+        save_partial(&path, &partial).unwrap();
+
+        let loaded = load_partial(&path).unwrap();
+        assert_eq!(loaded.done.len(), 1);
+        assert!(loaded.chunks == chunks && loaded.model == "whisper.cpp/base");
+        assert_ne!(plan_chunks(&[1_200_000], None, None, 300), loaded.chunks);
+    }
+}
@@ -0,0 +1,315 @@
+// src-tauri/src/transcript_cache.rs
+// Whisper results cached by what was transcribed: the audio file's identity,
+// the windows scanned and the transcription model. A book found again under
+// a new scan id still hits; re-encoded audio or a different model misses.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::error::{AppError, ErrorKind};
+use crate::whisper::AudioIntroResult;
+
+/// What a transcript was made from. `key` is a hash of the other fields.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheKey {
+    pub key: String,
+    /// Local file path, or the ABS server and item id.
+    pub source: String,
+    /// Size and modification time of each file read, or their ABS inodes and sizes.
+    pub fingerprint: String,
+    /// Intro and outro windows scanned.
+    pub windows: String,
+    /// Transcription backend and model.
+    pub model: String,
+}
+
+impl CacheKey {
+    pub fn new(source: &str, fingerprint: &str, windows: &str, model: &str) -> Self {
+        let material = [source, fingerprint, windows, model].join("\n");
+        CacheKey {
+            key: format!("{:016x}", fnv1a(material.as_bytes())),
+            source: source.to_string(),
+            fingerprint: fingerprint.to_string(),
+            windows: windows.to_string(),
+            model: model.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    #[serde(flatten)]
+    pub key: CacheKey,
+    /// Item the transcript was first made for.
+    pub item_id: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Unix seconds.
+    pub created_at: i64,
+    pub result: AudioIntroResult,
+}
+
+/// One row of the cache listing, without the transcript.
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheSummary {
+    pub key: String,
+    pub item_id: String,
+    pub title: Option<String>,
+    pub source: String,
+    pub windows: String,
+    pub model: String,
+    pub created_at: i64,
+    pub size_bytes: u64,
+    pub narrators: Vec<String>,
+    pub authors: Vec<String>,
+    /// Written before cache keys existed (keyed by item id); it never hits
+    /// again and is safe to purge.
+    pub legacy: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct PurgeResult {
+    pub removed: usize,
+    pub freed_bytes: u64,
+}
+
+// ---- Paths ----
+
+fn cache_dir() -> Result<PathBuf, String> {
+    let dir = dirs::data_dir()
+        .ok_or("Cannot find data directory")?
+        .join("Audiobook Tagger")
+        .join("transcript_cache");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Cache dir error: {}", e))?;
+    Ok(dir)
+}
+
+fn entry_path(dir: &Path, key: &str) -> PathBuf {
+    dir.join(format!("{}.json", key))
+}
+
+// ---- Reading and writing ----
+
+/// The cached result for `key`, if any.
+pub fn get(key: &CacheKey) -> Option<AudioIntroResult> {
+    get_in(&cache_dir().ok()?, key)
+}
+
+/// Cache a result. Failures are logged, never raised: the cache is an optimisation.
+pub fn put(key: &CacheKey, title: Option<&str>, result: &AudioIntroResult) {
+    let written = cache_dir().map_err(AppError::from).and_then(|dir| put_in(&dir, key, title, result));
+    if let Err(e) = written {
+        eprintln!("   Could not cache transcript: {}", e.message);
+    }
+}
+
+fn get_in(dir: &Path, key: &CacheKey) -> Option<AudioIntroResult> {
+    let data = std::fs::read_to_string(entry_path(dir, &key.key)).ok()?;
+    let entry: CacheEntry = serde_json::from_str(&data).ok()?;
+    // Guard against hash collisions
+    (entry.key == *key).then_some(entry.result)
+}
+
+fn put_in(dir: &Path, key: &CacheKey, title: Option<&str>, result: &AudioIntroResult) -> Result<(), AppError> {
+    let entry = CacheEntry {
+        key: key.clone(),
+        item_id: result.item_id.clone(),
+        title: title.map(str::to_string),
+        created_at: now(),
+        result: result.clone(),
+    };
+    let json = serde_json::to_string_pretty(&entry).map_err(|e| AppError::from(e.to_string()))?;
+    std::fs::write(entry_path(dir, &key.key), json)
+        .map_err(|e| AppError::new(ErrorKind::Io, "Could not write the transcript cache").with_detail(e.to_string()))
+}
+
+/// Every entry in `dir` with its file size. Entries from before cache keys
+/// are wrapped with an empty key and flagged legacy.
+fn read_all(dir: &Path) -> Vec<(CacheEntry, u64, bool)> {
+    let Ok(files) = std::fs::read_dir(dir) else { return vec![] };
+    let mut entries = Vec::new();
+    for file in files.flatten() {
+        let path = file.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Ok(meta) = file.metadata() else { continue };
+        let Ok(data) = std::fs::read_to_string(&path) else { continue };
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string();
+
+        if let Ok(entry) = serde_json::from_str::<CacheEntry>(&data) {
+            entries.push((entry, meta.len(), false));
+        } else if let Ok(result) = serde_json::from_str::<AudioIntroResult>(&data) {
+            let created_at = meta.modified().ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_secs() as i64);
+            let key = CacheKey { key: stem, ..CacheKey::new("", "", "", "") };
+            let entry = CacheEntry { key, item_id: result.item_id.clone(), title: None, created_at, result };
+            entries.push((entry, meta.len(), true));
+        }
+    }
+    entries.sort_by_key(|(entry, _, _)| std::cmp::Reverse(entry.created_at));
+    entries
+}
+
+fn list_in(dir: &Path) -> Vec<CacheSummary> {
+    read_all(dir)
+        .into_iter()
+        .map(|(entry, size_bytes, legacy)| CacheSummary {
+            key: entry.key.key,
+            item_id: entry.item_id,
+            title: entry.title.or(entry.result.title),
+            source: entry.key.source,
+            windows: entry.key.windows,
+            model: entry.key.model,
+            created_at: entry.created_at,
+            size_bytes,
+            narrators: entry.result.narrators,
+            authors: entry.result.authors,
+            legacy,
+        })
+        .collect()
+}
+
+/// Remove the entries named in `keys` (all when None) that are older than
+/// `older_than_days` (any age when None).
+fn purge_in(dir: &Path, keys: Option<&[String]>, older_than_days: Option<u32>) -> PurgeResult {
+    let cutoff = older_than_days.map(|days| now() - i64::from(days) * 86_400);
+    let mut purged = PurgeResult::default();
+    for (entry, size, _) in read_all(dir) {
+        if keys.is_some_and(|keys| !keys.contains(&entry.key.key)) {
+            continue;
+        }
+        if cutoff.is_some_and(|cutoff| entry.created_at > cutoff) {
+            continue;
+        }
+        if std::fs::remove_file(entry_path(dir, &entry.key.key)).is_ok() {
+            purged.removed += 1;
+            purged.freed_bytes += size;
+        }
+    }
+    purged
+}
+
+fn disk_usage_in(dir: &Path) -> u64 {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.metadata().map(|m| m.len()).unwrap_or(0))
+        .sum()
+}
+
+// ---- Tauri commands ----
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn transcript_cache_list() -> Result<Vec<CacheSummary>, AppError> {
+    Ok(list_in(&cache_dir()?))
+}
+
+/// Full entry, transcript included.
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn transcript_cache_get(key: String) -> Result<CacheEntry, AppError> {
+    read_all(&cache_dir()?)
+        .into_iter()
+        .map(|(entry, _, _)| entry)
+        .find(|entry| entry.key.key == key)
+        .ok_or_else(|| AppError::new(ErrorKind::NotFound, "No cached transcript with that key"))
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn transcript_cache_purge(keys: Option<Vec<String>>, older_than_days: Option<u32>) -> Result<PurgeResult, AppError> {
+    Ok(purge_in(&cache_dir()?, keys.as_deref(), older_than_days))
+}
+
+/// Write every entry, transcripts included, to `path` as a JSON array.
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn transcript_cache_export(path: String) -> Result<String, AppError> {
+    let entries: Vec<CacheEntry> = read_all(&cache_dir()?).into_iter().map(|(entry, _, _)| entry).collect();
+    let json = serde_json::to_string_pretty(&entries).map_err(|e| AppError::from(e.to_string()))?;
+    std::fs::write(&path, json)
+        .map_err(|e| AppError::new(ErrorKind::Io, "Could not write the export").with_detail(e.to_string()))?;
+    Ok(format!("Exported {} transcripts", entries.len()))
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn transcript_cache_get_disk_usage() -> Result<u64, AppError> {
+    Ok(disk_usage_in(&cache_dir()?))
+}
+
+// ---- Helpers ----
+
+/// 64-bit FNV-1a: stable across builds, unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3))
+}
+
+fn now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(item_id: &str, narrator: &str) -> AudioIntroResult {
+        serde_json::from_value(serde_json::json!({
+            "item_id": item_id, "transcript": "Read by someone", "title": "Dune", "subtitle": null,
+            "narrators": [narrator], "authors": ["Frank Herbert"], "publisher": null,
+            "audio_publisher": null, "language": "en", "parse_method": "regex", "confidence": 0.6,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn keys_follow_audio_windows_and_model() {
+        let key = CacheKey::new("/books/dune.m4b", "1000:17", "intro:0-60", "whisper.cpp/base");
+        assert_eq!(key, CacheKey::new("/books/dune.m4b", "1000:17", "intro:0-60", "whisper.cpp/base"));
+        assert_eq!(key.key.len(), 16);
+        for other in [
+            CacheKey::new("/books/dune.m4b", "1000:18", "intro:0-60", "whisper.cpp/base"),
+            CacheKey::new("/books/dune.m4b", "1000:17", "intro:0-180", "whisper.cpp/base"),
+            CacheKey::new("/books/dune.m4b", "1000:17", "intro:0-60", "whisper.cpp/small"),
+        ] {
+            assert_ne!(key.key, other.key);
+        }
+    }
+
+    #[test]
+    fn stores_lists_and_purges_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let dune = CacheKey::new("/books/dune.m4b", "1000:17", "intro", "whisper.cpp/base");
+        let hobbit = CacheKey::new("/books/hobbit.m4b", "2000:17", "intro", "whisper.cpp/base");
+        assert!(get_in(dir.path(), &dune).is_none());
+
+        put_in(dir.path(), &dune, Some("Dune"), &result("scan-1", "Scott Brick")).unwrap();
+        put_in(dir.path(), &hobbit, None, &result("scan-2", "Andy Serkis")).unwrap();
+        assert_eq!(get_in(dir.path(), &dune).unwrap().narrators, vec!["Scott Brick"]);
+        // Same hash, different identity: not a hit
+        let forged = CacheKey { model: "openai/whisper-1".into(), ..dune.clone() };
+        assert!(get_in(dir.path(), &forged).is_none());
+
+        // A result cached by item id before keys existed
+        let legacy = serde_json::to_string(&result("abs_item", "Rob Inglis")).unwrap();
+        std::fs::write(dir.path().join("abs_item.json"), legacy).unwrap();
+
+        let listed = list_in(dir.path());
+        assert_eq!(listed.len(), 3);
+        let row = listed.iter().find(|r| r.key == dune.key).unwrap();
+        assert_eq!((row.title.as_deref(), row.source.as_str(), row.legacy), (Some("Dune"), "/books/dune.m4b", false));
+        assert!(listed.iter().any(|r| r.key == "abs_item" && r.legacy));
+        let total: u64 = listed.iter().map(|r| r.size_bytes).sum();
+        assert_eq!(disk_usage_in(dir.path()), total);
+
+        // Nothing is older than a day yet
+        assert_eq!(purge_in(dir.path(), None, Some(1)).removed, 0);
+        let purged = purge_in(dir.path(), Some(&["abs_item".to_string()]), None);
+        assert_eq!(purged.removed, 1);
+        assert!(purged.freed_bytes > 0);
+        assert_eq!(purge_in(dir.path(), None, None).removed, 2);
+        assert_eq!(disk_usage_in(dir.path()), 0);
+    }
+}
@@ -1,14 +1,28 @@
 // src-tauri/src/whisper.rs
 // Audio intro extraction: FFmpeg + OpenAI Whisper + regex parsing
-// Extracts narrator, author, publisher from first 60s of audiobook audio
+// Extracts narrator, author, publisher from the first speech in the audiobook,
+// plus the closing credits at the end of the final file
 
 use regex::Regex;
 use reqwest::multipart;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::Path;
 use std::process::Command;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use futures::StreamExt;
 use tempfile::NamedTempFile;
+use tokio::sync::Semaphore;
+
+use crate::abs::AbsClient;
+use crate::config::{get_secret, AppConfig, Secret};
+use crate::error::{AppError, ErrorKind};
+use crate::names::clean_person_name;
+use crate::progress::{ProgressEvent, ProgressSink, Stage};
+use crate::transcribe::Segment;
+use crate::reconcile::{reconcile, FieldCheck, Reference};
+use crate::scanner::BookMetadata;
+use crate::transcript_cache::{self, CacheKey};
 
 static CANCELLED: AtomicBool = AtomicBool::new(false);
 
@@ -26,6 +40,73 @@ pub struct AudioIntroResult {
     pub language: Option<String>,
     pub parse_method: String,
     pub confidence: f32,
+    /// Copyright or phonogram year, usually read out in the closing credits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub copyright_year: Option<String>,
+    /// How the book is performed, from the number of narrators or an
+    /// announced full cast or dramatization.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub production: Option<ProductionType>,
+    /// Transcript of the closing credits, when the outro pass ran.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outro_transcript: Option<String>,
+    /// Which pass each found field came from and how far to trust it.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub field_confidence: BTreeMap<String, FieldConfidence>,
+    /// How title, authors and narrators compare with what the book already
+    /// has: confirmed, new or conflicting.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub field_status: BTreeMap<String, FieldCheck>,
+    /// Why nothing was extracted. Set instead of failing the whole batch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<AppError>,
+}
+
+/// Same values as the `production` field of book DNA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProductionType {
+    SingleVoice,
+    /// Two or more narrators taking turns.
+    DualNarrator,
+    FullCast,
+    Dramatized,
+}
+
+impl ProductionType {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().replace([' ', '_'], "-").as_str() {
+            "single-voice" | "single-narrator" => Some(ProductionType::SingleVoice),
+            "dual-narrator" | "dual-narrators" | "multi-narrator" | "multiple-narrators" => Some(ProductionType::DualNarrator),
+            "full-cast" => Some(ProductionType::FullCast),
+            "dramatized" | "dramatised" | "dramatization" => Some(ProductionType::Dramatized),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProductionType::SingleVoice => "single-voice",
+            ProductionType::DualNarrator => "dual-narrator",
+            ProductionType::FullCast => "full-cast",
+            ProductionType::Dramatized => "dramatized",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldSource {
+    Intro,
+    Outro,
+    /// Intro and outro agreed.
+    Both,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FieldConfidence {
+    pub source: FieldSource,
+    pub confidence: f32,
 }
 
 /// Request for audio intro extraction
@@ -35,6 +116,9 @@ pub struct AudioIntroRequest {
     pub source: String,            // "abs" or "local"
     pub title: Option<String>,
     pub author: Option<String>,
+    /// The book's current narrator, checked against what the intro says.
+    #[serde(default)]
+    pub narrator: Option<String>,
     pub file_ino: Option<String>,
     pub file_path: Option<String>,
     pub abs_base_url: Option<String>,
@@ -45,6 +129,19 @@ pub struct AudioIntroRequest {
     pub ollama_base_url: Option<String>,
     pub use_local_whisper: Option<bool>,
     pub whisper_model: Option<String>,
+    /// Final file of a multi-file book, for the outro pass. Defaults to
+    /// `file_ino` / `file_path` (or the item's last ABS file).
+    #[serde(default)]
+    pub last_file_ino: Option<String>,
+    #[serde(default)]
+    pub last_file_path: Option<String>,
+    /// Scan the closing credits: always, never, or (unset) only when the
+    /// intro found no narrator or no publisher.
+    #[serde(default)]
+    pub scan_outro: Option<bool>,
+    /// Length of the outro window in minutes.
+    #[serde(default)]
+    pub outro_minutes: Option<u32>,
 }
 
 /// Book info extracted from transcript via regex
@@ -52,188 +149,666 @@ pub struct AudioIntroRequest {
 struct ExtractedBookInfo {
     title: Option<String>,
     subtitle: Option<String>,
-    author: Option<String>,
-    narrator: Option<String>,
+    authors: Vec<String>,
+    narrators: Vec<String>,
     publisher: Option<String>,
     audio_publisher: Option<String>,
+    copyright_year: Option<String>,
+    production: Option<ProductionType>,
 }
 
-// ---- Tauri commands ----
+/// Which part of the book a transcript comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Passage {
+    Intro,
+    Outro,
+}
 
-#[tauri::command]
-pub fn cancel_audio_extraction() -> Result<String, String> {
-    CANCELLED.store(true, Ordering::SeqCst);
-    Ok("Cancelled".to_string())
+/// Where a pass reads its audio: streamed from ABS or a local file.
+#[derive(Clone)]
+enum AudioInput {
+    Abs { url: String, token: String },
+    Local { path: String },
 }
 
-#[tauri::command]
-pub async fn extract_audio_intro(request: AudioIntroRequest, window: tauri::Window) -> Result<AudioIntroResult, String> {
-    if !check_ffmpeg_available() {
-        return Err("FFmpeg is not installed. Install it with: brew install ffmpeg".to_string());
+/// One stretch of audio to transcribe and parse.
+struct Window<'a> {
+    input: &'a AudioInput,
+    start_secs: u32,
+    duration_secs: u32,
+    passage: Passage,
+}
+
+/// Fixed intro passes, used when speech detection finds nothing to aim at:
+/// Pass 1: 0-60s (most intros are here)
+/// Pass 2: 60-180s (skip dedication/music, catch late intros)
+/// Pass 3: 0-180s (grab everything, longer context)
+const INTRO_WINDOWS: &[(u32, u32, &str)] = &[
+    (0, 60, "first 60s"),
+    (60, 120, "60-180s"),
+    (0, 180, "first 3 min"),
+];
+/// The intro passes read up to this far into the first file; speech
+/// detection looks for narration within it.
+const INTRO_SCANNED_SECS: u32 = 300;
+/// At most this many speech windows are transcribed per book.
+const MAX_SPEECH_WINDOWS: usize = 3;
+const SPEECH_WINDOW_SECS: u32 = 60;
+const DEFAULT_OUTRO_MINUTES: u32 = 3;
+
+/// Books processed at once, mirroring the frontend's Performance settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntroConcurrency {
+    /// Local whisper-cpp and Ollama (CPU/GPU bound).
+    #[serde(default = "default_local_concurrency")]
+    pub local_concurrency: usize,
+    /// Cloud Whisper and cloud LLM (rate limited).
+    #[serde(default = "default_cloud_concurrency")]
+    pub cloud_concurrency: usize,
+}
+
+fn default_local_concurrency() -> usize { 2 }
+fn default_cloud_concurrency() -> usize { 5 }
+
+impl Default for IntroConcurrency {
+    fn default() -> Self {
+        IntroConcurrency {
+            local_concurrency: default_local_concurrency(),
+            cloud_concurrency: default_cloud_concurrency(),
+        }
+    }
+}
+
+/// Per-stage limits for a batch. Each stage holds its permit only while it
+/// runs, so book N+1's FFmpeg extraction overlaps book N's transcription.
+pub struct StageLimits {
+    ffmpeg: Semaphore,
+    local_whisper: Semaphore,
+    cloud_whisper: Semaphore,
+    llm: Semaphore,
+    in_flight: usize,
+}
+
+impl StageLimits {
+    /// Limits for a batch of `items`; they share one config, so the first
+    /// item decides whether transcription and parsing are local or cloud.
+    pub fn for_items(items: &[AudioIntroRequest], concurrency: IntroConcurrency) -> Self {
+        let local = concurrency.local_concurrency.max(1);
+        let cloud = concurrency.cloud_concurrency.max(1);
+        let first = items.first();
+        let local_whisper = first.and_then(|r| r.use_local_whisper).unwrap_or(false);
+        let local_llm = first.and_then(|r| r.use_local_ai).unwrap_or(false);
+
+        let ffmpeg = local.max(cloud);
+        let whisper = if local_whisper { local } else { cloud };
+        StageLimits {
+            ffmpeg: Semaphore::new(ffmpeg),
+            local_whisper: Semaphore::new(local),
+            cloud_whisper: Semaphore::new(cloud),
+            llm: Semaphore::new(if local_llm { local } else { cloud }),
+            // Enough books in flight to keep every stage busy
+            in_flight: ffmpeg + whisper,
+        }
+    }
+
+    /// One book at a time through every stage.
+    pub fn sequential() -> Self {
+        StageLimits {
+            ffmpeg: Semaphore::new(1),
+            local_whisper: Semaphore::new(1),
+            cloud_whisper: Semaphore::new(1),
+            llm: Semaphore::new(1),
+            in_flight: 1,
+        }
     }
 
-    // Check cache
-    if let Some(cached) = get_cached_transcript(&request.item_id) {
-        return Ok(cached);
+    pub fn in_flight(&self) -> usize {
+        self.in_flight
     }
+}
+
+// ---- Tauri commands ----
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn cancel_audio_extraction() -> Result<String, AppError> {
+    CANCELLED.store(true, Ordering::SeqCst);
+    Ok("Cancelled".to_string())
+}
 
-    Ok(extract_intro_metadata_with_stages(&request, &window, 1, 1).await)
+#[cfg(feature = "gui")]
+#[tauri::command]
+pub async fn extract_audio_intro(request: AudioIntroRequest, window: tauri::Window) -> Result<AudioIntroResult, AppError> {
+    extract_intro(&request, &window).await
 }
 
+#[cfg(feature = "gui")]
 #[tauri::command]
 pub async fn batch_extract_audio_intros(
     items: Vec<AudioIntroRequest>,
     force: bool,
+    concurrency: Option<IntroConcurrency>,
     window: tauri::Window,
-) -> Result<Vec<AudioIntroResult>, String> {
-    use tauri::Emitter;
+) -> Result<Vec<AudioIntroResult>, AppError> {
+    let concurrency = concurrency.unwrap_or_else(|| AppConfig::load_or_default().concurrency());
+    batch_extract_intros(items, force, concurrency, &window).await
+}
+
+// ---- Entry points shared by the app and the CLI ----
+
+/// Fill settings and credentials the caller left out from the saved config,
+/// so the frontend no longer has to pass API keys with every request.
+pub fn with_saved_config(mut items: Vec<AudioIntroRequest>) -> Vec<AudioIntroRequest> {
+    let config = AppConfig::load_or_default();
+    let abs_token = get_secret(Secret::AbsApiToken);
+    let openai_key = get_secret(Secret::OpenaiApiKey);
+
+    for item in &mut items {
+        if item.abs_base_url.is_none() {
+            item.abs_base_url = config.abs_url().map(str::to_string);
+        }
+        item.abs_api_token = item.abs_api_token.take().or_else(|| abs_token.clone());
+        item.openai_api_key = item.openai_api_key.take().or_else(|| openai_key.clone());
+        item.use_local_ai = item.use_local_ai.or(config.use_local_ai);
+        item.ollama_model = item.ollama_model.take().or_else(|| config.ollama_model.clone());
+        item.ollama_base_url = item.ollama_base_url.take().or_else(|| config.ollama_base_url.clone());
+        item.use_local_whisper = item.use_local_whisper.or(config.use_local_whisper);
+        item.whisper_model = item.whisper_model.take().or_else(|| config.whisper_model.clone());
+        item.scan_outro = item.scan_outro.or(config.scan_outro);
+        item.outro_minutes = item.outro_minutes.or(config.outro_minutes);
+    }
+    items
+}
 
+/// Extract intro metadata for one book, returning the cached result if any.
+pub async fn extract_intro(request: &AudioIntroRequest, progress: &dyn ProgressSink) -> Result<AudioIntroResult, AppError> {
     if !check_ffmpeg_available() {
-        return Err("FFmpeg is not installed. Install it with: brew install ffmpeg".to_string());
+        return Err(AppError::ffmpeg_missing());
+    }
+    let request = &with_saved_config(vec![request.clone()])[0];
+    let reference = Reference::load();
+
+    let result = extract_item(request, false, &reference, progress, &StageLimits::sequential(), 1, 1).await;
+    match result.error {
+        Some(e) => Err(e),
+        None => Ok(result),
+    }
+}
+
+/// Extract intro metadata for several books, several at a time within the
+/// `concurrency` limits. Results come back in input order. `force` bypasses
+/// the transcript cache. Stops starting new books when
+/// `cancel_audio_extraction` is called. Per-book failures are reported in
+/// each result's `error`.
+pub async fn batch_extract_intros(
+    items: Vec<AudioIntroRequest>,
+    force: bool,
+    concurrency: IntroConcurrency,
+    progress: &dyn ProgressSink,
+) -> Result<Vec<AudioIntroResult>, AppError> {
+    if !check_ffmpeg_available() {
+        return Err(AppError::ffmpeg_missing());
     }
 
     // Reset cancel flag
     CANCELLED.store(false, Ordering::SeqCst);
 
+    let items = with_saved_config(items);
     let total = items.len();
-    let mut results = Vec::with_capacity(total);
-    let mut found_count = 0;
-    let mut cached_count = 0;
-    let mut skipped_count = 0;
-
-    for (i, request) in items.into_iter().enumerate() {
-        // Check if cancelled
-        if CANCELLED.load(Ordering::SeqCst) {
-            let _ = window.emit("audio_intro_progress", serde_json::json!({
-                "current": i, "total": total, "found": found_count, "cached": cached_count,
-                "stage": "cancelled",
-                "status": format!("Cancelled after {} of {} books ({} found)", i, total, found_count),
-            }));
-            break;
-        }
+    let limits = StageLimits::for_items(&items, concurrency);
+    let reference = Reference::load();
+    let found_count = AtomicUsize::new(0);
+    let cached_count = AtomicUsize::new(0);
+    let skipped_count = AtomicUsize::new(0);
+    let failed_count = AtomicUsize::new(0);
+    let counts = || (found_count.load(Ordering::SeqCst), cached_count.load(Ordering::SeqCst));
+
+    let results: Vec<Option<AudioIntroResult>> = futures::stream::iter(items.iter().enumerate())
+        .map(|(i, request)| {
+            let (limits, reference, found_count, cached_count, skipped_count, failed_count) =
+                (&limits, &reference, &found_count, &cached_count, &skipped_count, &failed_count);
+            async move {
+                if CANCELLED.load(Ordering::SeqCst) {
+                    return None;
+                }
+                let title = request.title.as_deref().unwrap_or(&request.item_id);
+
+                // Check cache first
+                let key = cache_key(request).await;
+                if !force {
+                    if let Some(cached) = cached_result(key.as_ref(), request) {
+                        if !cached.narrators.is_empty() { found_count.fetch_add(1, Ordering::SeqCst); }
+                        cached_count.fetch_add(1, Ordering::SeqCst);
+                        let (found, cached_n) = counts();
+                        progress.report(
+                            ProgressEvent::intro(Stage::Cached, i + 1, total, format!("{} (cached)", title))
+                                .with_counts(found, cached_n),
+                        );
+                        return Some(reconciled(cached, request, reference));
+                    }
+                }
 
-        let title = request.title.as_deref().unwrap_or(&request.item_id);
-
-        // Check cache first
-        if !force {
-            if let Some(cached) = get_cached_transcript(&request.item_id) {
-                if !cached.narrators.is_empty() { found_count += 1; }
-                cached_count += 1;
-                let _ = window.emit("audio_intro_progress", serde_json::json!({
-                    "current": i + 1, "total": total, "found": found_count, "cached": cached_count,
-                    "stage": "cached",
-                    "status": format!("{} (cached)", title),
-                }));
-                results.push(cached);
-                continue;
+                // Stage: downloading
+                let (found, cached_n) = counts();
+                progress.report(
+                    ProgressEvent::intro(Stage::Downloading, i + 1, total, format!("Downloading audio: {}", title))
+                        .with_counts(found, cached_n),
+                );
+
+                let result = resolve_intro(request, None, reference, || {
+                    extract_intro_metadata_with_stages(request, key.as_ref(), progress, limits, i + 1, total)
+                })
+                .await;
+                if !result.narrators.is_empty() { found_count.fetch_add(1, Ordering::SeqCst); }
+                if result.error.is_some() {
+                    failed_count.fetch_add(1, Ordering::SeqCst);
+                } else if result.narrators.is_empty() && result.transcript.is_none() {
+                    skipped_count.fetch_add(1, Ordering::SeqCst);
+                }
+                Some(result)
             }
-        }
+        })
+        .buffered(limits.in_flight())
+        .collect()
+        .await;
 
-        // Stage: downloading
-        let _ = window.emit("audio_intro_progress", serde_json::json!({
-            "current": i + 1, "total": total, "found": found_count, "cached": cached_count,
-            "stage": "downloading",
-            "status": format!("Downloading audio: {}", title),
-        }));
-
-        let result = extract_intro_metadata_with_stages(&request, &window, i + 1, total).await;
-        if !result.narrators.is_empty() { found_count += 1; }
-        if result.narrators.is_empty() && result.transcript.is_none() { skipped_count += 1; }
-        results.push(result);
-    }
+    let results: Vec<AudioIntroResult> = results.into_iter().flatten().collect();
 
     // Clean up any lingering temp files in the system temp dir
     cleanup_temp_files();
 
-    if !CANCELLED.load(Ordering::SeqCst) {
-        let _ = window.emit("audio_intro_progress", serde_json::json!({
-            "current": total, "total": total, "found": found_count, "cached": cached_count,
-            "stage": "complete",
-            "status": format!("Done! {} found, {} cached, {} skipped", found_count, cached_count, skipped_count),
-        }));
+    let (found, cached) = counts();
+    if CANCELLED.load(Ordering::SeqCst) {
+        let done = results.len();
+        progress.report(
+            ProgressEvent::intro(Stage::Cancelled, done, total, format!("Cancelled after {} of {} books ({} found)", done, total, found))
+                .with_counts(found, cached),
+        );
+    } else {
+        progress.report(
+            ProgressEvent::intro(
+                Stage::Complete, total, total,
+                format!(
+                    "Done! {} found, {} cached, {} skipped, {} failed",
+                    found, cached, skipped_count.load(Ordering::SeqCst), failed_count.load(Ordering::SeqCst),
+                ),
+            )
+            .with_counts(found, cached),
+        );
     }
 
     Ok(results)
 }
 
+/// One book for the job queue: the cached result unless `force`, otherwise
+/// a fresh extraction, checked against `reference`. Failures are reported
+/// in the result's `error`.
+pub async fn extract_item(
+    request: &AudioIntroRequest,
+    force: bool,
+    reference: &Reference,
+    progress: &dyn ProgressSink,
+    limits: &StageLimits,
+    current: usize,
+    total: usize,
+) -> AudioIntroResult {
+    let key = cache_key(request).await;
+    let cached = if force { None } else { cached_result(key.as_ref(), request) };
+    resolve_intro(request, cached, reference, || {
+        extract_intro_metadata_with_stages(request, key.as_ref(), progress, limits, current, total)
+    })
+    .await
+}
+
+/// The cached result when there is one, otherwise a fresh `extract`; either
+/// way checked against `reference`.
+async fn resolve_intro<F, Fut>(
+    request: &AudioIntroRequest,
+    cached: Option<AudioIntroResult>,
+    reference: &Reference,
+    extract: F,
+) -> AudioIntroResult
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = AudioIntroResult>,
+{
+    let result = match cached {
+        Some(cached) => cached,
+        None => extract().await,
+    };
+    reconciled(result, request, reference)
+}
+
+/// `result` with near-misses corrected and each field compared with the
+/// book's current title, author and narrator. The cache keeps the result as
+/// heard, since the book's metadata can change.
+fn reconciled(mut result: AudioIntroResult, request: &AudioIntroRequest, reference: &Reference) -> AudioIntroResult {
+    if result.error.is_none() {
+        let current = BookMetadata {
+            title: request.title.clone().unwrap_or_default(),
+            author: request.author.clone().unwrap_or_default(),
+            narrator: request.narrator.clone().unwrap_or_default(),
+            ..Default::default()
+        };
+        reconcile(&mut result, &current, reference);
+    }
+    result
+}
+
+// ---- Cache keys ----
+
+/// What this request would transcribe: the files it reads, the windows it
+/// scans and the model. None when the audio can't be identified, in which
+/// case nothing is cached.
+async fn cache_key(request: &AudioIntroRequest) -> Option<CacheKey> {
+    let (source, fingerprint) = if request.source == "abs" {
+        let base = request.abs_base_url.as_deref().unwrap_or("");
+        let client = AbsClient::new(base, request.abs_api_token.as_deref().unwrap_or("")).ok()?;
+        let files = client.item_files(&request.item_id).await.ok()?;
+        let first = match request.file_ino.as_deref() {
+            Some(ino) => files.iter().find(|f| f.ino == ino),
+            None => files.first(),
+        }?;
+        let last = match request.last_file_ino.as_deref() {
+            Some(ino) => files.iter().find(|f| f.ino == ino),
+            None => files.last(),
+        }?;
+        let id = |f: &crate::abs::AudioFile| format!("{}:{}", f.ino, f.metadata.size.unwrap_or(0));
+        (format!("{}/items/{}", base.trim_end_matches('/'), request.item_id), format!("{} {}", id(first), id(last)))
+    } else {
+        let first = request.file_path.as_deref()?;
+        let last = request.last_file_path.as_deref().unwrap_or(first);
+        (first.to_string(), format!("{} {}", file_fingerprint(first)?, file_fingerprint(last)?))
+    };
+    Some(CacheKey::new(&source, &fingerprint, &windows_label(request), &model_label(request)))
+}
+
+/// Size and modification time of a local file.
+fn file_fingerprint(path: &str) -> Option<String> {
+    let meta = std::fs::metadata(path).ok()?;
+    let mtime = meta.modified().ok()?.duration_since(std::time::UNIX_EPOCH).ok()?;
+    Some(format!("{}:{}", meta.len(), mtime.as_secs()))
+}
+
+fn windows_label(request: &AudioIntroRequest) -> String {
+    let mut intro = vec![format!("speech<{}s", INTRO_SCANNED_SECS)];
+    intro.extend(INTRO_WINDOWS.iter().map(|(start, len, _)| format!("{}-{}", start, start + len)));
+    let outro = match request.scan_outro {
+        Some(true) => "on",
+        Some(false) => "off",
+        None => "auto",
+    };
+    let minutes = request.outro_minutes.unwrap_or(DEFAULT_OUTRO_MINUTES);
+    format!("intro:{};outro:{}:{}m", intro.join(","), outro, minutes)
+}
+
+fn model_label(request: &AudioIntroRequest) -> String {
+    if request.use_local_whisper.unwrap_or(false) {
+        format!("whisper.cpp/{}", request.whisper_model.as_deref().unwrap_or("base"))
+    } else {
+        TranscriptionProvider::from_config(request.openai_api_key.as_deref()).label()
+    }
+}
+
+/// The cached result for this request, relabelled with its current item id
+/// (local scan ids change between scans).
+fn cached_result(key: Option<&CacheKey>, request: &AudioIntroRequest) -> Option<AudioIntroResult> {
+    let mut cached = transcript_cache::get(key?)?;
+    cached.item_id = request.item_id.clone();
+    Some(cached)
+}
+
 // ---- Pipeline ----
 
-/// Pipeline with stage-by-stage progress events
-/// Tries multiple time windows if first pass finds nothing useful
+/// Intro passes, then the outro pass when it can fill gaps, merged field by
+/// field. Caches the merged result unless it failed.
 async fn extract_intro_metadata_with_stages(
     request: &AudioIntroRequest,
-    window: &tauri::Window,
+    key: Option<&CacheKey>,
+    progress: &dyn ProgressSink,
+    limits: &StageLimits,
+    current: usize,
+    total: usize,
+) -> AudioIntroResult {
+    let mut result = scan_intro_windows(request, progress, limits, current, total).await;
+    if result.error.is_some() {
+        return result;
+    }
+    score_fields(&mut result, FieldSource::Intro);
+
+    if wants_outro(request, &result) {
+        match scan_outro(request, progress, limits, current, total).await {
+            Ok(Some(mut outro)) => {
+                score_fields(&mut outro, FieldSource::Outro);
+                result = merge_outro(result, outro);
+            }
+            Ok(None) => {}
+            // The intro result stands on its own; a missing outro is not an error
+            Err(e) => {
+                let title = request.title.as_deref().unwrap_or(&request.item_id);
+                progress.report(ProgressEvent::intro(
+                    Stage::Parsing,
+                    current,
+                    total,
+                    format!("Outro scan failed for {}, keeping the intro: {}", title, e.message),
+                ));
+            }
+        }
+    }
+
+    if let Some(key) = key {
+        transcript_cache::put(key, request.title.as_deref(), &result);
+    }
+    result
+}
+
+/// Transcribes the intro's speech windows (or the fixed windows) in order,
+/// stopping at the first that finds enough
+async fn scan_intro_windows(
+    request: &AudioIntroRequest,
+    progress: &dyn ProgressSink,
+    limits: &StageLimits,
     current: usize,
     total: usize,
 ) -> AudioIntroResult {
-    use tauri::Emitter;
     let item_id = request.item_id.clone();
     let title = request.title.as_deref().unwrap_or(&item_id);
 
-    // Try progressively deeper into the audio if first pass finds nothing
-    // Pass 1: 0-60s (most intros are here)
-    // Pass 2: 60-180s (skip dedication/music, catch late intros)
-    // Pass 3: 0-180s (grab everything, longer context)
-    let time_windows: &[(u32, u32, &str)] = &[
-        (0, 60, "first 60s"),
-        (60, 120, "60-180s"),
-        (0, 180, "first 3 min"),
-    ];
+    let input = match intro_input(request).await {
+        Ok(input) => input,
+        Err(e) => {
+            progress.report(ProgressEvent::intro(Stage::Error, current, total, format!("{}: {}", title, e.message)));
+            return error_result(&item_id, e);
+        }
+    };
 
-    for (pass_idx, &(start_secs, duration_secs, label)) in time_windows.iter().enumerate() {
-        let is_retry = pass_idx > 0;
+    // Aim at the narration rather than the theme music; if none is found,
+    // try progressively deeper fixed windows
+    progress.report(ProgressEvent::intro(Stage::Extracting, current, total, format!("Finding speech: {}", title)));
+    let detected = {
+        let _permit = limits.ffmpeg.acquire().await;
+        let input = input.clone();
+        run_blocking(move || find_silences(&input, INTRO_SCANNED_SECS)).await
+    };
+    let time_windows: Vec<(u32, u32, String)> = match detected {
+        Ok(silences) => speech_windows(&speech_regions(&silences, u64::from(INTRO_SCANNED_SECS) * 1000)),
+        Err(e) => {
+            progress.report(ProgressEvent::intro(
+                Stage::Extracting,
+                current,
+                total,
+                format!("Speech detection failed for {}, using fixed windows: {}", title, e.message),
+            ));
+            Vec::new()
+        }
+    };
+    let time_windows = if time_windows.is_empty() {
+        INTRO_WINDOWS.iter().map(|&(start, len, label)| (start, len, label.to_string())).collect()
+    } else {
+        time_windows
+    };
+    let mut previous: Option<AudioIntroResult> = None;
 
-        let _ = window.emit("audio_intro_progress", serde_json::json!({
-            "current": current, "total": total,
-            "stage": if is_retry { "deep_scan" } else { "extracting" },
-            "status": format!("{}: {} ({})", if is_retry { "Deep scan" } else { "Extracting" }, title, label),
-        }));
+    for (pass_idx, (start_secs, duration_secs, label)) in time_windows.iter().enumerate() {
+        let (start_secs, duration_secs) = (*start_secs, *duration_secs);
+        let is_retry = pass_idx > 0;
 
-        let result = try_extract_at_offset(request, window, current, total, start_secs, duration_secs).await;
+        progress.report(ProgressEvent::intro(
+            if is_retry { Stage::DeepScan } else { Stage::Extracting },
+            current,
+            total,
+            format!("{}: {} ({})", if is_retry { "Deep scan" } else { "Extracting" }, title, label),
+        ));
 
-        // Consider it a good result if we found at least 2 useful fields
-        let fields_found = [
-            result.title.is_some(),
-            !result.narrators.is_empty(),
-            !result.authors.is_empty(),
-            result.publisher.is_some() || result.audio_publisher.is_some(),
-        ].iter().filter(|&&v| v).count();
+        let window = Window { input: &input, start_secs, duration_secs, passage: Passage::Intro };
+        let result = match try_extract_at_offset(request, progress, limits, current, total, window).await {
+            Ok(result) => result,
+            Err(e) => {
+                progress.report(ProgressEvent::intro(Stage::Error, current, total, format!("{}: {}", title, e.message)));
+                // A deeper window can fail where the first worked (e.g. a short file);
+                // keep the earlier transcript. Errors themselves are never cached.
+                return previous.unwrap_or_else(|| error_result(&item_id, e));
+            }
+        };
 
-        if fields_found >= 2 {
-            cache_transcript(&result);
+        // Consider it a good result if we found at least 2 useful fields.
+        // If this is the last pass, return whatever we got (even if empty),
+        // unless it failed where an earlier window didn't
+        if fields_found(&result) >= 2 {
             return result;
         }
-
-        // If this is the last pass, return whatever we got (even if empty)
         if pass_idx == time_windows.len() - 1 {
-            cache_transcript(&result);
-            return result;
+            return match previous {
+                Some(previous) if result.error.is_some() && previous.error.is_none() => previous,
+                _ => result,
+            };
         }
 
         // Otherwise, try next window
-        let _ = window.emit("audio_intro_progress", serde_json::json!({
-            "current": current, "total": total,
-            "stage": "deep_scan",
-            "status": format!("No metadata in {}, scanning deeper: {}", label, title),
-        }));
+        progress.report(ProgressEvent::intro(Stage::DeepScan, current, total, format!("No metadata in {}, scanning deeper: {}", label, title)));
+        previous = match previous {
+            Some(previous) if result.error.is_some() => Some(previous),
+            _ => Some(result),
+        };
     }
 
-    // Should not reach here, but just in case
-    empty_result(&item_id)
+    // Every window list has at least the fixed windows, so this is unreachable
+    error_result(&item_id, AppError::new(ErrorKind::Internal, "No intro windows to scan"))
+}
+
+fn fields_found(result: &AudioIntroResult) -> usize {
+    [
+        result.title.is_some(),
+        !result.narrators.is_empty(),
+        !result.authors.is_empty(),
+        result.publisher.is_some() || result.audio_publisher.is_some(),
+    ].iter().filter(|&&v| v).count()
+}
+
+/// Whether to spend a second transcription on the closing credits.
+fn wants_outro(request: &AudioIntroRequest, intro: &AudioIntroResult) -> bool {
+    request.scan_outro.unwrap_or_else(|| {
+        intro.narrators.is_empty() || (intro.publisher.is_none() && intro.audio_publisher.is_none())
+    })
+}
+
+/// Transcribe and parse the last minutes of the final file. None when there
+/// is nothing the intro passes have not already heard.
+async fn scan_outro(
+    request: &AudioIntroRequest,
+    progress: &dyn ProgressSink,
+    limits: &StageLimits,
+    current: usize,
+    total: usize,
+) -> Result<Option<AudioIntroResult>, AppError> {
+    let title = request.title.as_deref().unwrap_or(&request.item_id);
+    let (input, file_secs, same_file) = outro_input(request).await?;
+    let minutes = request.outro_minutes.unwrap_or(DEFAULT_OUTRO_MINUTES);
+    let Some((start_secs, duration_secs)) = outro_span(file_secs, minutes, same_file) else {
+        return Ok(None);
+    };
+
+    progress.report(ProgressEvent::intro(
+        Stage::DeepScan, current, total,
+        format!("Outro scan: {} (last {} min)", title, minutes.max(1)),
+    ));
+    let window = Window { input: &input, start_secs, duration_secs, passage: Passage::Outro };
+    try_extract_at_offset(request, progress, limits, current, total, window).await.map(Some)
+}
+
+/// Audio for the intro passes: the first (or requested) file.
+async fn intro_input(request: &AudioIntroRequest) -> Result<AudioInput, AppError> {
+    if request.source == "abs" {
+        // Stream directly from ABS - FFmpeg fetches only what it needs (no full download)
+        Ok(AudioInput::Abs {
+            url: build_abs_audio_url(request).await?,
+            token: request.abs_api_token.clone().unwrap_or_default(),
+        })
+    } else {
+        Ok(AudioInput::Local { path: request.file_path.clone().unwrap_or_default() })
+    }
+}
+
+/// Audio for the outro pass: the book's final file, its length in seconds,
+/// and whether it is the same file the intro passes read.
+async fn outro_input(request: &AudioIntroRequest) -> Result<(AudioInput, f64, bool), AppError> {
+    if request.source == "abs" {
+        let client = AbsClient::new(
+            request.abs_base_url.as_deref().unwrap_or(""),
+            request.abs_api_token.as_deref().unwrap_or(""),
+        )?;
+        let item = client.item(&request.item_id).await?;
+        let files = &item.media.audio_files;
+        let last = match request.last_file_ino.as_deref() {
+            Some(ino) => files.iter().find(|f| f.ino == ino),
+            None => files.last(),
+        }
+        .ok_or_else(|| AppError::new(ErrorKind::AbsNotFound, "AudiobookShelf item has no audio files"))?;
+
+        // Single-file books may only carry the item-level duration
+        let secs = last.duration
+            .or(if files.len() == 1 { item.media.duration } else { None })
+            .unwrap_or(0.0);
+        let first_ino = request.file_ino.as_deref().or(files.first().map(|f| f.ino.as_str()));
+        let same_file = first_ino == Some(last.ino.as_str());
+        let input = AudioInput::Abs {
+            url: client.file_url(&request.item_id, &last.ino),
+            token: request.abs_api_token.clone().unwrap_or_default(),
+        };
+        Ok((input, secs, same_file))
+    } else {
+        let first = request.file_path.clone().unwrap_or_default();
+        let path = request.last_file_path.clone().unwrap_or_else(|| first.clone());
+        let secs = crate::tags::read_file_tags(Path::new(&path))
+            .map_err(|e| AppError::new(ErrorKind::Io, "Could not read the final file's duration").with_detail(e))?
+            .duration_secs as f64;
+        let same_file = path == first;
+        Ok((AudioInput::Local { path }, secs, same_file))
+    }
+}
+
+/// Start and length of the outro window in a file of `file_secs`. When the
+/// intro passes read the same file, the window never overlaps them.
+fn outro_span(file_secs: f64, minutes: u32, same_file: bool) -> Option<(u32, u32)> {
+    let file_secs = file_secs.max(0.0).floor() as u32;
+    let mut start = file_secs.saturating_sub(minutes.max(1) * 60);
+    if same_file {
+        start = start.max(INTRO_SCANNED_SECS);
+    }
+    (start < file_secs).then(|| (start, file_secs - start))
 }
 
 /// Try extracting metadata from a specific time window in the audio
 async fn try_extract_at_offset(
     request: &AudioIntroRequest,
-    window: &tauri::Window,
+    progress: &dyn ProgressSink,
+    limits: &StageLimits,
     current: usize,
     total: usize,
-    start_secs: u32,
-    duration_secs: u32,
-) -> AudioIntroResult {
-    use tauri::Emitter;
+    window: Window<'_>,
+) -> Result<AudioIntroResult, AppError> {
     let item_id = request.item_id.clone();
     let title = request.title.as_deref().unwrap_or(&item_id);
+    let (start_secs, duration_secs) = (window.start_secs, window.duration_secs);
 
     let use_local_whisper = request.use_local_whisper.unwrap_or(false);
     let whisper_model = request.whisper_model.as_deref().unwrap_or("base");
@@ -245,117 +820,87 @@ async fn try_extract_at_offset(
         (".mp3", "mp3")
     };
 
-    let temp_audio = match NamedTempFile::with_suffix(out_suffix) {
-        Ok(f) => f,
-        Err(e) => {
-            println!("   Temp file error for {}: {}", item_id, e);
-            return empty_result(&item_id);
-        }
-    };
+    let temp_audio = NamedTempFile::with_suffix(out_suffix)
+        .map_err(|e| AppError::new(ErrorKind::Io, "Could not create a temp file").with_detail(e.to_string()))?;
     let out_path = temp_audio.path().to_string_lossy().to_string();
 
     // Build FFmpeg input: stream from ABS URL or read local file
-    let extract_result = if request.source == "abs" {
-        // Stream directly from ABS - FFmpeg fetches only what it needs (no full download)
-        let abs_url = match build_abs_audio_url(request).await {
-            Ok(url) => url,
-            Err(e) => {
-                println!("   ABS URL error for {}: {}", item_id, e);
-                return empty_result(&item_id);
-            }
-        };
-        let token = request.abs_api_token.as_deref().unwrap_or("");
-
-        extract_audio_from_url_with_offset(&abs_url, token, &out_path, start_secs, duration_secs, out_format)
-    } else {
-        // Local file
-        let local_path = request.file_path.as_deref().unwrap_or("");
-        extract_audio_segment(local_path, &out_path, start_secs, duration_secs, out_format)
-    };
+    match window.input {
+        AudioInput::Abs { url, token } => {
+            let (url, token, out_path) = (url.clone(), token.clone(), out_path.clone());
+
+            let _permit = limits.ffmpeg.acquire().await;
+            run_blocking(move || {
+                extract_audio_from_url_with_offset(&url, &token, &out_path, start_secs, duration_secs, out_format)
+            }).await?;
+        }
+        AudioInput::Local { path } => {
+            let (local_path, out_path) = (path.clone(), out_path.clone());
 
-    if let Err(e) = extract_result {
-        println!("   FFmpeg error for {}: {}", item_id, e);
-        return empty_result(&item_id);
+            let _permit = limits.ffmpeg.acquire().await;
+            run_blocking(move || {
+                extract_audio_segment(&local_path, &out_path, start_secs, duration_secs, out_format)
+            }).await?;
+        }
     }
 
     let audio_data = match std::fs::read(&out_path) {
         Ok(data) if data.len() > 100 => data,
         _ => {
-            println!("   Extracted audio too small for {}", item_id);
-            return empty_result(&item_id);
+            return Err(AppError::new(ErrorKind::FfmpegFailed, "No audio in the requested window")
+                .with_hint("The file may be shorter than the window or contain no audio stream"));
         }
     };
 
     // Stage: transcribing
     let (transcript, detected_language) = if use_local_whisper {
-        let _ = window.emit("audio_intro_progress", serde_json::json!({
-            "current": current, "total": total,
-            "stage": "transcribing",
-            "status": format!("Local Whisper: {}", title),
-        }));
-
-        match crate::whisper_local::transcribe_local(&out_path, whisper_model) {
-            Ok((text, lang)) => (text, lang),
-            Err(e) => {
-                println!("   Local whisper failed for {}: {}", item_id, e);
-                let _ = window.emit("audio_intro_progress", serde_json::json!({
-                    "current": current, "total": total,
-                    "stage": "error",
-                    "status": format!("Local Whisper failed: {}", e),
-                }));
-                return empty_result(&item_id);
-            }
-        }
+        let _permit = limits.local_whisper.acquire().await;
+        progress.report(ProgressEvent::intro(Stage::Transcribing, current, total, format!("Local Whisper: {}", title)));
+
+        crate::whisper_local::transcribe(out_path.clone(), whisper_model.to_string()).await?
     } else {
-        let _ = window.emit("audio_intro_progress", serde_json::json!({
-            "current": current, "total": total,
-            "stage": "transcribing",
-            "status": format!("Whisper transcribing: {}", title),
-        }));
-
-        match try_cloud_whisper(&audio_data, request.openai_api_key.as_deref()).await {
-            Ok(r) => (r.text, r.language),
-            Err(e) => {
-                println!("   Whisper API error for {}: {}", item_id, e);
-                return empty_result(&item_id);
-            }
-        }
+        let _permit = limits.cloud_whisper.acquire().await;
+        progress.report(ProgressEvent::intro(Stage::Transcribing, current, total, format!("Whisper transcribing: {}", title)));
+
+        let provider = TranscriptionProvider::from_config(request.openai_api_key.as_deref());
+        let r = try_cloud_whisper(&audio_data, &provider).await?;
+        (r.text, r.language)
     };
 
     if transcript.len() < 20 {
-        return AudioIntroResult {
-            item_id, transcript: Some(transcript), title: None, subtitle: None,
-            narrators: vec![], authors: vec![],
-            publisher: None, audio_publisher: None,
+        return Ok(AudioIntroResult {
+            transcript: Some(transcript),
             language: detected_language,
-            parse_method: "none".to_string(), confidence: 0.0,
-        };
+            ..empty_result(&item_id)
+        });
     }
 
     // Stage: parsing
-    let _ = window.emit("audio_intro_progress", serde_json::json!({
-        "current": current, "total": total,
-        "stage": "parsing",
-        "status": format!("Parsing transcript: {}", title),
-    }));
-
-    // Try LLM parsing first (more accurate), fall back to regex
-    let (extracted, method) = match try_llm_parse(&transcript, request.title.as_deref(), request).await {
-        Ok(info) => (info, "llm"),
-        Err(e) => {
-            println!("   LLM parse failed, using regex: {}", e);
-            (parse_book_info_from_transcript(&transcript), "regex")
-        }
+    progress.report(ProgressEvent::intro(Stage::Parsing, current, total, format!("Parsing transcript: {}", title)));
+
+    // Try LLM parsing first (more accurate), fall back to the regex rules
+    // for the spoken language
+    let language = detected_language.as_deref().and_then(Language::from_detected);
+    let llm_result = {
+        let _permit = limits.llm.acquire().await;
+        let language_name = language.map(Language::name).or(detected_language.as_deref());
+        try_llm_parse(&transcript, window.passage, language_name, request.title.as_deref(), request).await
+    };
+    let language = language.unwrap_or(Language::English);
+    let (mut extracted, method, llm_error) = match llm_result {
+        Ok(info) => (info, "llm", None),
+        Err(e) => (parse_book_info_from_transcript(&transcript, language), "regex", Some(e)),
     };
+    settle_production(&mut extracted, &transcript, language.rules());
 
-    let mut narrators = vec![];
-    if let Some(n) = &extracted.narrator { narrators.push(n.clone()); }
-    let mut authors = vec![];
-    if let Some(a) = &extracted.author { authors.push(a.clone()); }
+    // Map transcript spellings onto canonical names from the local authority file
+    let authority = crate::authority::AuthorityStore::load();
+    let narrators: Vec<String> = extracted.narrators.iter().map(|n| authority.canonicalize(n)).collect();
+    let authors: Vec<String> = extracted.authors.iter().map(|a| authority.canonicalize(a)).collect();
 
     let confidence = calculate_confidence(&extracted);
 
-    let result = AudioIntroResult {
+    let mut result = AudioIntroResult {
         item_id, transcript: Some(transcript),
         title: extracted.title,
         subtitle: extracted.subtitle,
@@ -365,49 +910,53 @@ async fn try_extract_at_offset(
         language: detected_language,
         parse_method: method.to_string(),
         confidence,
+        copyright_year: extracted.copyright_year,
+        production: extracted.production,
+        outro_transcript: None,
+        field_confidence: BTreeMap::new(),
+        field_status: BTreeMap::new(),
+        error: None,
     };
 
-    result
+    // The regex rules stand in for a failed LLM; when they find nothing
+    // either, the LLM failure is why
+    if let Some(e) = llm_error {
+        if fields_found(&result) == 0 {
+            result.error = Some(e);
+        } else {
+            let title = request.title.as_deref().unwrap_or(&request.item_id);
+            progress.report(ProgressEvent::intro(
+                Stage::Parsing,
+                current,
+                total,
+                format!("LLM parse failed for {}, using the regex rules: {}", title, e.message),
+            ));
+        }
+    }
+
+    Ok(result)
 }
 
 // ---- Audio streaming + extraction ----
 
 /// Build the ABS audio URL for streaming (resolves file_ino if needed)
-async fn build_abs_audio_url(request: &AudioIntroRequest) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-    let base_url = request.abs_base_url.as_deref().ok_or("No ABS base URL")?;
-    let token = request.abs_api_token.as_deref().ok_or("No ABS API token")?;
-
-    if base_url.is_empty() || token.is_empty() {
-        return Err("ABS URL or token is empty".into());
-    }
+async fn build_abs_audio_url(request: &AudioIntroRequest) -> Result<String, AppError> {
+    let client = AbsClient::new(
+        request.abs_base_url.as_deref().unwrap_or(""),
+        request.abs_api_token.as_deref().unwrap_or(""),
+    )?;
 
     // If we have file_ino, use it directly
-    if let Some(ino) = request.file_ino.as_deref() {
-        return Ok(format!("{}/api/items/{}/file/{}", base_url.trim_end_matches('/'), request.item_id, ino));
-    }
-
-    // Otherwise fetch the item to get the first audio file's ino
-    let item_url = format!("{}/api/items/{}?expanded=1", base_url.trim_end_matches('/'), request.item_id);
-    let client = reqwest::Client::builder().timeout(std::time::Duration::from_secs(30)).build()?;
-    let response = client.get(&item_url)
-        .header("Authorization", format!("Bearer {}", token))
-        .send().await?;
-
-    if !response.status().is_success() {
-        return Err(format!("ABS item fetch failed: HTTP {}", response.status()).into());
+    match request.file_ino.as_deref() {
+        Some(ino) => Ok(client.file_url(&request.item_id, ino)),
+        None => client.first_audio_file_url(&request.item_id).await,
     }
-
-    let item: serde_json::Value = response.json().await?;
-    let ino = item["media"]["audioFiles"][0]["ino"].as_str()
-        .ok_or("Could not find audio file ino from ABS item")?;
-
-    Ok(format!("{}/api/items/{}/file/{}", base_url.trim_end_matches('/'), request.item_id, ino))
 }
 
 /// Extract audio from an HTTP URL using FFmpeg streaming (no full download)
 fn extract_audio_from_url_with_offset(
     url: &str, auth_token: &str, output_path: &str, start_secs: u32, duration_secs: u32, format: &str,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+) -> Result<(), AppError> {
     let mut args = vec![
         "-y".to_string(),
         "-headers".to_string(), format!("Authorization: Bearer {}\r\n", auth_token),
@@ -428,24 +977,24 @@ fn extract_audio_from_url_with_offset(
     args.push(output_path.to_string());
 
     let output = ffmpeg_cmd()
-        .ok_or("FFmpeg not found")?
+        .ok_or_else(AppError::ffmpeg_missing)?
         .args(&args)
-        .output()?;
+        .output()
+        .map_err(|e| AppError::new(ErrorKind::FfmpegFailed, "Could not run FFmpeg").with_detail(e.to_string()))?;
 
     if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("FFmpeg stream failed: {}", stderr).into());
+        return Err(ffmpeg_error(&String::from_utf8_lossy(&output.stderr), true));
     }
-    if std::fs::metadata(output_path)?.len() == 0 {
-        return Err("FFmpeg produced empty output".into());
+    if std::fs::metadata(output_path).map(|m| m.len()).unwrap_or(0) == 0 {
+        return Err(AppError::new(ErrorKind::FfmpegFailed, "FFmpeg produced empty output"));
     }
     Ok(())
 }
 
 /// Extract first N seconds from a local file using FFmpeg
-fn extract_audio_segment(
+pub(crate) fn extract_audio_segment(
     input_path: &str, output_path: &str, start_secs: u32, duration_secs: u32, format: &str,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+) -> Result<(), AppError> {
     let mut args = vec![
         "-y".to_string(),
         "-i".to_string(), input_path.to_string(),
@@ -465,108 +1014,381 @@ fn extract_audio_segment(
     args.push(output_path.to_string());
 
     let output = ffmpeg_cmd()
-        .ok_or("FFmpeg not found")?
+        .ok_or_else(AppError::ffmpeg_missing)?
         .args(&args)
-        .output()?;
+        .output()
+        .map_err(|e| AppError::new(ErrorKind::FfmpegFailed, "Could not run FFmpeg").with_detail(e.to_string()))?;
 
     if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("FFmpeg failed: {}", stderr).into());
+        return Err(ffmpeg_error(&String::from_utf8_lossy(&output.stderr), false));
     }
-    if std::fs::metadata(output_path)?.len() == 0 {
-        return Err("FFmpeg produced empty output".into());
+    if std::fs::metadata(output_path).map(|m| m.len()).unwrap_or(0) == 0 {
+        return Err(AppError::new(ErrorKind::FfmpegFailed, "FFmpeg produced empty output"));
     }
     Ok(())
 }
 
+/// Classify FFmpeg stderr. When streaming from ABS, HTTP failures surface here.
+fn ffmpeg_error(stderr: &str, streaming: bool) -> AppError {
+    let lower = stderr.to_lowercase();
+    let err = if streaming && (lower.contains("401 unauthorized") || lower.contains("403 forbidden")) {
+        AppError::http_status("ABS", 401, "")
+    } else if streaming && lower.contains("404 not found") {
+        AppError::http_status("ABS", 404, "")
+    } else if streaming
+        && (lower.contains("connection refused") || lower.contains("failed to resolve") || lower.contains("timed out"))
+    {
+        AppError::new(ErrorKind::AbsUnreachable, "Could not reach AudiobookShelf")
+            .with_hint("Check that the ABS server is running and the URL in Settings is correct")
+    } else if !streaming && lower.contains("no such file or directory") {
+        AppError::new(ErrorKind::NotFound, "Audio file not found")
+    } else {
+        AppError::new(ErrorKind::FfmpegFailed, "FFmpeg could not read the audio")
+    };
+    err.with_detail(stderr)
+}
+
+/// Run an FFmpeg or whisper-cpp call on the blocking pool so concurrent
+/// books don't stall the async workers.
+pub(crate) async fn run_blocking<T: Send + 'static>(
+    f: impl FnOnce() -> Result<T, AppError> + Send + 'static,
+) -> Result<T, AppError> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| AppError::new(ErrorKind::Internal, "Background task failed").with_detail(e.to_string()))?
+}
+
 fn ffmpeg_cmd() -> Option<Command> {
     crate::whisper_local::find_ffmpeg_binary().map(Command::new)
 }
 
-fn check_ffmpeg_available() -> bool {
+pub(crate) fn check_ffmpeg_available() -> bool {
     crate::whisper_local::find_ffmpeg_binary().is_some()
 }
 
+// ---- Speech detection ----
+
+/// Quieter than this in the speech band counts as a pause.
+const PAUSE_NOISE_DB: i32 = -35;
+const PAUSE_MIN_SECS: f32 = 0.3;
+/// Narration pauses between phrases; sound running longer than this
+/// without one is a music bed.
+const MAX_UNBROKEN_SPEECH_MS: u64 = 12_000;
+/// Pauses longer than this end a speech region.
+const REGION_GAP_MS: u64 = 3_000;
+/// Regions shorter than this (a cough, a chime) are ignored.
+const MIN_REGION_MS: u64 = 2_000;
+/// Windows start this far before the speech they aim at.
+const SPEECH_LEAD_IN_SECS: u32 = 1;
+
+/// Pauses in the first `secs` of the input as (start, end) milliseconds,
+/// measured on the voice band so bass-heavy music doesn't mask them.
+fn find_silences(input: &AudioInput, secs: u32) -> Result<Vec<(u64, u64)>, AppError> {
+    let mut args: Vec<String> = vec!["-hide_banner".into(), "-nostats".into()];
+    let streaming = match input {
+        AudioInput::Abs { url, token } => {
+            args.extend(["-headers".into(), format!("Authorization: Bearer {}\r\n", token), "-i".into(), url.clone()]);
+            true
+        }
+        AudioInput::Local { path } => {
+            args.extend(["-i".into(), path.clone()]);
+            false
+        }
+    };
+    args.extend([
+        "-t".into(), secs.to_string(),
+        "-vn".into(),
+        "-af".into(), format!("highpass=f=200,lowpass=f=3500,silencedetect=noise={}dB:d={}", PAUSE_NOISE_DB, PAUSE_MIN_SECS),
+        "-f".into(), "null".into(), "-".into(),
+    ]);
+
+    let output = ffmpeg_cmd()
+        .ok_or_else(AppError::ffmpeg_missing)?
+        .args(&args)
+        .output()
+        .map_err(|e| AppError::new(ErrorKind::FfmpegFailed, "Could not run FFmpeg").with_detail(e.to_string()))?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(ffmpeg_error(&stderr, streaming));
+    }
+    Ok(parse_silences(&stderr, u64::from(secs) * 1000))
+}
+
+/// `silence_start: 1.2` / `silence_end: 3.4 | ...` pairs from silencedetect.
+/// A silence still open at the end runs to `span_ms`.
+fn parse_silences(stderr: &str, span_ms: u64) -> Vec<(u64, u64)> {
+    let value = |rest: &str| rest.split('|').next()?.trim().parse::<f64>().ok().map(|s| (s.max(0.0) * 1000.0).round() as u64);
+    let mut silences = Vec::new();
+    let mut open = None;
+    for line in stderr.lines() {
+        if let Some((_, rest)) = line.split_once("silence_start:") {
+            open = value(rest);
+        } else if let Some((_, rest)) = line.split_once("silence_end:") {
+            if let Some(end) = value(rest) {
+                silences.push((open.take().unwrap_or(0), end));
+            }
+        }
+    }
+    if let Some(start) = open {
+        silences.push((start, span_ms));
+    }
+    silences
+}
+
+/// Stretches of narration in the first `span_ms`: the sound between pauses,
+/// minus anything too long and unbroken to be speech, joined across short
+/// pauses.
+fn speech_regions(silences: &[(u64, u64)], span_ms: u64) -> Vec<(u64, u64)> {
+    let mut sounds = Vec::new();
+    let mut cursor = 0;
+    for &(start, end) in silences {
+        if start > cursor {
+            sounds.push((cursor, start.min(span_ms)));
+        }
+        cursor = cursor.max(end);
+    }
+    if cursor < span_ms {
+        sounds.push((cursor, span_ms));
+    }
+
+    let mut regions: Vec<(u64, u64)> = Vec::new();
+    for (start, end) in sounds.into_iter().filter(|(s, e)| e - s <= MAX_UNBROKEN_SPEECH_MS) {
+        match regions.last_mut() {
+            Some(last) if start - last.1 <= REGION_GAP_MS => last.1 = end,
+            _ => regions.push((start, end)),
+        }
+    }
+    regions.retain(|(s, e)| e - s >= MIN_REGION_MS);
+    regions
+}
+
+/// Transcription windows covering the speech regions in order, each
+/// starting just before speech and reaching at most the scanned span.
+fn speech_windows(regions: &[(u64, u64)]) -> Vec<(u32, u32, String)> {
+    let mut windows = Vec::new();
+    let mut covered_ms = 0;
+    for &(start_ms, _) in regions {
+        if start_ms < covered_ms {
+            continue;
+        }
+        let start = ((start_ms / 1000) as u32).saturating_sub(SPEECH_LEAD_IN_SECS);
+        let duration = SPEECH_WINDOW_SECS.min(INTRO_SCANNED_SECS.saturating_sub(start));
+        if duration == 0 {
+            break;
+        }
+        windows.push((start, duration, format!("speech at {}:{:02}", start / 60, start % 60)));
+        covered_ms = u64::from(start + duration) * 1000;
+        if windows.len() == MAX_SPEECH_WINDOWS {
+            break;
+        }
+    }
+    windows
+}
+
 // ---- Whisper API ----
 
 /// Whisper result with transcript and detected language
+#[derive(Debug)]
 struct WhisperResult {
     text: String,
     language: Option<String>,
+    /// Timed segments, relative to the start of the clip.
+    segments: Vec<Segment>,
 }
 
-/// Try cloud Whisper (OpenAI API). Returns error if no API key.
+const OPENAI_TRANSCRIPTION_URL: &str = "https://api.openai.com/v1";
+const OPENAI_TRANSCRIPTION_MODEL: &str = "whisper-1";
+
+/// Where cloud transcription goes: OpenAI, or any server with an
+/// OpenAI-compatible `/audio/transcriptions` endpoint (faster-whisper,
+/// whisper.cpp, ...).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptionProvider {
+    /// Up to and including the API version, e.g. `https://api.openai.com/v1`.
+    pub base_url: String,
+    pub model: String,
+    pub api_key: Option<String>,
+    pub response_format: ResponseFormat,
+}
+
+/// Only `verbose_json` carries timestamps and the detected language.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResponseFormat {
+    #[default]
+    VerboseJson,
+    Json,
+    Text,
+}
+
+impl ResponseFormat {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "verbose_json" => Some(ResponseFormat::VerboseJson),
+            "json" => Some(ResponseFormat::Json),
+            "text" => Some(ResponseFormat::Text),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            ResponseFormat::VerboseJson => "verbose_json",
+            ResponseFormat::Json => "json",
+            ResponseFormat::Text => "text",
+        }
+    }
+}
+
+impl TranscriptionProvider {
+    /// The provider in the saved config. `openai_key` is the caller's OpenAI
+    /// key, sent to OpenAI only; other servers get the transcription key.
+    pub fn from_config(openai_key: Option<&str>) -> Self {
+        let config = AppConfig::load_or_default();
+        let base_url = config
+            .transcription_base_url
+            .as_deref()
+            .map(|url| url.trim().trim_end_matches('/'))
+            .filter(|url| !url.is_empty())
+            .unwrap_or(OPENAI_TRANSCRIPTION_URL)
+            .to_string();
+        let transcription_key = get_secret(Secret::TranscriptionApiKey);
+        let api_key = if base_url == OPENAI_TRANSCRIPTION_URL {
+            openai_key.filter(|k| !k.is_empty()).map(str::to_string).or(transcription_key)
+        } else {
+            transcription_key
+        };
+        TranscriptionProvider {
+            base_url,
+            model: config
+                .transcription_model
+                .filter(|m| !m.trim().is_empty())
+                .unwrap_or_else(|| OPENAI_TRANSCRIPTION_MODEL.to_string()),
+            api_key,
+            response_format: config
+                .transcription_response_format
+                .as_deref()
+                .and_then(ResponseFormat::parse)
+                .unwrap_or_default(),
+        }
+    }
+
+    fn is_openai(&self) -> bool {
+        self.base_url == OPENAI_TRANSCRIPTION_URL
+    }
+
+    /// Names the model in cache keys and resumable transcripts.
+    pub fn label(&self) -> String {
+        if self.is_openai() {
+            format!("openai/{}", self.model)
+        } else {
+            format!("{}/{}", self.base_url, self.model)
+        }
+    }
+
+    fn service(&self) -> &'static str {
+        if self.is_openai() { "OpenAI" } else { "Transcription server" }
+    }
+}
+
+/// Transcribe with the cloud provider. OpenAI needs an API key; other
+/// servers are called without one if none is set.
 async fn try_cloud_whisper(
-    audio_data: &[u8], api_key: Option<&str>,
-) -> Result<WhisperResult, Box<dyn std::error::Error + Send + Sync>> {
-    let key = api_key.filter(|k| !k.is_empty())
-        .ok_or("No OpenAI API key for cloud Whisper")?;
-    call_whisper_api(audio_data.to_vec(), key).await
+    audio_data: &[u8], provider: &TranscriptionProvider,
+) -> Result<WhisperResult, AppError> {
+    if provider.is_openai() && provider.api_key.as_deref().is_none_or(str::is_empty) {
+        return Err(AppError::new(ErrorKind::MissingApiKey, "No OpenAI API key for cloud Whisper")
+            .with_hint("Add an OpenAI API key in Settings, or switch to local Whisper"));
+    }
+    call_whisper_api(audio_data.to_vec(), provider).await
+}
+
+/// Cloud Whisper with timestamps: segments relative to the start of the
+/// clip and the detected language. Providers answering in `json` or `text`
+/// return the whole clip as one segment.
+pub(crate) async fn transcribe_cloud_segments(
+    audio_data: &[u8], provider: &TranscriptionProvider,
+) -> Result<(Vec<Segment>, Option<String>), AppError> {
+    let r = try_cloud_whisper(audio_data, provider).await?;
+    Ok((r.segments, r.language))
 }
 
 async fn call_whisper_api(
-    audio_data: Vec<u8>, api_key: &str,
-) -> Result<WhisperResult, Box<dyn std::error::Error + Send + Sync>> {
+    audio_data: Vec<u8>, provider: &TranscriptionProvider,
+) -> Result<WhisperResult, AppError> {
     let client = reqwest::Client::new();
 
     let part = multipart::Part::bytes(audio_data)
-        .file_name("audio.mp3").mime_str("audio/mpeg")?;
+        .file_name("audio.mp3").mime_str("audio/mpeg")
+        .map_err(|e| AppError::from(e.to_string()))?;
 
-    // Use verbose_json format to get language detection
-    // Don't set language param - let Whisper auto-detect
+    // verbose_json also reports the language; don't set the language
+    // param, let Whisper auto-detect
     let form = multipart::Form::new()
-        .text("model", "whisper-1")
-        .text("response_format", "verbose_json")
+        .text("model", provider.model.clone())
+        .text("response_format", provider.response_format.as_str())
         .part("file", part);
 
-    let response = client
-        .post("https://api.openai.com/v1/audio/transcriptions")
-        .header("Authorization", format!("Bearer {}", api_key))
-        .multipart(form)
-        .send().await?;
+    let mut request = client
+        .post(format!("{}/audio/transcriptions", provider.base_url))
+        .multipart(form);
+    if let Some(key) = provider.api_key.as_deref().filter(|k| !k.is_empty()) {
+        request = request.header("Authorization", format!("Bearer {}", key));
+    }
+    let response = request.send().await
+        .map_err(|e| AppError::request_failed(provider.service(), &e))?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
-        return Err(format!("Whisper API error {}: {}", status, body).into());
+        return Err(AppError::http_status(provider.service(), status.as_u16(), &body));
     }
 
-    let data: serde_json::Value = response.json().await?;
-    let text = data["text"].as_str().unwrap_or("").trim().to_string();
-    let language = data["language"].as_str().map(|s| s.to_string());
-
-    Ok(WhisperResult { text, language })
-}
-
-// ---- Transcript cache ----
-
-fn transcript_cache_dir() -> Result<PathBuf, String> {
-    let dir = dirs::data_dir()
-        .ok_or("Cannot find data directory")?
-        .join("Audiobook Tagger")
-        .join("transcript_cache");
-    std::fs::create_dir_all(&dir).map_err(|e| format!("Cache dir error: {}", e))?;
-    Ok(dir)
+    let body = response.text().await
+        .map_err(|e| AppError::request_failed(provider.service(), &e))?;
+    parse_transcription(&body, provider.response_format)
 }
 
-fn get_cached_transcript(item_id: &str) -> Option<AudioIntroResult> {
-    let cache_dir = transcript_cache_dir().ok()?;
-    let cache_file = cache_dir.join(format!("{}.json", sanitize_filename(item_id)));
-    let data = std::fs::read_to_string(cache_file).ok()?;
-    serde_json::from_str(&data).ok()
+fn parse_transcription(body: &str, format: ResponseFormat) -> Result<WhisperResult, AppError> {
+    if format == ResponseFormat::Text {
+        let text = body.trim().to_string();
+        let segments = whole_clip(&text);
+        return Ok(WhisperResult { text, language: None, segments });
+    }
+    let data: serde_json::Value = serde_json::from_str(body)
+        .map_err(|e| AppError::new(ErrorKind::Parse, "Unexpected response from the transcription API").with_detail(e.to_string()))?;
+    let text = data["text"].as_str().unwrap_or("").trim().to_string();
+    let language = data["language"].as_str().map(|s| s.to_string());
+    let segments = match parse_verbose_segments(&data) {
+        segments if segments.is_empty() => whole_clip(&text),
+        segments => segments,
+    };
+    Ok(WhisperResult { text, language, segments })
 }
 
-fn cache_transcript(result: &AudioIntroResult) {
-    if let Ok(cache_dir) = transcript_cache_dir() {
-        let cache_file = cache_dir.join(format!("{}.json", sanitize_filename(&result.item_id)));
-        if let Ok(json) = serde_json::to_string_pretty(result) {
-            let _ = std::fs::write(cache_file, json);
-        }
+/// Untimed text as one segment at the clip's start.
+fn whole_clip(text: &str) -> Vec<Segment> {
+    if text.is_empty() {
+        return Vec::new();
     }
+    vec![Segment { start_ms: 0, end_ms: 0, text: text.to_string() }]
 }
 
-fn sanitize_filename(s: &str) -> String {
-    s.chars().map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect()
+/// `segments` of a verbose_json response, seconds converted to milliseconds.
+pub(crate) fn parse_verbose_segments(data: &serde_json::Value) -> Vec<Segment> {
+    let ms = |v: &serde_json::Value| (v.as_f64().unwrap_or(0.0).max(0.0) * 1000.0).round() as u64;
+    data["segments"]
+        .as_array()
+        .map(|items| {
+            items.iter()
+                .map(|item| Segment {
+                    start_ms: ms(&item["start"]),
+                    end_ms: ms(&item["end"]),
+                    text: item["text"].as_str().unwrap_or("").trim().to_string(),
+                })
+                .filter(|s| !s.text.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 // ---- Temp file cleanup ----
@@ -593,27 +1415,47 @@ fn cleanup_temp_files() {
 /// Send transcript to LLM (GPT or Ollama) for structured extraction.
 async fn try_llm_parse(
     transcript: &str,
+    passage: Passage,
+    language: Option<&str>,
     known_title: Option<&str>,
     request: &AudioIntroRequest,
-) -> Result<ExtractedBookInfo, Box<dyn std::error::Error + Send + Sync>> {
+) -> Result<ExtractedBookInfo, AppError> {
     let system_prompt = "You extract audiobook metadata from transcripts. Return only valid JSON.";
 
+    let (passage_name, passage_rules) = match passage {
+        Passage::Intro => ("audio intro", ""),
+        Passage::Outro => (
+            "closing credits",
+            "- Closing credits often advertise other audiobooks; only extract the book that just ended (e.g. \"You have been listening to ...\")\n",
+        ),
+    };
+    let language_rules = match language {
+        Some(language) => format!(
+            "- The transcript is in {}: recognise its credit phrases (e.g. \"gelesen von\", \"lu par\", \"narrado por\"), \
+             and keep titles and publishers in that language; do not translate them\n",
+            language
+        ),
+        None => String::new(),
+    };
     let user_prompt = format!(
-        r#"Extract metadata from this audiobook audio intro transcript.
+        r#"Extract metadata from this audiobook {} transcript.
 
 Return ONLY a JSON object:
 {{
   "title": "book title or null",
   "subtitle": "subtitle or null",
-  "author": "author full name or null",
-  "narrator": "narrator/reader full name or null",
+  "authors": ["each author's full name"],
+  "narrators": ["each narrator's/reader's full name"],
+  "production": "single-voice, dual-narrator, full-cast, dramatized or null",
   "publisher": "print publisher or null",
-  "audio_publisher": "audiobook publisher or null"
+  "audio_publisher": "audiobook publisher or null",
+  "copyright_year": "four-digit copyright or production year or null"
 }}
 
 RULES:
 - Extract the book title and author even if mentioned indirectly (e.g. "Larry McMurtry's transformative novel" means author is "Larry McMurtry")
-- Extract narrator if announced (e.g. "read by Name", "narrated by Name")
+- Extract narrator if announced (e.g. "read by Name", "narrated by Name"); list every narrator (e.g. "read by Kate Reading and Michael Kramer" is two narrators)
+- "Full cast" or "a full-cast production" sets production to "full-cast", and a dramatization or audio drama sets "dramatized"; neither is a narrator name
 - "This is Audible" means audio_publisher is "Audible", NOT the title
 - "Lonesome Dove" mentioned as a novel title means title is "Lonesome Dove"
 - Fix phonetic misspellings from speech-to-text (e.g. "Condeed" -> "Candide")
@@ -622,20 +1464,83 @@ RULES:
 - Separate title from subtitle
 - Do NOT invent information not present in the transcript at all
 - Person names only for author/narrator fields
-
+{}{}
 Transcript: "{}""#,
-        &transcript[..transcript.len().min(800)]
+        passage_name,
+        passage_rules,
+        language_rules,
+        excerpt(transcript, passage, 800)
     );
 
+    let parsed = ask_llm(system_prompt, &user_prompt, &LlmSettings::from(request)).await?;
+
+    let parse_field = |key: &str| -> Option<String> {
+        parsed[key].as_str().filter(|s| !s.is_empty() && *s != "null").map(|s| s.to_string())
+    };
+
+    Ok(ExtractedBookInfo {
+        title: parse_field("title"),
+        subtitle: parse_field("subtitle"),
+        authors: parse_people(&parsed, "authors", "author"),
+        narrators: parse_people(&parsed, "narrators", "narrator"),
+        publisher: parse_field("publisher"),
+        audio_publisher: parse_field("audio_publisher"),
+        copyright_year: parse_field("copyright_year").filter(|y| is_plausible_year(y)),
+        production: parse_field("production").as_deref().and_then(ProductionType::parse),
+    })
+}
+
+/// Names from a list field, or from a single-name field when a model
+/// answers in the older shape.
+fn parse_people(parsed: &serde_json::Value, list_key: &str, single_key: &str) -> Vec<String> {
+    let names: Vec<&str> = match (&parsed[list_key], &parsed[single_key]) {
+        (serde_json::Value::Array(items), _) => items.iter().filter_map(|v| v.as_str()).collect(),
+        (serde_json::Value::String(name), _) | (_, serde_json::Value::String(name)) => vec![name.as_str()],
+        _ => Vec::new(),
+    };
+    let mut people: Vec<String> = Vec::new();
+    for name in names.into_iter().map(str::trim).filter(|n| !n.is_empty() && *n != "null") {
+        if !people.iter().any(|p| p.eq_ignore_ascii_case(name)) {
+            people.push(name.to_string());
+        }
+    }
+    people
+}
+
+/// Which model answers a prompt: Ollama when `use_local_ai`, OpenAI otherwise.
+pub(crate) struct LlmSettings<'a> {
+    pub use_local_ai: bool,
+    pub ollama_model: Option<&'a str>,
+    pub ollama_base_url: Option<&'a str>,
+    pub openai_api_key: Option<&'a str>,
+}
+
+impl<'a> From<&'a AudioIntroRequest> for LlmSettings<'a> {
+    fn from(request: &'a AudioIntroRequest) -> Self {
+        LlmSettings {
+            use_local_ai: request.use_local_ai.unwrap_or(false),
+            ollama_model: request.ollama_model.as_deref(),
+            ollama_base_url: request.ollama_base_url.as_deref(),
+            openai_api_key: request.openai_api_key.as_deref(),
+        }
+    }
+}
+
+/// Send one prompt and parse the reply as a JSON object.
+pub(crate) async fn ask_llm(
+    system_prompt: &str,
+    user_prompt: &str,
+    settings: &LlmSettings<'_>,
+) -> Result<serde_json::Value, AppError> {
+    let service = if settings.use_local_ai { "Ollama" } else { "OpenAI" };
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(30))
-        .build()?;
-
-    let use_local = request.use_local_ai.unwrap_or(false);
+        .build()
+        .map_err(|e| AppError::request_failed(service, &e))?;
 
-    let content = if use_local {
+    let content = if settings.use_local_ai {
         // Ollama: OpenAI-compatible chat completions
-        let model = request.ollama_model.as_deref().unwrap_or("qwen3:4b");
+        let model = settings.ollama_model.unwrap_or("qwen3:4b");
         let body = serde_json::json!({
             "model": model,
             "messages": [
@@ -648,24 +1553,27 @@ Transcript: "{}""#,
         });
 
         let response = client
-            .post(format!("{}/v1/chat/completions", crate::ollama::effective_base(request.ollama_base_url.as_deref().unwrap_or(""))))
+            .post(format!("{}/v1/chat/completions", crate::ollama::effective_base(settings.ollama_base_url.unwrap_or(""))))
             .json(&body)
-            .send().await?;
+            .send().await
+            .map_err(|e| if e.is_connect() { AppError::ollama_not_running() } else { AppError::request_failed(service, &e) })?;
 
         if !response.status().is_success() {
-            let err = response.text().await.unwrap_or_default();
-            return Err(format!("Ollama error: {}", err).into());
+            let status = response.status().as_u16();
+            return Err(AppError::http_status(service, status, &response.text().await.unwrap_or_default()));
         }
 
-        let resp: serde_json::Value = response.json().await?;
+        let resp: serde_json::Value = response.json().await.map_err(|e| AppError::request_failed(service, &e))?;
         resp["choices"][0]["message"]["content"]
             .as_str()
-            .ok_or("No content in Ollama response")?
+            .ok_or_else(|| AppError::new(ErrorKind::ApiError, "No content in Ollama response"))?
             .to_string()
     } else {
         // OpenAI Responses API (gpt-5-nano)
-        let api_key = request.openai_api_key.as_deref()
-            .ok_or("No OpenAI API key")?;
+        let api_key = settings.openai_api_key.ok_or_else(|| {
+            AppError::new(ErrorKind::MissingApiKey, "No OpenAI API key for transcript parsing")
+                .with_hint("Add an OpenAI API key in Settings, or use local AI")
+        })?;
 
         let body = serde_json::json!({
             "model": "gpt-5-nano",
@@ -682,15 +1590,15 @@ Transcript: "{}""#,
             .post("https://api.openai.com/v1/responses")
             .header("Authorization", format!("Bearer {}", api_key))
             .json(&body)
-            .send().await?;
+            .send().await
+            .map_err(|e| AppError::request_failed(service, &e))?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let err_body = response.text().await.unwrap_or_default();
-            return Err(format!("LLM API error {}: {}", status, err_body).into());
+            let status = response.status().as_u16();
+            return Err(AppError::http_status(service, status, &response.text().await.unwrap_or_default()));
         }
 
-        let resp: serde_json::Value = response.json().await?;
+        let resp: serde_json::Value = response.json().await.map_err(|e| AppError::request_failed(service, &e))?;
         resp["output_text"].as_str()
             .or_else(|| {
                 resp["output"].as_array()?.iter()
@@ -699,7 +1607,7 @@ Transcript: "{}""#,
                     .find(|c| c["type"] == "output_text" || c["type"] == "text")?
                     ["text"].as_str()
             })
-            .ok_or("No text in LLM response")?
+            .ok_or_else(|| AppError::new(ErrorKind::ApiError, "No text in OpenAI response"))?
             .to_string()
     };
 
@@ -707,107 +1615,312 @@ Transcript: "{}""#,
         .trim_start_matches("```json").trim_start_matches("```")
         .trim_end_matches("```").trim();
 
-    let parsed: serde_json::Value = serde_json::from_str(json_str)?;
-
-    let parse_field = |key: &str| -> Option<String> {
-        parsed[key].as_str().filter(|s| !s.is_empty() && *s != "null").map(|s| s.to_string())
-    };
-
-    Ok(ExtractedBookInfo {
-        title: parse_field("title"),
-        subtitle: parse_field("subtitle"),
-        author: parse_field("author"),
-        narrator: parse_field("narrator"),
-        publisher: parse_field("publisher"),
-        audio_publisher: parse_field("audio_publisher"),
+    serde_json::from_str(json_str).map_err(|e| {
+        AppError::new(ErrorKind::Parse, format!("{} did not answer with JSON", service)).with_detail(e.to_string())
     })
 }
 
+/// At most `max` bytes of the transcript: the start of an intro, or the end
+/// of an outro where the credits are read.
+fn excerpt(transcript: &str, passage: Passage, max: usize) -> &str {
+    if transcript.len() <= max {
+        return transcript;
+    }
+    match passage {
+        Passage::Intro => {
+            let end = (0..=max).rev().find(|&i| transcript.is_char_boundary(i)).unwrap_or(0);
+            &transcript[..end]
+        }
+        Passage::Outro => {
+            let start = (transcript.len() - max..transcript.len()).find(|&i| transcript.is_char_boundary(i)).unwrap_or(0);
+            &transcript[start..]
+        }
+    }
+}
+
 // ---- Regex parsing ----
 
-fn parse_book_info_from_transcript(transcript: &str) -> ExtractedBookInfo {
-    let mut info = ExtractedBookInfo::default();
-    let text = transcript.replace('\n', " ").replace('\r', " ");
-    let text = text.trim();
+/// Languages with their own intro phrasing. Transcripts in any other
+/// language are parsed with the English rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Language {
+    English,
+    German,
+    French,
+    Spanish,
+}
 
-    lazy_static::lazy_static! {
-        // Name pattern: 1-5 capitalized words, handles initials (J.K., J.R.R., Dr.)
-        // More restrictive than before to avoid grabbing book text
-        static ref NAME_PATTERN: &'static str = r"(?:[A-Z][a-zA-Z.]{0,15}\.?\s+){0,4}[A-Z][a-zA-Z.]+";
+impl Language {
+    /// From the language Whisper detected, as a code (`de`) or a name (`german`).
+    fn from_detected(detected: &str) -> Option<Language> {
+        match detected.trim().to_lowercase().as_str() {
+            "en" | "english" => Some(Language::English),
+            "de" | "german" | "deutsch" => Some(Language::German),
+            "fr" | "french" | "français" | "francais" => Some(Language::French),
+            "es" | "spanish" | "español" | "espanol" => Some(Language::Spanish),
+            _ => None,
+        }
+    }
 
-        static ref TITLE_BY_AUTHOR_NARRATOR: Regex = Regex::new(
-            &format!(r"(?i)^(.+?)\s+by\s+({})\s*[,.]?\s+(?:read|narrated|performed)\s+by\s+({})", *NAME_PATTERN, *NAME_PATTERN)
-        ).unwrap();
+    fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "German",
+            Language::French => "French",
+            Language::Spanish => "Spanish",
+        }
+    }
 
-        static ref THIS_IS_BY: Regex = Regex::new(
-            &format!(r"(?i)(?:this is|welcome to|you are listening to)\s+(.+?)\s+by\s+({})", *NAME_PATTERN)
-        ).unwrap();
+    fn rules(self) -> &'static IntroRules {
+        match self {
+            Language::English => &ENGLISH_RULES,
+            Language::German => &GERMAN_RULES,
+            Language::French => &FRENCH_RULES,
+            Language::Spanish => &SPANISH_RULES,
+        }
+    }
+}
 
-        static ref WRITTEN_BY: Regex = Regex::new(
-            &format!(r"(?i)^(.+?),?\s+written\s+by\s+({})", *NAME_PATTERN)
-        ).unwrap();
+/// How a language announces an audiobook, as regex fragments. The `*_by`
+/// fragments include their trailing whitespace.
+struct IntroPhrases {
+    /// A person's name: 1-5 words, handles initials (J.K., J.R.R., Dr.)
+    name: &'static str,
+    /// Separator before "by": French and Spanish "de" also joins words
+    /// inside titles, so there it needs a comma.
+    by_separator: &'static str,
+    by: &'static str,
+    written_by: &'static str,
+    narrated_by: &'static str,
+    published_by: &'static str,
+    /// Opening phrases ("This is", "Sie hören") before the title.
+    lead_in: &'static str,
+    /// "A Macmillan Audio production": captures the producer.
+    production: &'static str,
+    /// Joins the last two names of a list.
+    and: &'static str,
+    full_cast: &'static str,
+    dramatized: &'static str,
+    /// Lowercase words that end a captured name ("gelesen", "chapitre").
+    /// Name particles ("von", "de") must not be listed.
+    stop_words: &'static [&'static str],
+}
 
-        static ref SIMPLE_BY: Regex = Regex::new(
-            &format!(r"(?i)^(.+?)\s+by\s+({})", *NAME_PATTERN)
-        ).unwrap();
+const ENGLISH: IntroPhrases = IntroPhrases {
+    // More restrictive than the other languages to avoid grabbing book text
+    name: r"(?:[A-Z][a-zA-Z.]{0,15}\.?\s+){0,4}[A-Z][a-zA-Z.]+",
+    by_separator: r",?\s+",
+    by: r"by\s+",
+    written_by: r"written\s+by\s+",
+    narrated_by: r"(?:read|narrated|performed)\s+by\s+",
+    published_by: r"(?:published|produced)\s+by\s+",
+    lead_in: r"this is|welcome to|you are listening to|you(?:'ve| have) been listening to",
+    production: r"\ba\s+([^.,]+?)\s+(?:production|recording|audiobook)",
+    and: "and",
+    full_cast: r"\bfull[\s-]cast\b|\bmulti[\s-]?cast\b|\bensemble cast\b",
+    dramatized: r"\bdramati[sz](?:ed|ation)\b|\b(?:radio|audio) (?:drama|play)\b",
+    stop_words: &[
+        "this", "the", "a", "an", "and", "but", "or", "for", "in", "on", "at",
+        "to", "from", "with", "is", "was", "are", "were", "be", "been", "have",
+        "has", "had", "do", "does", "did", "will", "would", "could", "should",
+        "may", "might", "shall", "can", "it", "its", "that", "which", "who",
+        "whom", "whose", "where", "when", "how", "what", "why", "not", "no",
+        "so", "if", "then", "than", "as", "of", "by", "read", "narrated",
+        "performed", "written", "published", "produced", "chapter", "part",
+        "book", "volume", "copyright", "all", "rights", "reserved",
+    ],
+};
+
+const GERMAN: IntroPhrases = IntroPhrases {
+    name: UNICODE_NAME,
+    by_separator: r",?\s+",
+    by: r"von\s+",
+    written_by: r"geschrieben\s+von\s+",
+    narrated_by: r"(?:(?:gelesen|gesprochen|vorgelesen|erzählt)\s+von|es\s+liest|sprecher(?:in)?:?)\s+",
+    published_by: r"(?:veröffentlicht|verlegt|produziert)\s+(?:von|bei)\s+",
+    lead_in: r"sie hören|sie hörten|hier ist|willkommen (?:zu|bei)",
+    production: r"\b(?:eine\s+)?(?:produktion|hörbuchproduktion)\s+(?:von|des|der)\s+([^.,]+)",
+    and: "und",
+    full_cast: r"\bverteilten\s+rollen\b|\bgroßer\s+besetzung\b|\bensemble\b",
+    dramatized: r"\bhörspiel",
+    stop_words: &[
+        "und", "oder", "aber", "ist", "war", "sind", "sie", "es", "das", "die",
+        "ein", "eine", "einer", "mit", "für", "im", "am", "zu", "bei", "aus",
+        "nach", "hören", "hörten", "gelesen", "gesprochen", "vorgelesen",
+        "erzählt", "liest", "sprecher", "sprecherin", "geschrieben",
+        "veröffentlicht", "verlegt", "produziert", "kapitel", "teil", "buch",
+        "band", "folge", "prolog", "copyright", "alle", "rechte", "vorbehalten",
+    ],
+};
+
+const FRENCH: IntroPhrases = IntroPhrases {
+    name: UNICODE_NAME,
+    by_separator: r",\s+",
+    by: r"(?:par\s+|de\s+|d')",
+    written_by: r"écrit\s+par\s+",
+    narrated_by: r"(?:(?:lu|raconté|interprété|narré)e?\s+par|(?:avec\s+)?la\s+voix\s+de)\s+",
+    published_by: r"(?:publié|édité|produit)e?\s+par\s+",
+    lead_in: r"vous écoutez|vous avez écouté|voici|bienvenue (?:dans|à)",
+    production: r"\bune\s+production\s+(?:de\s+|d')([^.,]+)",
+    and: "et",
+    full_cast: r"\bdistribution\s+complète\b|\bplusieurs\s+voix\b",
+    dramatized: r"\bfiction\s+(?:radiophonique|sonore|audio)\b|\bdramatisation\b|\badaptation\s+radiophonique\b",
+    stop_words: &[
+        "et", "ou", "mais", "est", "était", "sont", "ce", "cette", "il", "elle",
+        "un", "une", "les", "des", "au", "aux", "avec", "pour", "dans", "sur",
+        "par", "vous", "écoutez", "lu", "lue", "raconté", "interprété", "narré",
+        "voix", "écrit", "publié", "édité", "produit", "chapitre", "partie",
+        "livre", "tome", "prologue", "copyright", "tous", "droits", "réservés",
+    ],
+};
+
+const SPANISH: IntroPhrases = IntroPhrases {
+    name: UNICODE_NAME,
+    by_separator: r",\s+",
+    by: r"(?:por|de)\s+",
+    written_by: r"escrit[oa]\s+por\s+",
+    narrated_by: r"(?:narrad[oa]|leíd[oa]|interpretad[oa]|contad[oa])\s+por\s+",
+    published_by: r"(?:publicad[oa]|editad[oa]|producid[oa])\s+por\s+",
+    lead_in: r"estás escuchando|está escuchando|has escuchado|ha escuchado|esto es|bienvenid[oa]s? a",
+    production: r"\buna\s+producción\s+de\s+([^.,]+)",
+    and: "y",
+    full_cast: r"\breparto\s+(?:completo|coral)\b|\bvarias\s+voces\b",
+    dramatized: r"\bdramatizad[oa]\b|\bdramatización\b|\bradioteatro\b|\baudiodrama\b|\bficción\s+sonora\b",
+    stop_words: &[
+        "y", "o", "pero", "es", "era", "son", "este", "esta", "esto", "un",
+        "una", "los", "las", "con", "para", "en", "por", "estás", "escuchando",
+        "narrado", "narrada", "leído", "leída", "interpretado", "interpretada",
+        "contado", "contada", "escrito", "escrita", "publicado", "editado",
+        "producido", "capítulo", "parte", "libro", "tomo", "volumen", "prólogo",
+        "copyright", "todos", "derechos", "reservados",
+    ],
+};
+
+/// Names with accented letters, hyphens and apostrophes (Saint-Exupéry, D'Arcy).
+/// `\p{L}` already covers both cases; case folding it only slows compiling.
+const UNICODE_NAME: &str = r"(?-i:(?:\p{L}[\p{L}.'-]*\s+){0,4}\p{L}[\p{L}.'-]+)";
+
+/// One language's intro patterns, compiled.
+struct IntroRules {
+    title_by_author_narrator: Regex,
+    lead_in: Regex,
+    this_is_by: Regex,
+    written_by: Regex,
+    simple_by: Regex,
+    narrator: Regex,
+    published_by: Regex,
+    production: Regex,
+    /// Splits a captured list of names.
+    list_separator: Regex,
+    full_cast: Regex,
+    dramatized: Regex,
+    stop_words: &'static [&'static str],
+}
 
-        static ref NARRATOR_PATTERN: Regex = Regex::new(
-            &format!(r"(?i)(?:narrated|read|performed)\s+by\s+({})", *NAME_PATTERN)
-        ).unwrap();
+impl IntroRules {
+    fn new(p: &'static IntroPhrases) -> IntroRules {
+        let author_by = format!("(?:{}|{})", p.written_by, p.by);
+        // "A", "A and B" or "A, B and C"; commas alone don't continue a list
+        let names = format!(r"{n}(?:(?:\s*,\s*{n})*,?\s+(?:{and}|&)\s+{n})?", n = p.name, and = p.and);
+        let compile = |pattern: String| Regex::new(&format!("(?i){}", pattern)).unwrap();
+        IntroRules {
+            title_by_author_narrator: compile(format!(
+                r"^(.+?){}{}({})\s*[,.]?\s+{}({})",
+                p.by_separator, author_by, names, p.narrated_by, names
+            )),
+            lead_in: compile(format!(r"^(?:{})\s+", p.lead_in)),
+            this_is_by: compile(format!(r"(?:{})\s+(.+?){}{}({})", p.lead_in, p.by_separator, author_by, names)),
+            written_by: compile(format!(r"^(.+?),?\s+{}({})", p.written_by, names)),
+            simple_by: compile(format!(r"^(.+?){}{}({})", p.by_separator, p.by, names)),
+            narrator: compile(format!(r"{}({})", p.narrated_by, names)),
+            published_by: compile(format!(r"{}(.+?)(?:\.|,|$)", p.published_by)),
+            production: compile(p.production.to_string()),
+            list_separator: compile(format!(r"\s*,\s*(?:(?:{and})\s+)?|\s+(?:{and})\s+|\s*&\s*", and = p.and)),
+            full_cast: compile(p.full_cast.to_string()),
+            dramatized: compile(p.dramatized.to_string()),
+            stop_words: p.stop_words,
+        }
+    }
 
-        static ref PUBLISHED_BY: Regex = Regex::new(
-            r"(?i)(?:published|produced)\s+by\s+(.+?)(?:\.|,|$)"
-        ).unwrap();
+    /// Each person in a captured list, cleaned, without cast markers
+    /// ("read by a full cast") or repeats.
+    fn people(&self, captured: &str) -> Vec<String> {
+        let mut people: Vec<String> = Vec::new();
+        for name in self.list_separator.split(captured).map(|n| clean_person_name(n, self.stop_words)) {
+            if !name.is_empty() && !self.is_cast_marker(&name) && !people.contains(&name) {
+                people.push(name);
+            }
+        }
+        people
+    }
 
-        static ref PUBLISHER_PRODUCTION: Regex = Regex::new(
-            r"(?i)a\s+(.+?)\s+(?:production|recording|audiobook)"
-        ).unwrap();
+    fn is_cast_marker(&self, name: &str) -> bool {
+        self.full_cast.is_match(name) || self.dramatized.is_match(name)
     }
+}
+
+lazy_static::lazy_static! {
+    static ref ENGLISH_RULES: IntroRules = IntroRules::new(&ENGLISH);
+    static ref GERMAN_RULES: IntroRules = IntroRules::new(&GERMAN);
+    static ref FRENCH_RULES: IntroRules = IntroRules::new(&FRENCH);
+    static ref SPANISH_RULES: IntroRules = IntroRules::new(&SPANISH);
+
+    static ref COPYRIGHT_YEAR: Regex = Regex::new(
+        r"(?i)(?:copyright|©|℗|\(p\)|phonogram)\D{0,12}?(\d{4})"
+    ).unwrap();
+}
+
+fn parse_book_info_from_transcript(transcript: &str, language: Language) -> ExtractedBookInfo {
+    let mut info = ExtractedBookInfo::default();
+    let text = transcript.replace(['\n', '\r'], " ");
+    let text = text.trim();
+    let rules = language.rules();
 
     // Try full pattern with narrator
-    if let Some(caps) = TITLE_BY_AUTHOR_NARRATOR.captures(text) {
-        info.title = caps.get(1).map(|m| m.as_str().trim().to_string());
-        info.author = caps.get(2).map(|m| clean_person_name(m.as_str()));
-        info.narrator = caps.get(3).map(|m| clean_person_name(m.as_str()));
+    if let Some(caps) = rules.title_by_author_narrator.captures(text) {
+        info.title = caps.get(1).map(|m| rules.lead_in.replace(m.as_str().trim(), "").to_string());
+        info.authors = caps.get(2).map(|m| rules.people(m.as_str())).unwrap_or_default();
+        info.narrators = caps.get(3).map(|m| rules.people(m.as_str())).unwrap_or_default();
     }
 
     if info.title.is_none() {
-        if let Some(caps) = THIS_IS_BY.captures(text) {
+        if let Some(caps) = rules.this_is_by.captures(text) {
             info.title = caps.get(1).map(|m| m.as_str().trim().to_string());
-            info.author = caps.get(2).map(|m| clean_person_name(m.as_str()));
+            info.authors = caps.get(2).map(|m| rules.people(m.as_str())).unwrap_or_default();
         }
     }
 
     if info.title.is_none() {
-        if let Some(caps) = WRITTEN_BY.captures(text) {
+        if let Some(caps) = rules.written_by.captures(text) {
             info.title = caps.get(1).map(|m| m.as_str().trim().to_string());
-            info.author = caps.get(2).map(|m| clean_person_name(m.as_str()));
+            info.authors = caps.get(2).map(|m| rules.people(m.as_str())).unwrap_or_default();
         }
     }
 
     if info.title.is_none() {
-        if let Some(caps) = SIMPLE_BY.captures(text) {
+        if let Some(caps) = rules.simple_by.captures(text) {
             let t = caps.get(1).map(|m| m.as_str()).unwrap_or("");
             if t.len() < 100 && t.split_whitespace().count() < 15 {
                 info.title = Some(t.trim().to_string());
-                info.author = caps.get(2).map(|m| clean_person_name(m.as_str()));
+                info.authors = caps.get(2).map(|m| rules.people(m.as_str())).unwrap_or_default();
             }
         }
     }
 
-    if info.narrator.is_none() {
-        if let Some(caps) = NARRATOR_PATTERN.captures(text) {
-            info.narrator = caps.get(1).map(|m| clean_person_name(m.as_str()));
+    if info.narrators.is_empty() {
+        if let Some(caps) = rules.narrator.captures(text) {
+            info.narrators = caps.get(1).map(|m| rules.people(m.as_str())).unwrap_or_default();
         }
     }
 
     // Publisher extraction
     if info.publisher.is_none() {
-        if let Some(caps) = PUBLISHED_BY.captures(text) {
+        if let Some(caps) = rules.published_by.captures(text) {
             let pub_name = caps.get(1).map(|m| m.as_str().trim().to_string());
             if let Some(ref name) = pub_name {
                 let lower = name.to_lowercase();
                 if lower.contains("audio") || lower.contains("record") || lower.contains("audible")
+                    || lower.contains("hörbuch") || lower.contains("hörverlag")
                     || lower.contains("brilliance") || lower.contains("tantor")
                     || lower.contains("blackstone") || lower.contains("listening library")
                 {
@@ -819,79 +1932,578 @@ fn parse_book_info_from_transcript(transcript: &str) -> ExtractedBookInfo {
         }
     }
     if info.audio_publisher.is_none() {
-        if let Some(caps) = PUBLISHER_PRODUCTION.captures(text) {
+        if let Some(caps) = rules.production.captures(text) {
             info.audio_publisher = caps.get(1).map(|m| m.as_str().trim().to_string());
         }
     }
 
+    info.copyright_year = COPYRIGHT_YEAR.captures_iter(text)
+        .filter_map(|caps| caps.get(1))
+        .map(|m| m.as_str().to_string())
+        .find(|y| is_plausible_year(y));
+    settle_production(&mut info, text, rules);
+
     info
 }
 
-/// Clean a person name extracted from transcript.
-/// Strips trailing non-name words (book text that got captured),
-/// sentence boundaries, and common false matches.
-fn clean_person_name(raw: &str) -> String {
-    // Split on sentence boundary first
-    let name = raw.split(". ").next().unwrap_or(raw);
-
-    // Words that are NOT part of a person's name - if we hit one, stop
-    let stop_words: &[&str] = &[
-        "this", "the", "a", "an", "and", "but", "or", "for", "in", "on", "at",
-        "to", "from", "with", "is", "was", "are", "were", "be", "been", "have",
-        "has", "had", "do", "does", "did", "will", "would", "could", "should",
-        "may", "might", "shall", "can", "it", "its", "that", "which", "who",
-        "whom", "whose", "where", "when", "how", "what", "why", "not", "no",
-        "so", "if", "then", "than", "as", "of", "by", "chapter", "part",
-        "book", "volume", "copyright", "all", "rights", "reserved",
-    ];
-
-    let words: Vec<&str> = name.split_whitespace().collect();
-    let mut clean_words = Vec::new();
-
-    for word in &words {
-        let lower = word.to_lowercase();
-        let lower = lower.trim_end_matches(['.', ',', ';', ':']);
-
-        // Stop at common English words (not part of a name)
-        if stop_words.contains(&&*lower) {
-            break;
-        }
-
-        // Stop at lowercase words that aren't initials (names are capitalized)
-        // Allow: "de", "van", "von", "le", "la", "del", "da" (name particles)
-        let name_particles = ["de", "van", "von", "le", "la", "del", "da", "di", "el", "al"];
-        if word.chars().next().map(|c| c.is_lowercase()).unwrap_or(false)
-            && !name_particles.contains(&&*lower)
-        {
-            break;
-        }
-
-        clean_words.push(*word);
-    }
-
-    // A person name should be 1-5 words
-    if clean_words.len() > 5 {
-        clean_words.truncate(5);
+/// Drop cast markers a parse took for narrators, and fill the production
+/// type from the transcript's markers or the number of narrators.
+fn settle_production(info: &mut ExtractedBookInfo, text: &str, rules: &IntroRules) {
+    info.narrators.retain(|n| !rules.is_cast_marker(n));
+    if info.production.is_some() {
+        return;
     }
+    info.production = if rules.dramatized.is_match(text) {
+        Some(ProductionType::Dramatized)
+    } else if rules.full_cast.is_match(text) {
+        Some(ProductionType::FullCast)
+    } else {
+        match info.narrators.len() {
+            0 => None,
+            1 => Some(ProductionType::SingleVoice),
+            _ => Some(ProductionType::DualNarrator),
+        }
+    };
+}
 
-    let result = clean_words.join(" ");
-    result.trim_end_matches(['.', ',', ';', ':']).trim().to_string()
+fn is_plausible_year(year: &str) -> bool {
+    year.len() == 4 && year.parse::<u32>().is_ok_and(|y| (1800..=2100).contains(&y))
 }
 
 fn calculate_confidence(info: &ExtractedBookInfo) -> f32 {
     let mut score = 0.0f32;
-    if info.narrator.is_some() { score += 0.3; }
-    if info.author.is_some() { score += 0.3; }
+    if !info.narrators.is_empty() || info.production.is_some() { score += 0.3; }
+    if !info.authors.is_empty() { score += 0.3; }
     if info.publisher.is_some() || info.audio_publisher.is_some() { score += 0.2; }
     if info.title.is_some() { score += 0.1; }
     score.min(1.0)
 }
 
+/// How far to trust a field from a parse: LLM over regex, and in the outro
+/// less for title and author, since closing credits often advertise other books.
+fn field_score(result: &AudioIntroResult, field: &str, source: FieldSource) -> f32 {
+    let method = match result.parse_method.as_str() {
+        "llm" => 1.0,
+        "regex" => 0.8,
+        _ => 0.0,
+    };
+    let passage = match (source, field) {
+        (FieldSource::Outro, "title" | "subtitle") => 0.5,
+        (FieldSource::Outro, "authors") => 0.7,
+        _ => 1.0,
+    };
+    // A transcript that yielded several fields is more likely the real credits
+    method * passage * (0.5 + result.confidence / 2.0)
+}
+
+/// Record a score for every field the result found.
+fn score_fields(result: &mut AudioIntroResult, source: FieldSource) {
+    let present = [
+        ("title", result.title.is_some()),
+        ("subtitle", result.subtitle.is_some()),
+        ("authors", !result.authors.is_empty()),
+        ("narrators", !result.narrators.is_empty()),
+        ("publisher", result.publisher.is_some()),
+        ("audio_publisher", result.audio_publisher.is_some()),
+        ("copyright_year", result.copyright_year.is_some()),
+        ("production", result.production.is_some()),
+    ];
+    for (field, found) in present {
+        if found {
+            let confidence = field_score(result, field, source);
+            result.field_confidence.insert(field.to_string(), FieldConfidence { source, confidence });
+        }
+    }
+}
+
+/// Merge the outro into the intro field by field: the outro fills gaps and
+/// replaces intro values it is more confident about; agreement raises confidence.
+fn merge_outro(mut intro: AudioIntroResult, outro: AudioIntroResult) -> AudioIntroResult {
+    fn merge_field<T>(
+        field: &str,
+        ours: &mut T,
+        theirs: T,
+        key: fn(&T) -> String,
+        scores: &mut BTreeMap<String, FieldConfidence>,
+        their_scores: &BTreeMap<String, FieldConfidence>,
+    ) {
+        let Some(&their_score) = their_scores.get(field).filter(|_| !key(&theirs).is_empty()) else {
+            return;
+        };
+        match scores.get_mut(field).filter(|_| !key(ours).is_empty()) {
+            None => {
+                *ours = theirs;
+                scores.insert(field.to_string(), their_score);
+            }
+            Some(score) if key(ours) == key(&theirs) => {
+                score.source = FieldSource::Both;
+                score.confidence = 1.0 - (1.0 - score.confidence) * (1.0 - their_score.confidence);
+            }
+            Some(score) if their_score.confidence > score.confidence => {
+                *ours = theirs;
+                *score = their_score;
+            }
+            Some(_) => {}
+        }
+    }
+    let one = |v: &Option<String>| v.as_deref().unwrap_or("").trim().to_lowercase();
+    let many = |v: &Vec<String>| v.join(", ").to_lowercase();
+
+    let scores = &mut intro.field_confidence;
+    let theirs = &outro.field_confidence;
+    merge_field("title", &mut intro.title, outro.title, one, scores, theirs);
+    merge_field("subtitle", &mut intro.subtitle, outro.subtitle, one, scores, theirs);
+    merge_field("authors", &mut intro.authors, outro.authors, many, scores, theirs);
+    merge_field("narrators", &mut intro.narrators, outro.narrators, many, scores, theirs);
+    merge_field("publisher", &mut intro.publisher, outro.publisher, one, scores, theirs);
+    merge_field("audio_publisher", &mut intro.audio_publisher, outro.audio_publisher, one, scores, theirs);
+    merge_field("copyright_year", &mut intro.copyright_year, outro.copyright_year, one, scores, theirs);
+    let production = |v: &Option<ProductionType>| v.map(ProductionType::as_str).unwrap_or("").to_string();
+    merge_field("production", &mut intro.production, outro.production, production, scores, theirs);
+
+    intro.language = intro.language.or(outro.language);
+    intro.outro_transcript = outro.transcript;
+    intro.confidence = calculate_confidence(&ExtractedBookInfo {
+        title: intro.title.clone(),
+        authors: intro.authors.clone(),
+        narrators: intro.narrators.clone(),
+        publisher: intro.publisher.clone(),
+        audio_publisher: intro.audio_publisher.clone(),
+        production: intro.production,
+        ..Default::default()
+    });
+    intro
+}
+
 fn empty_result(item_id: &str) -> AudioIntroResult {
     AudioIntroResult {
         item_id: item_id.to_string(), transcript: None, title: None, subtitle: None,
         narrators: vec![], authors: vec![],
         publisher: None, audio_publisher: None, language: None,
         parse_method: "none".to_string(), confidence: 0.0,
+        copyright_year: None, production: None, outro_transcript: None, field_confidence: BTreeMap::new(),
+        field_status: BTreeMap::new(), error: None,
+    }
+}
+
+fn error_result(item_id: &str, error: AppError) -> AudioIntroResult {
+    AudioIntroResult { error: Some(error), ..empty_result(item_id) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_ffmpeg_stderr() {
+        let e = ffmpeg_error("[https @ 0x1] HTTP error 401 Unauthorized\nhttps://abs/...: Server returned 401 Unauthorized", true);
+        assert_eq!(e.kind, ErrorKind::AbsUnauthorized);
+        assert!(e.detail.unwrap().contains("401 Unauthorized"));
+
+        let e = ffmpeg_error("Connection to tcp://nas:13378 failed: Connection refused", true);
+        assert_eq!(e.kind, ErrorKind::AbsUnreachable);
+
+        let e = ffmpeg_error("/books/missing.m4b: No such file or directory", false);
+        assert_eq!(e.kind, ErrorKind::NotFound);
+
+        let e = ffmpeg_error("Invalid data found when processing input", false);
+        assert_eq!(e.kind, ErrorKind::FfmpegFailed);
+    }
+
+    #[test]
+    fn parses_verbose_json_segments() {
+        let data = serde_json::json!({
+            "text": "Part Two. The Storm", "language": "english",
+            "segments": [
+                { "id": 0, "start": 0.0, "end": 1.52, "text": " Part Two." },
+                { "id": 1, "start": 1.52, "end": 3.0049, "text": " The Storm" },
+            ],
+        });
+        assert_eq!(parse_verbose_segments(&data), vec![
+            Segment { start_ms: 0, end_ms: 1520, text: "Part Two.".into() },
+            Segment { start_ms: 1520, end_ms: 3005, text: "The Storm".into() },
+        ]);
+        assert!(parse_verbose_segments(&serde_json::json!({ "text": "" })).is_empty());
+    }
+
+    #[test]
+    fn stage_limits_follow_local_and_cloud_settings() {
+        let request = |local_whisper, local_ai| AudioIntroRequest {
+            item_id: "a".into(), source: "local".into(), title: None, author: None, narrator: None,
+            file_ino: None, file_path: None, abs_base_url: None, abs_api_token: None,
+            openai_api_key: None, use_local_ai: Some(local_ai), ollama_model: None,
+            ollama_base_url: None, use_local_whisper: Some(local_whisper), whisper_model: None,
+            last_file_ino: None, last_file_path: None, scan_outro: None, outro_minutes: None,
+        };
+        let concurrency = IntroConcurrency { local_concurrency: 2, cloud_concurrency: 5 };
+
+        let cloud = StageLimits::for_items(&[request(false, false)], concurrency);
+        assert_eq!(cloud.ffmpeg.available_permits(), 5);
+        assert_eq!(cloud.cloud_whisper.available_permits(), 5);
+        assert_eq!(cloud.llm.available_permits(), 5);
+        assert_eq!(cloud.in_flight(), 10);
+
+        let local = StageLimits::for_items(&[request(true, true)], concurrency);
+        assert_eq!(local.local_whisper.available_permits(), 2);
+        assert_eq!(local.llm.available_permits(), 2);
+        assert_eq!(local.in_flight(), 7);
+
+        let zero = IntroConcurrency { local_concurrency: 0, cloud_concurrency: 0 };
+        assert_eq!(StageLimits::for_items(&[], zero).in_flight(), 2);
+    }
+
+    #[test]
+    fn aims_intro_windows_at_speech_after_the_theme() {
+        // 40s theme with no pauses, narration from 41s, a dedication at 2:30
+        let stderr = "\
+[silencedetect @ 0x1] silence_start: 40.2
+[silencedetect @ 0x1] silence_end: 41.1 | silence_duration: 0.9
+[silencedetect @ 0x1] silence_start: 44.0
+[silencedetect @ 0x1] silence_end: 44.5 | silence_duration: 0.5
+[silencedetect @ 0x1] silence_start: 49.3
+[silencedetect @ 0x1] silence_end: 150.0 | silence_duration: 100.7
+[silencedetect @ 0x1] silence_start: 155.0
+[silencedetect @ 0x1] silence_end: 155.6 | silence_duration: 0.6
+[silencedetect @ 0x1] silence_start: 159.0";
+        let silences = parse_silences(stderr, 300_000);
+        assert_eq!(silences.first(), Some(&(40_200, 41_100)));
+        assert_eq!(silences.last(), Some(&(159_000, 300_000)));
+
+        let regions = speech_regions(&silences, 300_000);
+        assert_eq!(regions, vec![(41_100, 49_300), (150_000, 159_000)]);
+        assert_eq!(speech_windows(&regions), vec![
+            (40, 60, "speech at 0:40".to_string()),
+            (149, 60, "speech at 2:29".to_string()),
+        ]);
+
+        // Wall-to-wall music: nothing to aim at, so the fixed windows are used
+        assert!(speech_regions(&[], 300_000).is_empty());
+        // Speech near the end of the span gets a shortened window
+        assert_eq!(speech_windows(&[(290_000, 295_000)]), vec![(289, 11, "speech at 4:49".to_string())]);
+    }
+
+    #[test]
+    fn outro_window_covers_the_end_without_rereading_the_intro() {
+        // Ten-hour final file: the last three minutes
+        assert_eq!(outro_span(36_000.4, 3, true), Some((35_820, 180)));
+        // A short final file of a multi-file book is read whole
+        assert_eq!(outro_span(120.0, 3, false), Some((0, 120)));
+        // Single short file: only what the intro passes skipped
+        assert_eq!(outro_span(320.0, 3, true), Some((300, 20)));
+        assert_eq!(outro_span(250.0, 3, true), None);
+        assert_eq!(outro_span(0.0, 3, false), None);
+    }
+
+    #[test]
+    fn parses_closing_credits() {
+        let info = parse_book_info_from_transcript(
+            "You have been listening to Dune, written by Frank Herbert, narrated by Scott Brick. \
+             Copyright 1965 by Frank Herbert. This has been a Macmillan Audio production.",
+            Language::English,
+        );
+        assert_eq!(info.title.as_deref(), Some("Dune"));
+        assert_eq!(info.authors, vec!["Frank Herbert"]);
+        assert_eq!(info.narrators, vec!["Scott Brick"]);
+        assert_eq!(info.audio_publisher.as_deref(), Some("Macmillan Audio"));
+        assert_eq!(info.copyright_year.as_deref(), Some("1965"));
+
+        let info = parse_book_info_from_transcript("The End. (P) and © 2016 Recorded Books.", Language::English);
+        assert_eq!(info.copyright_year.as_deref(), Some("2016"));
+        assert_eq!(excerpt("intro ... credits", Passage::Outro, 7), "credits");
+        assert_eq!(excerpt("é and more", Passage::Intro, 1), "");
+    }
+
+    #[test]
+    fn parses_intros_in_the_detected_language() {
+        let parse = |detected: &str, transcript: &str| {
+            parse_book_info_from_transcript(transcript, Language::from_detected(detected).unwrap())
+        };
+
+        let de = parse(
+            "de",
+            "Sie hören Der Schwarm von Frank Schätzing, gelesen von Christian Brückner. \
+             Eine Produktion von Der Hörverlag. ℗ 2004",
+        );
+        assert_eq!(de.title.as_deref(), Some("Der Schwarm"));
+        assert_eq!(de.authors, vec!["Frank Schätzing"]);
+        assert_eq!(de.narrators, vec!["Christian Brückner"]);
+        assert_eq!(de.audio_publisher.as_deref(), Some("Der Hörverlag"));
+        assert_eq!(de.copyright_year.as_deref(), Some("2004"));
+        let de = parse("german", "Tintenherz. Es liest Rainer Strecker. Veröffentlicht von Oetinger Audio.");
+        assert_eq!(de.narrators, vec!["Rainer Strecker"]);
+        assert_eq!(de.audio_publisher.as_deref(), Some("Oetinger Audio"));
+
+        let fr = parse(
+            "fr",
+            "Le Petit Prince, d'Antoine de Saint-Exupéry, lu par Bernard Giraudeau. Une production de Gallimard Jeunesse.",
+        );
+        assert_eq!(fr.title.as_deref(), Some("Le Petit Prince"));
+        assert_eq!(fr.authors, vec!["Antoine de Saint-Exupéry"]);
+        assert_eq!(fr.narrators, vec!["Bernard Giraudeau"]);
+        assert_eq!(fr.audio_publisher.as_deref(), Some("Gallimard Jeunesse"));
+
+        // "de" inside the title is not taken for "by"
+        let es = parse(
+            "spanish",
+            "Cien años de soledad, de Gabriel García Márquez, narrado por Enrique Arce. Una producción de Penguin Audio.",
+        );
+        assert_eq!(es.title.as_deref(), Some("Cien años de soledad"));
+        assert_eq!(es.authors, vec!["Gabriel García Márquez"]);
+        assert_eq!(es.narrators, vec!["Enrique Arce"]);
+        assert_eq!(es.audio_publisher.as_deref(), Some("Penguin Audio"));
+
+        // Without punctuation, each language's own words end a name
+        let de = parse("de", "Der Schwarm von Frank Schätzing Gelesen von Christian Brückner Kapitel Eins");
+        assert_eq!(de.authors, vec!["Frank Schätzing"]);
+        assert_eq!(de.narrators, vec!["Christian Brückner"]);
+        let fr = parse("fr", "Le Petit Prince. Lu par Bernard Giraudeau Chapitre Premier");
+        assert_eq!(fr.narrators, vec!["Bernard Giraudeau"]);
+        let es = parse("es", "Cien años de soledad. Narrado por Enrique Arce Capítulo Uno");
+        assert_eq!(es.narrators, vec!["Enrique Arce"]);
+
+        // English phrasing isn't recognised in a German transcript, and
+        // unsupported languages have no rule set of their own
+        assert!(parse("de", "Narrated by Scott Brick.").narrators.is_empty());
+        assert_eq!(Language::from_detected("ja"), None);
+    }
+
+    #[test]
+    fn parses_narrator_lists_and_cast_markers() {
+        let info = parse_book_info_from_transcript(
+            "The Way of Kings, by Brandon Sanderson, read by Kate Reading and Michael Kramer.",
+            Language::English,
+        );
+        assert_eq!(info.authors, vec!["Brandon Sanderson"]);
+        assert_eq!(info.narrators, vec!["Kate Reading", "Michael Kramer"]);
+        assert_eq!(info.production, Some(ProductionType::DualNarrator));
+
+        let info = parse_book_info_from_transcript(
+            "Good Omens by Neil Gaiman and Terry Pratchett. Performed by a full cast. A BBC Radio production.",
+            Language::English,
+        );
+        assert_eq!(info.authors, vec!["Neil Gaiman", "Terry Pratchett"]);
+        assert!(info.narrators.is_empty());
+        assert_eq!(info.production, Some(ProductionType::FullCast));
+
+        let info = parse_book_info_from_transcript(
+            "Narrated by Adjoa Andoh, Jonathan Keeble and Full Cast. A dramatized adaptation.",
+            Language::English,
+        );
+        assert_eq!(info.narrators, vec!["Adjoa Andoh", "Jonathan Keeble"]);
+        assert_eq!(info.production, Some(ProductionType::Dramatized));
+
+        let de = parse_book_info_from_transcript("Es liest Anna Thalbach und Rufus Beck. Ein Hörspiel.", Language::German);
+        assert_eq!(de.narrators, vec!["Anna Thalbach", "Rufus Beck"]);
+        assert_eq!(de.production, Some(ProductionType::Dramatized));
+
+        // A comma alone doesn't make a list
+        let info = parse_book_info_from_transcript("Narrated by Scott Brick, Copyright 1965.", Language::English);
+        assert_eq!(info.narrators, vec!["Scott Brick"]);
+        assert_eq!(info.production, Some(ProductionType::SingleVoice));
+
+        // LLM answers in either shape
+        let parsed = serde_json::json!({ "narrators": ["Kate Reading", "kate reading", "Michael Kramer"], "author": "Brandon Sanderson" });
+        assert_eq!(parse_people(&parsed, "narrators", "narrator"), vec!["Kate Reading", "Michael Kramer"]);
+        assert_eq!(parse_people(&parsed, "authors", "author"), vec!["Brandon Sanderson"]);
+        assert_eq!(ProductionType::parse("Full Cast"), Some(ProductionType::FullCast));
+    }
+
+    #[test]
+    fn merges_outro_fields_by_confidence() {
+        let parsed = |method: &str, confidence: f32| AudioIntroResult {
+            parse_method: method.into(),
+            confidence,
+            ..empty_result("a")
+        };
+        let mut intro = AudioIntroResult {
+            title: Some("Dune".into()),
+            authors: vec!["Frank Herbert".into()],
+            publisher: Some("Chilton".into()),
+            language: Some("en".into()),
+            ..parsed("regex", 0.6)
+        };
+        score_fields(&mut intro, FieldSource::Intro);
+        let mut outro = AudioIntroResult {
+            transcript: Some("You have been listening to...".into()),
+            title: Some("Dune Messiah".into()),
+            authors: vec!["frank herbert".into()],
+            narrators: vec!["Scott Brick".into()],
+            publisher: Some("Macmillan Audio".into()),
+            copyright_year: Some("1965".into()),
+            ..parsed("llm", 0.9)
+        };
+        score_fields(&mut outro, FieldSource::Outro);
+
+        let merged = merge_outro(intro, outro);
+        let source = |f: &str| merged.field_confidence[f].source;
+        // Advertised titles in the outro don't displace the intro's
+        assert_eq!(merged.title.as_deref(), Some("Dune"));
+        assert_eq!(source("title"), FieldSource::Intro);
+        // Agreement raises confidence above either pass
+        assert_eq!(source("authors"), FieldSource::Both);
+        assert!(merged.field_confidence["authors"].confidence > 0.8);
+        // Gaps are filled, and a more confident LLM parse wins
+        assert_eq!(merged.narrators, vec!["Scott Brick"]);
+        assert_eq!(source("narrators"), FieldSource::Outro);
+        assert_eq!(merged.publisher.as_deref(), Some("Macmillan Audio"));
+        assert_eq!(merged.copyright_year.as_deref(), Some("1965"));
+        assert_eq!(merged.outro_transcript.as_deref(), Some("You have been listening to..."));
+        assert_eq!(merged.language.as_deref(), Some("en"));
+        assert!((merged.confidence - 0.9).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn cache_keys_follow_audio_not_item_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("book.m4b");
+        std::fs::write(&file, b"audio").unwrap();
+        let request = |item_id: &str, model: &str| AudioIntroRequest {
+            item_id: item_id.into(), source: "local".into(), title: None, author: None, narrator: None,
+            file_ino: None, file_path: Some(file.to_string_lossy().into()), abs_base_url: None,
+            abs_api_token: None, openai_api_key: None, use_local_ai: None, ollama_model: None,
+            ollama_base_url: None, use_local_whisper: Some(true), whisper_model: Some(model.into()),
+            last_file_ino: None, last_file_path: None, scan_outro: None, outro_minutes: None,
+        };
+
+        let key = cache_key(&request("scan-1", "base")).await.unwrap();
+        // A rescan assigns a new id to the same audio
+        assert_eq!(cache_key(&request("scan-2", "base")).await.unwrap(), key);
+        assert_ne!(cache_key(&request("scan-1", "small")).await.unwrap().key, key.key);
+        let outro_off = AudioIntroRequest { scan_outro: Some(false), ..request("scan-1", "base") };
+        assert_ne!(cache_key(&outro_off).await.unwrap().key, key.key);
+
+        std::fs::write(&file, b"re-encoded audio").unwrap();
+        assert_ne!(cache_key(&request("scan-1", "base")).await.unwrap().key, key.key);
+        std::fs::remove_file(&file).unwrap();
+        assert!(cache_key(&request("scan-1", "base")).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn cached_and_fresh_results_are_reconciled() {
+        let request = AudioIntroRequest {
+            item_id: "scan-1".into(), source: "local".into(), title: None, author: Some("Voltaire".into()),
+            narrator: None, file_ino: None, file_path: None, abs_base_url: None, abs_api_token: None,
+            openai_api_key: None, use_local_ai: None, ollama_model: None, ollama_base_url: None,
+            use_local_whisper: None, whisper_model: None, last_file_ino: None, last_file_path: None,
+            scan_outro: None, outro_minutes: None,
+        };
+        let library = [BookMetadata { title: "Candide".into(), author: "Voltaire".into(), ..Default::default() }];
+        let reference = Reference::new(crate::authority::AuthorityStore::default(), &library);
+        let heard = || -> AudioIntroResult {
+            serde_json::from_value(serde_json::json!({
+                "item_id": "scan-1", "transcript": "...", "title": "Condeed", "subtitle": null,
+                "authors": ["Voltair"], "narrators": [], "publisher": null, "audio_publisher": null,
+                "language": "en", "parse_method": "llm", "confidence": 1.0,
+            }))
+            .unwrap()
+        };
+
+        let cached = resolve_intro(&request, Some(heard()), &reference, || async { unreachable!() }).await;
+        let fresh = resolve_intro(&request, None, &reference, || async { heard() }).await;
+        for result in [cached, fresh] {
+            assert_eq!(result.title.as_deref(), Some("Candide"));
+            assert_eq!(result.authors, vec!["Voltaire"]);
+            assert_eq!(result.field_status["authors"].status, crate::reconcile::FieldStatus::Confirmed);
+        }
+
+        // A failed extraction is passed through untouched
+        let failed = resolve_intro(&request, None, &reference, || async {
+            AudioIntroResult { error: Some(AppError::ffmpeg_missing()), ..heard() }
+        })
+        .await;
+        assert_eq!(failed.title.as_deref(), Some("Condeed"));
+    }
+
+    #[tokio::test]
+    async fn llm_failures_are_app_errors() {
+        let settings = LlmSettings { use_local_ai: false, ollama_model: None, ollama_base_url: None, openai_api_key: None };
+        let err = ask_llm("system", "user", &settings).await.unwrap_err();
+        assert_eq!(err.kind, ErrorKind::MissingApiKey);
+        assert!(err.hint.is_some());
+    }
+
+    #[tokio::test]
+    async fn resolves_and_streams_abs_audio_from_mock_server() {
+        let mock = crate::abs_mock::MockAbs::start().await;
+        let request = |item_id: &str| AudioIntroRequest {
+            item_id: item_id.into(), source: "abs".into(), title: None, author: None, narrator: None,
+            file_ino: None, file_path: None, abs_base_url: Some(mock.url().into()),
+            abs_api_token: Some(crate::abs_mock::TOKEN.into()), openai_api_key: None, use_local_ai: None,
+            ollama_model: None, ollama_base_url: None, use_local_whisper: None, whisper_model: None,
+            last_file_ino: None, last_file_path: None, scan_outro: None, outro_minutes: None,
+        };
+
+        let url = build_abs_audio_url(&request("li_hobbit")).await.unwrap();
+        assert_eq!(url, format!("{}/api/items/li_hobbit/file/2001", mock.url()));
+        let err = build_abs_audio_url(&request("li_empty")).await.unwrap_err();
+        assert_eq!(err.kind, ErrorKind::AbsNotFound);
+
+        // The outro reads the last file, using its ABS duration
+        let (input, secs, same_file) = outro_input(&request("li_hobbit")).await.unwrap();
+        let AudioInput::Abs { url: outro_url, .. } = input else { panic!("expected an ABS input") };
+        assert_eq!(outro_url, format!("{}/api/items/li_hobbit/file/2002", mock.url()));
+        assert_eq!((secs, same_file), (crate::abs_mock::AUDIO_SECS as f64, false));
+        let (_, _, same_file) = outro_input(&request("li_dune")).await.unwrap();
+        assert!(same_file);
+        let key = cache_key(&request("li_hobbit")).await.unwrap();
+        assert_eq!(key.source, format!("{}/items/li_hobbit", mock.url()));
+        assert!(key.fingerprint.starts_with("2001:") && key.fingerprint.contains(" 2002:"));
+
+        if !check_ffmpeg_available() {
+            println!("FFmpeg not installed; skipping the streaming half");
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("clip.wav");
+        let out_path = out.to_string_lossy().to_string();
+        let token = crate::abs_mock::TOKEN;
+        run_blocking(move || extract_audio_from_url_with_offset(&url, token, &out_path, 1, 2, "wav")).await.unwrap();
+        assert!(std::fs::metadata(&out).unwrap().len() > 44);
+
+        mock.reject_auth(true);
+        let url = format!("{}/api/items/li_hobbit/file/2001", mock.url());
+        let out_path = dir.path().join("denied.wav").to_string_lossy().to_string();
+        let err = run_blocking(move || extract_audio_from_url_with_offset(&url, token, &out_path, 0, 2, "wav"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind, ErrorKind::AbsUnauthorized);
+    }
+
+    #[tokio::test]
+    async fn transcribes_with_an_openai_compatible_server() {
+        use crate::abs_mock::{MockAbs, TOKEN, TRANSCRIPT};
+        let mock = MockAbs::start().await;
+        let provider = |response_format| TranscriptionProvider {
+            base_url: format!("{}/v1", mock.url()),
+            model: "Systran/faster-whisper-small".into(),
+            api_key: Some(TOKEN.into()),
+            response_format,
+        };
+
+        let r = call_whisper_api(b"ID3".to_vec(), &provider(ResponseFormat::VerboseJson)).await.unwrap();
+        assert_eq!(r.text, TRANSCRIPT);
+        assert_eq!(r.language.as_deref(), Some("english"));
+        assert_eq!(r.segments.len(), 2);
+        assert_eq!((r.segments[1].start_ms, r.segments[1].end_ms), (1500, 4000));
+
+        for format in [ResponseFormat::Json, ResponseFormat::Text] {
+            let r = call_whisper_api(b"ID3".to_vec(), &provider(format)).await.unwrap();
+            assert_eq!(r.text, TRANSCRIPT);
+            assert_eq!(r.language, None);
+            assert_eq!(r.segments, vec![Segment { start_ms: 0, end_ms: 0, text: TRANSCRIPT.into() }]);
+        }
+        let sent = mock.requests();
+        assert!(sent.iter().all(|r| r.path == "/v1/audio/transcriptions"));
+        assert!(sent[0].body.contains("Systran/faster-whisper-small"));
+
+        // A server without a key is called anyway; this one rejects it
+        let anonymous = TranscriptionProvider { api_key: None, ..provider(ResponseFormat::Json) };
+        let err = try_cloud_whisper(b"ID3", &anonymous).await.unwrap_err();
+        assert_eq!(err.kind, ErrorKind::ApiUnauthorized);
+        assert!(err.message.contains("Transcription server"), "{}", err.message);
+
+        let openai = TranscriptionProvider { base_url: OPENAI_TRANSCRIPTION_URL.into(), ..anonymous };
+        assert_eq!(try_cloud_whisper(b"ID3", &openai).await.unwrap_err().kind, ErrorKind::MissingApiKey);
+        assert_eq!(openai.label(), "openai/Systran/faster-whisper-small");
     }
 }
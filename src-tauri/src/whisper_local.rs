@@ -1,9 +1,17 @@
 // src-tauri/src/whisper_local.rs
 // Local whisper.cpp manager - download binary + models, transcribe locally
+// with a process per clip or a managed whisper-server that keeps the model loaded
 // Follows the same pattern as ollama.rs
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::config::AppConfig;
+use crate::error::{AppError, ErrorKind};
+use crate::progress::{ProgressEvent, ProgressSink, Stage};
+use crate::transcribe::Segment;
 
 const MODEL_BASE_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
 const WHISPER_VERSION: &str = "1.8.4";
@@ -29,7 +37,10 @@ pub struct WhisperLocalStatus {
     pub installed: bool,
     pub binary_path: Option<String>,
     pub models: Vec<WhisperLocalModel>,
+    /// Model loaded in the running whisper-server, if any.
     pub active_model: Option<String>,
+    /// whisper-server was found, so server mode can be used.
+    pub server_installed: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -230,34 +241,39 @@ fn get_binary_download_url() -> Result<(&'static str, &'static str), String> {
 
 // ---- Tauri commands ----
 
-#[tauri::command]
-pub async fn whisper_local_get_status() -> Result<WhisperLocalStatus, String> {
+#[cfg_attr(feature = "gui", tauri::command)]
+pub async fn whisper_local_get_status() -> Result<WhisperLocalStatus, AppError> {
     let binary = find_whisper_binary();
     let models = list_models();
     Ok(WhisperLocalStatus {
         installed: binary.is_some(),
         binary_path: binary.map(|p| p.to_string_lossy().into_owned()),
         models,
-        active_model: None,
+        active_model: running_server().map(|s| s.model_id),
+        server_installed: find_server_binary().is_some(),
     })
 }
 
-#[tauri::command]
+#[cfg_attr(feature = "gui", tauri::command)]
 pub fn whisper_local_get_model_presets() -> Vec<WhisperModelPreset> {
     WHISPER_MODEL_PRESETS.to_vec()
 }
 
+#[cfg(feature = "gui")]
 #[tauri::command]
-pub async fn whisper_local_install(window: tauri::Window) -> Result<String, String> {
-    use tauri::Emitter;
+pub async fn whisper_local_install(window: tauri::Window) -> Result<String, AppError> {
+    install(&window).await
+}
 
+/// Install whisper-cpp (and FFmpeg if missing).
+pub async fn install(progress: &dyn ProgressSink) -> Result<String, AppError> {
     // Ensure ffmpeg is available (bundled or system). Download if missing.
     if find_ffmpeg_binary().is_none() {
-        let _ = window.emit("whisper_install_progress", serde_json::json!({
-            "stage": "downloading", "status": "Downloading FFmpeg...",
-        }));
-        if let Err(e) = install_ffmpeg(&window).await {
-            return Err(format!("FFmpeg install failed: {}. Install manually with 'brew install ffmpeg' (macOS) or download from ffmpeg.org.", e));
+        progress.report(ProgressEvent::install(Stage::Downloading, "Downloading FFmpeg..."));
+        if let Err(e) = install_ffmpeg(progress).await {
+            return Err(AppError::new(ErrorKind::FfmpegMissing, "FFmpeg install failed")
+                .with_detail(e)
+                .with_hint("Install manually with 'brew install ffmpeg' (macOS) or download from ffmpeg.org"));
         }
     }
 
@@ -272,16 +288,12 @@ pub async fn whisper_local_install(window: tauri::Window) -> Result<String, Stri
 
     // macOS/Linux: try brew first (handles Metal acceleration, easy updates)
     if archive_type == "brew" {
-        let _ = window.emit("whisper_install_progress", serde_json::json!({
-            "stage": "installing", "status": "Installing whisper-cpp via Homebrew...",
-        }));
+        progress.report(ProgressEvent::install(Stage::Installing, "Installing whisper-cpp via Homebrew..."));
 
         // Try brew
         if let Ok(output) = std::process::Command::new("brew").args(["install", "whisper-cpp"]).output() {
             if output.status.success() {
-                let _ = window.emit("whisper_install_progress", serde_json::json!({
-                    "stage": "complete", "status": "whisper-cpp installed via Homebrew",
-                }));
+                progress.report(ProgressEvent::install(Stage::Complete, "whisper-cpp installed via Homebrew"));
                 return Ok("whisper-cpp installed via Homebrew".to_string());
             }
         }
@@ -289,31 +301,30 @@ pub async fn whisper_local_install(window: tauri::Window) -> Result<String, Stri
         // Brew failed - try downloading prebuilt for macOS ARM
         #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
         {
-            let _ = window.emit("whisper_install_progress", serde_json::json!({
-                "stage": "downloading", "status": "Homebrew not available, downloading binary...",
-            }));
+            progress.report(ProgressEvent::install(Stage::Downloading, "Homebrew not available, downloading binary..."));
             return download_and_install_binary(
                 "https://github.com/ggml-org/whisper.cpp/releases/download/v1.8.4/whisper-v1.8.4-xcframework.zip",
-                &window,
-            ).await;
+                progress,
+            ).await.map_err(install_failed);
         }
 
-        return Err("Could not install whisper-cpp. Install Homebrew (https://brew.sh) and try again, or install manually.".to_string());
+        return Err(AppError::new(ErrorKind::WhisperNotInstalled, "Could not install whisper-cpp")
+            .with_hint("Install Homebrew (https://brew.sh) and try again, or install whisper-cpp manually"));
     }
 
     // Windows: download prebuilt binary
-    let _ = window.emit("whisper_install_progress", serde_json::json!({
-        "stage": "downloading", "status": "Downloading whisper-cpp...",
-    }));
+    progress.report(ProgressEvent::install(Stage::Downloading, "Downloading whisper-cpp..."));
 
-    download_and_install_binary(url, &window).await
+    download_and_install_binary(url, progress).await.map_err(install_failed)
+}
+
+fn install_failed(detail: String) -> AppError {
+    AppError::new(ErrorKind::WhisperNotInstalled, "whisper-cpp install failed").with_detail(detail)
 }
 
 /// Download and install a static ffmpeg binary into `whisper_dir()`.
 /// macOS: evermeet.cx (universal). Windows: gyan.dev essentials build.
-async fn install_ffmpeg(window: &tauri::Window) -> Result<String, String> {
-    use tauri::Emitter;
-
+async fn install_ffmpeg(progress: &dyn ProgressSink) -> Result<String, String> {
     let dir = whisper_dir()?;
     std::fs::create_dir_all(&dir).map_err(|e| format!("Dir error: {}", e))?;
 
@@ -335,9 +346,7 @@ async fn install_ffmpeg(window: &tauri::Window) -> Result<String, String> {
 
     let bytes = resp.bytes().await.map_err(|e| format!("Read error: {}", e))?;
 
-    let _ = window.emit("whisper_install_progress", serde_json::json!({
-        "stage": "extracting", "status": "Extracting FFmpeg...",
-    }));
+    progress.report(ProgressEvent::install(Stage::Extracting, "Extracting FFmpeg..."));
 
     let temp_dir = tempfile::tempdir().map_err(|e| format!("Temp error: {}", e))?;
     let target_path = bundled_ffmpeg_path()?;
@@ -400,16 +409,12 @@ async fn install_ffmpeg(window: &tauri::Window) -> Result<String, String> {
             .map_err(|e| format!("Permission error: {}", e))?;
     }
 
-    let _ = window.emit("whisper_install_progress", serde_json::json!({
-        "stage": "ffmpeg_done", "status": "FFmpeg installed",
-    }));
+    progress.report(ProgressEvent::install(Stage::FfmpegDone, "FFmpeg installed"));
 
     Ok("FFmpeg installed".to_string())
 }
 
-async fn download_and_install_binary(url: &str, window: &tauri::Window) -> Result<String, String> {
-    use tauri::Emitter;
-
+async fn download_and_install_binary(url: &str, progress: &dyn ProgressSink) -> Result<String, String> {
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(300))
         .build().map_err(|e| format!("HTTP error: {}", e))?;
@@ -421,9 +426,7 @@ async fn download_and_install_binary(url: &str, window: &tauri::Window) -> Resul
 
     let bytes = resp.bytes().await.map_err(|e| format!("Read error: {}", e))?;
 
-    let _ = window.emit("whisper_install_progress", serde_json::json!({
-        "stage": "extracting", "status": "Extracting binary...",
-    }));
+    progress.report(ProgressEvent::install(Stage::Extracting, "Extracting binary..."));
 
     let dir = whisper_dir()?;
     let binary_path = bundled_binary_path()?;
@@ -488,23 +491,25 @@ async fn download_and_install_binary(url: &str, window: &tauri::Window) -> Resul
             .map_err(|e| format!("Permission error: {}", e))?;
     }
 
-    let _ = window.emit("whisper_install_progress", serde_json::json!({
-        "stage": "complete", "status": "whisper-cpp installed",
-    }));
+    progress.report(ProgressEvent::install(Stage::Complete, "whisper-cpp installed"));
 
     Ok("whisper-cpp installed successfully".to_string())
 }
 
+#[cfg(feature = "gui")]
 #[tauri::command]
 pub async fn whisper_local_download_model(
     model_id: String,
     window: tauri::Window,
-) -> Result<String, String> {
-    use tauri::Emitter;
+) -> Result<String, AppError> {
+    download_model(&model_id, &window).await
+}
 
+/// Download a ggml model by preset id into the models directory.
+pub async fn download_model(model_id: &str, progress: &dyn ProgressSink) -> Result<String, AppError> {
     let preset = WHISPER_MODEL_PRESETS.iter()
         .find(|p| p.id == model_id)
-        .ok_or_else(|| format!("Unknown model: {}", model_id))?;
+        .ok_or_else(|| AppError::invalid_input(format!("Unknown model: {}", model_id)))?;
 
     let models_dir = whisper_models_dir()?;
     std::fs::create_dir_all(&models_dir).map_err(|e| format!("Dir error: {}", e))?;
@@ -515,18 +520,15 @@ pub async fn whisper_local_download_model(
     }
 
     let url = format!("{}/{}", MODEL_BASE_URL, preset.filename);
-    let _ = window.emit("whisper_install_progress", serde_json::json!({
-        "stage": "downloading",
-        "status": format!("Downloading {} ({}MB)...", preset.label, preset.size_mb),
-    }));
+    progress.report(ProgressEvent::install(Stage::Downloading, format!("Downloading {} ({}MB)...", preset.label, preset.size_mb)));
 
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(600))
-        .build().map_err(|e| format!("HTTP error: {}", e))?;
+        .build().map_err(|e| AppError::request_failed("Hugging Face", &e))?;
 
-    let resp = client.get(&url).send().await.map_err(|e| format!("Download failed: {}", e))?;
+    let resp = client.get(&url).send().await.map_err(|e| AppError::request_failed("Hugging Face", &e))?;
     if !resp.status().is_success() {
-        return Err(format!("Download failed: HTTP {}", resp.status()));
+        return Err(AppError::http_status("Hugging Face", resp.status().as_u16(), ""));
     }
 
     let total_size = resp.content_length().unwrap_or(0);
@@ -538,48 +540,60 @@ pub async fn whisper_local_download_model(
     let mut stream = resp.bytes_stream();
 
     while let Some(chunk) = stream.next().await {
-        let chunk = chunk.map_err(|e| format!("Stream error: {}", e))?;
+        let chunk = chunk.map_err(|e| AppError::request_failed("Hugging Face", &e))?;
         file.write_all(&chunk).map_err(|e| format!("Write error: {}", e))?;
         downloaded += chunk.len() as u64;
 
         if total_size > 0 {
             let pct = (downloaded as f64 / total_size as f64 * 100.0) as u32;
-            let _ = window.emit("whisper_install_progress", serde_json::json!({
-                "stage": "downloading",
-                "status": format!("Downloading {}: {}%", preset.label, pct),
-                "percent": pct,
-                "downloaded": downloaded,
-                "total": total_size,
-            }));
+            progress.report(
+                ProgressEvent::install(Stage::Downloading, format!("Downloading {}: {}%", preset.label, pct))
+                    .with_bytes(downloaded, total_size),
+            );
         }
     }
 
-    let _ = window.emit("whisper_install_progress", serde_json::json!({
-        "stage": "complete",
-        "status": format!("{} model downloaded", preset.label),
-    }));
+    progress.report(ProgressEvent::install(Stage::Complete, format!("{} model downloaded", preset.label)));
 
     Ok(format!("Model {} downloaded ({} MB)", model_id, preset.size_mb))
 }
 
-#[tauri::command]
-pub async fn whisper_local_delete_model(model_id: String) -> Result<String, String> {
+/// Keep a model loaded in whisper-server; defaults to the configured model.
+#[cfg_attr(feature = "gui", tauri::command)]
+pub async fn whisper_server_start(model_id: Option<String>) -> Result<String, AppError> {
+    let model_id = model_id
+        .or_else(|| AppConfig::load_or_default().whisper_model)
+        .unwrap_or_else(|| "base".to_string());
+    start_server(&model_id).await
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub async fn whisper_server_stop() -> Result<String, AppError> {
+    Ok(stop_server().await)
+}
+
+#[cfg_attr(feature = "gui", tauri::command)]
+pub async fn whisper_local_delete_model(model_id: String) -> Result<String, AppError> {
     let models_dir = whisper_models_dir()?;
     let filename = format!("ggml-{}.bin", model_id);
     let path = models_dir.join(&filename);
 
+    if running_server().is_some_and(|s| s.model_id == model_id) {
+        stop_server().await;
+    }
     if path.exists() {
         std::fs::remove_file(&path).map_err(|e| format!("Delete error: {}", e))?;
         Ok(format!("Deleted model {}", model_id))
     } else {
-        Err(format!("Model file not found: {}", filename))
+        Err(AppError::new(ErrorKind::NotFound, format!("Model file not found: {}", filename)))
     }
 }
 
 /// Uninstall whisper-cpp: remove bundled binary and all downloaded models
-#[tauri::command]
-pub async fn whisper_local_uninstall() -> Result<String, String> {
+#[cfg_attr(feature = "gui", tauri::command)]
+pub async fn whisper_local_uninstall() -> Result<String, AppError> {
     let dir = whisper_dir()?;
+    stop_server().await;
 
     // Remove bundled binary
     if let Ok(binary) = bundled_binary_path() {
@@ -603,8 +617,8 @@ pub async fn whisper_local_uninstall() -> Result<String, String> {
     Ok("Local Whisper removed".to_string())
 }
 
-#[tauri::command]
-pub fn whisper_local_get_disk_usage() -> Result<u64, String> {
+#[cfg_attr(feature = "gui", tauri::command)]
+pub fn whisper_local_get_disk_usage() -> Result<u64, AppError> {
     let dir = whisper_dir()?;
     if !dir.exists() { return Ok(0); }
 
@@ -619,31 +633,255 @@ pub fn whisper_local_get_disk_usage() -> Result<u64, String> {
     Ok(total)
 }
 
+// ---- whisper-server ----
+
+/// Not whisper-server's default 8080, which is often taken.
+const WHISPER_SERVER_PORT: u16 = 8178;
+
+/// The whisper-server this app started and the model it loaded.
+#[derive(Debug, Clone)]
+struct RunningServer {
+    pid: u32,
+    model_id: String,
+}
+
+static WHISPER_SERVER: Mutex<Option<RunningServer>> = Mutex::new(None);
+/// Held while starting or stopping, so concurrent clips don't race to spawn.
+static SERVER_LIFECYCLE: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+/// Set when server mode couldn't start, so every clip doesn't retry; cleared
+/// by an explicit start.
+static SERVER_GAVE_UP: AtomicBool = AtomicBool::new(false);
+
+fn server_base() -> String {
+    format!("http://127.0.0.1:{}", WHISPER_SERVER_PORT)
+}
+
+fn running_server() -> Option<RunningServer> {
+    WHISPER_SERVER.lock().ok().and_then(|guard| guard.clone())
+}
+
+/// whisper-server ships next to whisper-cli in releases and brew installs.
+fn find_server_binary() -> Option<PathBuf> {
+    let name = if cfg!(windows) { "whisper-server.exe" } else { "whisper-server" };
+    if let Some(sibling) = find_whisper_binary().and_then(|b| b.parent().map(|dir| dir.join(name))) {
+        if sibling.exists() { return Some(sibling); }
+    }
+
+    #[cfg(unix)]
+    if let Ok(output) = std::process::Command::new("which").arg(name).output() {
+        if output.status.success() {
+            let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if !path.is_empty() { return Some(PathBuf::from(path)); }
+        }
+    }
+
+    #[cfg(windows)]
+    if let Ok(output) = std::process::Command::new("where").arg(name).output() {
+        if output.status.success() {
+            let path = String::from_utf8_lossy(&output.stdout).lines().next().unwrap_or("").trim().to_string();
+            if !path.is_empty() { return Some(PathBuf::from(path)); }
+        }
+    }
+
+    None
+}
+
+/// Ready once the model is loaded. Older servers have no /health but only
+/// listen after loading, so any answer from them counts.
+async fn server_ready() -> bool {
+    match reqwest::get(format!("{}/health", server_base())).await {
+        Ok(r) => r.status().is_success() || r.status() == reqwest::StatusCode::NOT_FOUND,
+        Err(_) => false,
+    }
+}
+
+/// Whether clips for `model_id` go to the server: it is already running
+/// that model, or server mode is on and it can be (re)started.
+async fn server_for(model_id: &str) -> bool {
+    if running_server().is_some_and(|s| s.model_id == model_id) {
+        return true;
+    }
+    if SERVER_GAVE_UP.load(Ordering::Relaxed) || !AppConfig::load_or_default().whisper_server.unwrap_or(false) {
+        return false;
+    }
+    match start_server(model_id).await {
+        Ok(_) => true,
+        Err(e) => {
+            eprintln!("   ⚠️ whisper-server unavailable, using whisper-cpp: {}", e);
+            SERVER_GAVE_UP.store(true, Ordering::Relaxed);
+            false
+        }
+    }
+}
+
+/// Start whisper-server with `model_id`, replacing one that has another model loaded.
+pub async fn start_server(model_id: &str) -> Result<String, AppError> {
+    let _lifecycle = SERVER_LIFECYCLE.lock().await;
+    if let Some(running) = running_server() {
+        if running.model_id == model_id && server_ready().await {
+            return Ok(format!("whisper-server is already running {}", model_id));
+        }
+        forget_server(running.pid);
+        kill_server(running.pid).await;
+    }
+
+    let binary = find_server_binary().ok_or_else(|| {
+        AppError::new(ErrorKind::WhisperNotInstalled, "whisper-server is not installed")
+            .with_hint("It ships with whisper.cpp; reinstall Local Whisper or install whisper-cpp with Homebrew")
+    })?;
+    let mut cmd = tokio::process::Command::from(command_with_model(&binary, model_id)?);
+    cmd.args(["--host", "127.0.0.1", "--port", &WHISPER_SERVER_PORT.to_string()])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+    let mut child = cmd.spawn().map_err(|e| {
+        AppError::new(ErrorKind::TranscriptionFailed, "Could not start whisper-server").with_detail(e.to_string())
+    })?;
+
+    let pid = child.id().unwrap_or(0);
+    if let Ok(mut guard) = WHISPER_SERVER.lock() {
+        *guard = Some(RunningServer { pid, model_id: model_id.to_string() });
+    }
+
+    // Reap the child, and forget it if it exits on its own
+    tokio::spawn(async move {
+        let _ = child.wait().await;
+        forget_server(pid);
+    });
+
+    // Larger models take a while to load
+    for _ in 0..60 {
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        if running_server().is_none() {
+            return Err(AppError::new(ErrorKind::TranscriptionFailed, "whisper-server exited while starting")
+                .with_hint(format!("Another program may be using port {}", WHISPER_SERVER_PORT)));
+        }
+        if server_ready().await {
+            SERVER_GAVE_UP.store(false, Ordering::Relaxed);
+            return Ok(format!("whisper-server started with {} (PID {})", model_id, pid));
+        }
+    }
+    forget_server(pid);
+    kill_server(pid).await;
+    Err(AppError::new(ErrorKind::TranscriptionFailed, "whisper-server didn't become ready within 30 seconds"))
+}
+
+/// Stop the whisper-server this app started.
+pub async fn stop_server() -> String {
+    let _lifecycle = SERVER_LIFECYCLE.lock().await;
+    match WHISPER_SERVER.lock().ok().and_then(|mut guard| guard.take()) {
+        Some(server) => {
+            kill_server(server.pid).await;
+            "whisper-server stopped".to_string()
+        }
+        None => "whisper-server is not running".to_string(),
+    }
+}
+
+fn forget_server(pid: u32) {
+    if let Ok(mut guard) = WHISPER_SERVER.lock() {
+        if guard.as_ref().is_some_and(|s| s.pid == pid) {
+            *guard = None;
+        }
+    }
+}
+
+/// Signal `pid` and wait briefly for the port to close.
+async fn kill_server(pid: u32) {
+    if pid == 0 {
+        return;
+    }
+    #[cfg(unix)]
+    unsafe { libc::kill(pid as i32, libc::SIGTERM); }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        let _ = tokio::process::Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/F"])
+            .creation_flags(0x08000000) // CREATE_NO_WINDOW
+            .output().await;
+    }
+    for _ in 0..10 {
+        if !server_ready().await {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+    }
+}
+
+/// What whisper-server heard in one clip.
+#[derive(Debug, Clone, PartialEq)]
+struct ServerResponse {
+    text: String,
+    segments: Vec<Segment>,
+    language: Option<String>,
+}
+
+async fn server_inference(audio_path: &str) -> Result<ServerResponse, AppError> {
+    let audio = tokio::fs::read(audio_path).await
+        .map_err(|e| AppError::new(ErrorKind::Io, "Could not read the extracted audio").with_detail(e.to_string()))?;
+    let part = reqwest::multipart::Part::bytes(audio)
+        .file_name("audio.wav").mime_str("audio/wav")
+        .map_err(|e| AppError::from(e.to_string()))?;
+    let form = reqwest::multipart::Form::new()
+        .text("response_format", "verbose_json")
+        .text("temperature", "0.0")
+        .part("file", part);
+
+    let response = reqwest::Client::new()
+        .post(format!("{}/inference", server_base()))
+        .multipart(form)
+        .send().await
+        .map_err(|e| AppError::new(ErrorKind::TranscriptionFailed, "Could not reach whisper-server").with_detail(e.to_string()))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(AppError::new(ErrorKind::TranscriptionFailed, format!("whisper-server returned HTTP {}", status))
+            .with_detail(body));
+    }
+    let data: serde_json::Value = response.json().await
+        .map_err(|e| AppError::new(ErrorKind::Parse, "Unexpected whisper-server output").with_detail(e.to_string()))?;
+    Ok(parse_server_response(&data))
+}
+
+/// whisper-server's verbose_json is OpenAI's shape; newer builds add
+/// `detected_language` alongside the language's full name.
+fn parse_server_response(data: &serde_json::Value) -> ServerResponse {
+    ServerResponse {
+        text: data["text"].as_str().unwrap_or("").trim().to_string(),
+        segments: crate::whisper::parse_verbose_segments(data),
+        language: data["detected_language"].as_str().or(data["language"].as_str()).map(str::to_string),
+    }
+}
+
 // ---- Transcription ----
 
-/// Run whisper-cpp locally on an audio file. Returns transcript text and detected language.
-pub fn transcribe_local(
-    audio_path: &str,
-    model_id: &str,
-) -> Result<(String, Option<String>), String> {
-    let binary = find_whisper_binary()
-        .ok_or("whisper-cpp not installed. Install it from Settings.")?;
+/// whisper-cpp set up to run `model_id`, with GPU resources found on macOS.
+fn whisper_command(model_id: &str) -> Result<std::process::Command, AppError> {
+    let binary = find_whisper_binary().ok_or_else(|| {
+        AppError::new(ErrorKind::WhisperNotInstalled, "whisper-cpp is not installed")
+            .with_hint("Install it from Settings")
+    })?;
+    command_with_model(&binary, model_id)
+}
 
+/// `binary` (whisper-cpp or whisper-server) loading `model_id`.
+fn command_with_model(binary: &Path, model_id: &str) -> Result<std::process::Command, AppError> {
     let models_dir = whisper_models_dir()?;
     let model_filename = format!("ggml-{}.bin", model_id);
     let model_path = models_dir.join(&model_filename);
 
     if !model_path.exists() {
-        return Err(format!("Whisper model '{}' not downloaded. Download it from Settings.", model_id));
+        return Err(AppError::new(ErrorKind::ModelNotDownloaded, format!("Whisper model '{}' is not downloaded", model_id))
+            .with_hint("Download it from Settings"));
     }
 
-    let mut cmd = std::process::Command::new(&binary);
-    cmd.args([
-        "-m", model_path.to_str().unwrap_or_default(),
-        "-f", audio_path,
-        "--no-timestamps",
-        "-l", "auto",
-    ]);
+    let mut cmd = std::process::Command::new(binary);
+    cmd.args(["-m", model_path.to_str().unwrap_or_default(), "-l", "auto"]);
 
     // Set Metal resources path on macOS for GPU acceleration
     #[cfg(target_os = "macos")]
@@ -653,12 +891,50 @@ pub fn transcribe_local(
             cmd.env("GGML_METAL_PATH_RESOURCES", brew_share.to_str().unwrap_or_default());
         }
     }
+    Ok(cmd)
+}
 
-    let output = cmd.output().map_err(|e| format!("whisper-cpp failed to run: {}", e))?;
+/// Transcribe a clip with the running whisper-server when there is one for
+/// `model_id` (or one can be started), otherwise with a whisper-cpp process.
+pub async fn transcribe(audio_path: String, model_id: String) -> Result<(String, Option<String>), AppError> {
+    if server_for(&model_id).await {
+        match server_inference(&audio_path).await {
+            Ok(response) if !response.text.is_empty() => return Ok((response.text, response.language)),
+            Ok(_) => eprintln!("   ⚠️ whisper-server returned no text, using whisper-cpp"),
+            Err(e) => eprintln!("   ⚠️ whisper-server failed, using whisper-cpp: {}", e),
+        }
+    }
+    crate::whisper::run_blocking(move || transcribe_local(&audio_path, &model_id)).await
+}
+
+/// Like `transcribe`, with timestamped segments.
+pub async fn transcribe_segments(audio_path: String, model_id: String) -> Result<(Vec<Segment>, Option<String>), AppError> {
+    if server_for(&model_id).await {
+        match server_inference(&audio_path).await {
+            // Older whisper-server builds answer without segments
+            Ok(response) if !response.segments.is_empty() => return Ok((response.segments, response.language)),
+            Ok(_) => eprintln!("   ⚠️ whisper-server returned no segments, using whisper-cpp"),
+            Err(e) => eprintln!("   ⚠️ whisper-server failed, using whisper-cpp: {}", e),
+        }
+    }
+    crate::whisper::run_blocking(move || transcribe_local_segments(&audio_path, &model_id)).await
+}
+
+/// Run whisper-cpp locally on an audio file. Returns transcript text and detected language.
+fn transcribe_local(
+    audio_path: &str,
+    model_id: &str,
+) -> Result<(String, Option<String>), AppError> {
+    let mut cmd = whisper_command(model_id)?;
+    cmd.args(["-f", audio_path, "--no-timestamps"]);
+
+    let output = cmd.output().map_err(|e| {
+        AppError::new(ErrorKind::TranscriptionFailed, "whisper-cpp failed to run").with_detail(e.to_string())
+    })?;
 
     if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("whisper-cpp error: {}", stderr));
+        return Err(AppError::new(ErrorKind::TranscriptionFailed, "whisper-cpp could not transcribe the audio")
+            .with_detail(String::from_utf8_lossy(&output.stderr)));
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout).to_string();
@@ -681,8 +957,109 @@ pub fn transcribe_local(
     };
 
     if transcript.is_empty() {
-        return Err("whisper-cpp produced no output".to_string());
+        return Err(AppError::new(ErrorKind::TranscriptionFailed, "whisper-cpp produced no output"));
     }
 
     Ok((transcript, language))
 }
+
+/// Run whisper-cpp with timestamps. Returns segments relative to the start
+/// of the clip and the detected language.
+fn transcribe_local_segments(
+    audio_path: &str,
+    model_id: &str,
+) -> Result<(Vec<Segment>, Option<String>), AppError> {
+    let out_dir = tempfile::tempdir()
+        .map_err(|e| AppError::new(ErrorKind::Io, "Could not create a temp dir").with_detail(e.to_string()))?;
+    let out_prefix = out_dir.path().join("transcript");
+
+    let mut cmd = whisper_command(model_id)?;
+    cmd.args(["-f", audio_path, "--output-json", "--no-prints", "-of"]).arg(&out_prefix);
+    let output = cmd.output().map_err(|e| {
+        AppError::new(ErrorKind::TranscriptionFailed, "whisper-cpp failed to run").with_detail(e.to_string())
+    })?;
+    if !output.status.success() {
+        return Err(AppError::new(ErrorKind::TranscriptionFailed, "whisper-cpp could not transcribe the audio")
+            .with_detail(String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let json = std::fs::read_to_string(out_prefix.with_extension("json")).map_err(|e| {
+        AppError::new(ErrorKind::TranscriptionFailed, "whisper-cpp produced no output").with_detail(e.to_string())
+    })?;
+    parse_segments_json(&json)
+}
+
+/// Segments from whisper-cpp's `--output-json` file.
+fn parse_segments_json(json: &str) -> Result<(Vec<Segment>, Option<String>), AppError> {
+    let data: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| AppError::new(ErrorKind::Parse, "Unexpected whisper-cpp output").with_detail(e.to_string()))?;
+    let segments = data["transcription"]
+        .as_array()
+        .map(|items| {
+            items.iter()
+                .map(|item| Segment {
+                    start_ms: item["offsets"]["from"].as_u64().unwrap_or(0),
+                    end_ms: item["offsets"]["to"].as_u64().unwrap_or(0),
+                    text: item["text"].as_str().unwrap_or("").trim().to_string(),
+                })
+                .filter(|s| !s.text.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    let language = data["result"]["language"].as_str().map(str::to_string);
+    Ok((segments, language))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_whisper_server_responses() {
+        let data = serde_json::json!({
+            "task": "transcribe",
+            "language": "english",
+            "detected_language": "en",
+            "text": " Chapter One. The Storm\n",
+            "segments": [
+                { "id": 0, "start": 0.0, "end": 2.5, "text": " Chapter One." },
+                { "id": 1, "start": 2.6, "end": 7.1, "text": " The Storm" }
+            ]
+        });
+        let response = parse_server_response(&data);
+        assert_eq!(response.text, "Chapter One. The Storm");
+        assert_eq!(response.language.as_deref(), Some("en"));
+        assert_eq!(response.segments[1], Segment { start_ms: 2600, end_ms: 7100, text: "The Storm".into() });
+
+        // Older builds: plain json, language by name only
+        let older = parse_server_response(&serde_json::json!({ "text": "Dune", "language": "english" }));
+        assert_eq!((older.segments.len(), older.language.as_deref()), (0, Some("english")));
+    }
+
+    #[tokio::test]
+    async fn server_mode_is_off_without_a_running_server() {
+        // Stopping with nothing started is not an error
+        assert!(running_server().is_none());
+        assert_eq!(stop_server().await, "whisper-server is not running");
+    }
+
+    #[test]
+    fn parses_whisper_cpp_json_segments() {
+        let json = r#"{
+            "result": { "language": "en" },
+            "transcription": [
+                { "timestamps": { "from": "00:00:00,000", "to": "00:00:02,500" },
+                  "offsets": { "from": 0, "to": 2500 }, "text": " Chapter One." },
+                { "offsets": { "from": 2500, "to": 2600 }, "text": " " },
+                { "offsets": { "from": 2600, "to": 7100 }, "text": " The Storm" }
+            ]
+        }"#;
+        let (segments, language) = parse_segments_json(json).unwrap();
+        assert_eq!(language.as_deref(), Some("en"));
+        assert_eq!(segments, vec![
+            Segment { start_ms: 0, end_ms: 2500, text: "Chapter One.".into() },
+            Segment { start_ms: 2600, end_ms: 7100, text: "The Storm".into() },
+        ]);
+        assert_eq!(parse_segments_json("not json").unwrap_err().kind, ErrorKind::Parse);
+    }
+}
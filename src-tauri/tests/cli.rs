@@ -0,0 +1,31 @@
+// src-tauri/tests/cli.rs
+// Runs the CLI binary: with --json, stdout must be exactly one JSON document
+// whatever the library code logs along the way.
+
+use std::process::Command;
+
+#[test]
+fn json_output_is_the_only_thing_on_stdout() {
+    let data = tempfile::tempdir().unwrap();
+    let books = tempfile::tempdir().unwrap();
+    let folder = books.path().join("Brandon Sanderson").join("Mistborn");
+    std::fs::create_dir_all(&folder).unwrap();
+    // Unreadable audio: the scanner skips it, and must not say so on stdout
+    std::fs::write(folder.join("01.mp3"), b"not really audio").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_audiobook-tagger-cli"))
+        .args(["--json", "scan"])
+        .arg(books.path())
+        // Keep the library database and config out of the real data directory
+        .env("HOME", data.path())
+        .env("XDG_DATA_HOME", data.path())
+        .env("APPDATA", data.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap_or_else(|e| panic!("{}: {}", e, stdout));
+    assert!(parsed["groups"].is_array());
+    assert!(parsed["total_files"].is_u64());
+}
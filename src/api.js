@@ -41,6 +41,7 @@ const DEFAULT_CONFIG = {
   abs_library_id: '',
   openai_api_key: null,
   anthropic_api_key: null,
+  transcription_api_key: null,
   backup_tags: true,
   genre_enforcement: true,
   performance_preset: 'balanced',
@@ -55,10 +56,28 @@ const DEFAULT_CONFIG = {
   custom_providers: [],
 };
 
+// In the desktop app, API keys and tokens are stored by the backend (OS
+// keyring or encrypted file) and never written to localStorage. They are
+// kept here in memory after get_config so synchronous readers still see them.
+const SECRET_FIELDS = ['abs_api_token', 'openai_api_key', 'anthropic_api_key', 'transcription_api_key'];
+let secretCache = {};
+
+function withoutSecrets(config) {
+  const copy = { ...config };
+  for (const field of SECRET_FIELDS) delete copy[field];
+  return copy;
+}
+
+function cacheSecrets(config) {
+  for (const field of SECRET_FIELDS) {
+    if (field in config) secretCache[field] = config[field];
+  }
+}
+
 export function getLocalConfig() {
   try {
     const stored = localStorage.getItem(CONFIG_KEY);
-    if (stored) return { ...DEFAULT_CONFIG, ...JSON.parse(stored) };
+    if (stored) return { ...DEFAULT_CONFIG, ...JSON.parse(stored), ...secretCache };
   } catch (e) {
     console.warn('Failed to load config:', e);
   }
@@ -121,6 +140,11 @@ const TAURI_COMMANDS = new Set([
   'extract_audio_intro',
   'batch_extract_audio_intros',
   'cancel_audio_extraction',
+  'transcript_cache_list',
+  'transcript_cache_get',
+  'transcript_cache_purge',
+  'transcript_cache_export',
+  'transcript_cache_get_disk_usage',
   'whisper_local_get_status',
   'whisper_local_get_model_presets',
   'whisper_local_install',
@@ -128,6 +152,33 @@ const TAURI_COMMANDS = new Set([
   'whisper_local_delete_model',
   'whisper_local_get_disk_usage',
   'whisper_local_uninstall',
+  'whisper_server_start',
+  'whisper_server_stop',
+  'list_jobs',
+  'enqueue_job',
+  'cancel_job',
+  'pause_job',
+  'resume_job',
+  'get_job_results',
+  'clear_finished_jobs',
+  'abs_push_preview',
+  'get_abs_chapters',
+  'compare_abs_chapters',
+  'push_abs_chapters',
+  'embed_abs_chapters',
+  'library_query',
+  'library_get_group',
+  'library_save_metadata',
+  'library_get_history',
+  'library_diff_versions',
+  'library_revert_fields',
+  'library_get_provenance',
+  'library_set_chapters',
+  'library_get_chapters',
+  'library_set_cover',
+  'library_get_cover',
+  'library_set_push_status',
+  'library_get_push_status',
 ]);
 
 // ============================================================================
@@ -136,14 +187,36 @@ const TAURI_COMMANDS = new Set([
 // All existing component code calls this — we just changed what's under the hood.
 // ============================================================================
 
+// Rust commands reject with { kind, message, detail?, hint? }. Turn that into
+// an Error so existing `err.message` / String(err) handling keeps working,
+// with the structured fields attached for callers that want them.
+function toBackendError(err) {
+  if (err && typeof err === 'object' && typeof err.message === 'string' && err.kind) {
+    const e = new Error(err.message);
+    e.kind = err.kind;
+    e.detail = err.detail;
+    e.hint = err.hint;
+    return e;
+  }
+  return err instanceof Error ? err : new Error(String(err));
+}
+
+async function invokeTauri(cmd, args = {}) {
+  const invoke = await getTauriInvoke();
+  try {
+    return await invoke(cmd, args);
+  } catch (err) {
+    throw toBackendError(err);
+  }
+}
+
 export async function callBackend(cmd, args = {}) {
   // In Tauri, check if this command has a native override
   if (isTauri() && TAURI_COMMANDS.has(cmd)) {
-    const invoke = await getTauriInvoke();
-    return invoke(cmd, args);
+    return invokeTauri(cmd, args);
   }
 
-  const handler = HANDLERS[cmd];
+  const handler = (isTauri() && TAURI_HANDLERS[cmd]) || HANDLERS[cmd];
   if (handler) {
     return handler(args);
   }
@@ -225,6 +298,15 @@ function convertDnaToTags(dna) {
  * - Genres: enforced (max 3, validated)
  * - Tags: at TOP LEVEL, DNA-aware enforcement
  */
+// Genre and tag policies, applied before anything is pushed to ABS.
+function enforcePushPolicies(meta) {
+  const config = getLocalConfig();
+  const genres = config.genre_enforcement !== false
+    ? enforceGenrePolicyWithSplit(meta.genres || [])
+    : (meta.genres || []).slice(0, 3);
+  return { genres, tags: enforceTagPolicyWithDna(meta.tags || []) };
+}
+
 function buildAbsPayload(meta) {
   const metadata = {};
 
@@ -252,12 +334,8 @@ function buildAbsPayload(meta) {
   }
 
   // Genres: enforce policy (max 3, validated against approved list) if enabled
-  if (meta.genres && meta.genres.length > 0) {
-    const config = getLocalConfig();
-    metadata.genres = config.genre_enforcement !== false
-      ? enforceGenrePolicyWithSplit(meta.genres)
-      : meta.genres.slice(0, 3);
-  }
+  const { genres, tags } = enforcePushPolicies(meta);
+  if (meta.genres && meta.genres.length > 0) metadata.genres = genres;
 
   // Series: use all_series if available, fall back to series/sequence
   // NO id field — let ABS match by name to avoid duplicates
@@ -276,9 +354,8 @@ function buildAbsPayload(meta) {
   }
 
   // Tags: TOP LEVEL (not inside metadata), DNA-aware enforcement
-  const finalTags = enforceTagPolicyWithDna(meta.tags || []);
   const payload = { metadata };
-  if (finalTags.length > 0) payload.tags = finalTags;
+  if (tags.length > 0) payload.tags = tags;
 
   return payload;
 }
@@ -287,6 +364,83 @@ function buildAbsPayload(meta) {
 // COMMAND HANDLERS — client-side implementations
 // ============================================================================
 
+// Desktop-only handlers that wrap a Rust command with client-side work.
+const TAURI_HANDLERS = {
+  // Settings live in the backend store. On first run after upgrading it is
+  // empty, so the existing localStorage config (secrets included) is
+  // imported once; from then on localStorage only holds a secret-free copy.
+  get_config: async () => {
+    let stored = await invokeTauri('get_config');
+    if (!stored) {
+      await invokeTauri('save_config', { config: getLocalConfig() });
+      stored = (await invokeTauri('get_config')) || {};
+    }
+    secretCache = {};
+    cacheSecrets(stored);
+    saveLocalConfig(withoutSecrets({ ...getLocalConfig(), ...stored }));
+    return getLocalConfig();
+  },
+  save_config: async (args) => {
+    const config = args.config || args;
+    await invokeTauri('save_config', { config });
+    cacheSecrets(config);
+    saveLocalConfig(withoutSecrets(config));
+    return {};
+  },
+
+  // Pushes run as a backend job: only changed fields are written, requests
+  // are throttled, and an interrupted push resumes on the next launch.
+  push_abs_updates: async (args) => {
+    const items = (args.request?.items || args.items || []).map(item => {
+      const meta = item.metadata || item;
+      return {
+        abs_id: item.abs_id || item.group_id || item.id || '',
+        group_id: item.group_id || null,
+        metadata: { ...meta, ...enforcePushPolicies(meta) },
+      };
+    });
+    const options = { dry_run: !!args.dryRun };
+    const queued = await invokeTauri('enqueue_job', { spec: { type: 'abs_push', items, options } });
+    const job = await waitForJob(queued.id);
+    if (job.state === 'failed') throw toBackendError(job.error);
+    const outcomes = await invokeTauri('get_job_results', { jobId: job.id });
+
+    const done = outcomes.filter(Boolean);
+    return {
+      updated: done.filter(o => o.status === 'updated').length,
+      unchanged: done.filter(o => o.status === 'unchanged').length,
+      unmatched: done.filter(o => o.error?.kind === 'abs_not_found').map(o => o.abs_id),
+      failed: done
+        .filter(o => o.status === 'failed' && o.error?.kind !== 'abs_not_found')
+        .map(o => ({ id: o.abs_id, error: o.error?.message })),
+      outcomes: done,
+    };
+  },
+
+  // Chapter naming transcribes a window per chapter, so it runs as a job;
+  // resolves with the proposal (titles are not saved anywhere).
+  propose_chapter_titles: async (args) => {
+    const request = args.request || args;
+    const queued = await invokeTauri('enqueue_job', { spec: { type: 'chapter_titles', request } });
+    const job = await waitForJob(queued.id);
+    if (job.state === 'failed') throw toBackendError(job.error);
+    if (job.state === 'cancelled') throw new Error('Chapter naming was cancelled');
+    const [titles] = await invokeTauri('get_job_results', { jobId: job.id });
+    return titles;
+  },
+};
+
+// Resolve with a job's final state once it completes, fails or is cancelled.
+async function waitForJob(jobId, pollMs = 1000) {
+  for (;;) {
+    const jobs = await invokeTauri('list_jobs');
+    const job = jobs.find(j => j.id === jobId);
+    if (!job) throw new Error('Job is no longer in the queue');
+    if (['completed', 'failed', 'cancelled'].includes(job.state)) return job;
+    await new Promise(resolve => setTimeout(resolve, pollMs));
+  }
+}
+
 const HANDLERS = {
   // === Config (localStorage) ===
   get_config: () => getLocalConfig(),
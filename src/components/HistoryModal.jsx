@@ -0,0 +1,171 @@
+import { useState, useEffect } from 'react';
+import { callBackend } from '../api';
+import { X, History, RotateCcw, AlertTriangle } from 'lucide-react';
+
+const SOURCE_LABELS = {
+  scan: 'Scan',
+  ai: 'AI',
+  whisper: 'Whisper',
+  manual: 'Manual',
+  push: 'Pushed to ABS',
+  revert: 'Revert',
+};
+
+// Field values come straight from BookMetadata: strings, string lists,
+// people ({name, role}) and series entries ({name, position})
+function formatValue(value) {
+  if (value === null || value === undefined || value === '') return '—';
+  if (Array.isArray(value)) {
+    if (value.length === 0) return '—';
+    return value
+      .map(v => (typeof v === 'object' ? [v.name, v.position && `#${v.position}`].filter(Boolean).join(' ') : v))
+      .join(', ');
+  }
+  return String(value);
+}
+
+export function HistoryModal({ isOpen, onClose, group, onReverted }) {
+  const [versions, setVersions] = useState([]);
+  const [selected, setSelected] = useState(null);
+  const [changes, setChanges] = useState([]);
+  const [loading, setLoading] = useState(true);
+  const [reverting, setReverting] = useState(null);
+  const [error, setError] = useState(null);
+
+  // A rescan keeps what it read as a version that isn't current
+  const latest = versions.find(v => v.current)?.version;
+
+  const loadHistory = async () => {
+    setLoading(true);
+    setError(null);
+    try {
+      const history = await callBackend('library_get_history', { groupId: group.id });
+      setVersions(history);
+      // Compare against the version before the current one by default
+      const current = history.findIndex(v => v.current);
+      setSelected(current > 0 ? history[current - 1].version : null);
+    } catch (err) {
+      setError(err.message || String(err));
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  useEffect(() => {
+    if (isOpen && group) loadHistory();
+  }, [isOpen, group?.id]);
+
+  useEffect(() => {
+    if (!selected || !latest || selected === latest) {
+      setChanges([]);
+      return;
+    }
+    callBackend('library_diff_versions', { groupId: group.id, from: selected, to: latest })
+      .then(setChanges)
+      .catch(err => setError(err.message || String(err)));
+  }, [selected, latest]);
+
+  const handleRevert = async (change) => {
+    setReverting(change.field);
+    try {
+      await callBackend('library_revert_fields', { groupId: group.id, version: selected, fields: [change.field] });
+      onReverted?.(group.id, { [change.field]: change.before });
+      await loadHistory();
+    } catch (err) {
+      setError(err.message || String(err));
+    } finally {
+      setReverting(null);
+    }
+  };
+
+  if (!isOpen) return null;
+
+  return (
+    <div className="fixed inset-0 bg-black/50 flex items-center justify-center z-50 p-4">
+      <div className="bg-neutral-900 rounded-xl shadow-2xl max-w-3xl w-full max-h-[80vh] flex flex-col overflow-hidden">
+        <div className="p-6 pb-4 flex items-center gap-3 border-b border-neutral-800">
+          <History className="w-5 h-5 text-gray-400" />
+          <div className="flex-1 min-w-0">
+            <h3 className="text-lg font-semibold text-gray-100">Metadata history</h3>
+            <p className="text-sm text-gray-500 truncate">{group?.metadata?.title || group?.group_name}</p>
+          </div>
+          <button onClick={onClose} className="p-1 hover:bg-neutral-800 rounded-lg transition-colors">
+            <X className="w-5 h-5 text-gray-400" />
+          </button>
+        </div>
+
+        {error && (
+          <div className="mx-6 mt-4 p-3 rounded-lg bg-red-500/10 text-red-400 text-sm flex items-center gap-2">
+            <AlertTriangle className="w-4 h-4 flex-shrink-0" />
+            {error}
+          </div>
+        )}
+
+        <div className="flex flex-1 min-h-0">
+          <div className="w-56 border-r border-neutral-800 overflow-y-auto p-3 space-y-1">
+            {loading && <div className="text-sm text-gray-500 p-2">Loading…</div>}
+            {!loading && versions.length === 0 && (
+              <div className="text-sm text-gray-500 p-2">This book is not in the library yet.</div>
+            )}
+            {[...versions].reverse().map(v => (
+              <button
+                key={v.version}
+                onClick={() => setSelected(v.version)}
+                disabled={v.version === latest}
+                className={`w-full text-left px-3 py-2 rounded-lg text-sm transition-colors ${
+                  v.version === selected ? 'bg-neutral-700 text-white' : 'text-gray-300 hover:bg-neutral-800'
+                } ${v.version === latest ? 'cursor-default' : ''}`}
+              >
+                <div className="font-medium">
+                  v{v.version} · {SOURCE_LABELS[v.source] || v.source}
+                  {v.version === latest && <span className="ml-1 text-xs text-gray-500">(current)</span>}
+                </div>
+                <div className="text-xs text-gray-500">{new Date(v.created_at * 1000).toLocaleString()}</div>
+              </button>
+            ))}
+          </div>
+
+          <div className="flex-1 overflow-y-auto p-4">
+            {selected && changes.length === 0 && !loading && (
+              <div className="text-sm text-gray-500">No differences from the current metadata.</div>
+            )}
+            {!selected && !loading && versions.length > 0 && (
+              <div className="text-sm text-gray-500">Only one version so far.</div>
+            )}
+            {changes.length > 0 && (
+              <table className="w-full text-sm">
+                <thead>
+                  <tr className="text-left text-xs text-gray-500 uppercase tracking-wider">
+                    <th className="pb-2 pr-3">Field</th>
+                    <th className="pb-2 pr-3">v{selected}</th>
+                    <th className="pb-2 pr-3">Current</th>
+                    <th className="pb-2" />
+                  </tr>
+                </thead>
+                <tbody>
+                  {changes.map(change => (
+                    <tr key={change.field} className="border-t border-neutral-800 align-top">
+                      <td className="py-2 pr-3 text-gray-400">{change.field}</td>
+                      <td className="py-2 pr-3 text-gray-300 break-words">{formatValue(change.before)}</td>
+                      <td className="py-2 pr-3 text-white break-words">{formatValue(change.after)}</td>
+                      <td className="py-2 text-right">
+                        <button
+                          onClick={() => handleRevert(change)}
+                          disabled={reverting !== null}
+                          className="px-2 py-1 bg-neutral-800 hover:bg-neutral-700 text-gray-200 rounded text-xs flex items-center gap-1 disabled:opacity-50"
+                        >
+                          <RotateCcw className="w-3 h-3" />
+                          {reverting === change.field ? 'Reverting…' : 'Revert'}
+                        </button>
+                      </td>
+                    </tr>
+                  ))}
+                </tbody>
+              </table>
+            )}
+          </div>
+        </div>
+      </div>
+    </div>
+  );
+}
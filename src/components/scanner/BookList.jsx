@@ -197,6 +197,8 @@ export function BookList({
   hasAbsConnection = false,
   onImportFromAbs,
   onNavigateToSettings,
+  libraryTotal = 0, // books in the library database, loaded or not
+  onLoadMore,
 }) {
   const [coverCache, setCoverCache] = useState({});
   const [visibleRange, setVisibleRange] = useState({ start: 0, end: 30 });
@@ -1038,6 +1040,19 @@ export function BookList({
           </div>
         </div>
         )}
+
+        {/* Further pages of the library database */}
+        {onLoadMore && libraryTotal > groups.length && (
+          <div className="flex justify-center p-4">
+            <button
+              onClick={onLoadMore}
+              disabled={scanning}
+              className="px-4 py-2 text-sm text-gray-300 bg-neutral-800 hover:bg-neutral-700 rounded-lg transition-colors disabled:opacity-50"
+            >
+              Load more ({groups.length} of {libraryTotal})
+            </button>
+          </div>
+        )}
       </div>
     </div>
   );
@@ -2,8 +2,9 @@ import { useState, useEffect, useRef } from 'react';
 import { callBackend } from '../../api';
 import { proxyFetch } from '../../lib/proxy';
 import { performLookup } from './performLookup';
-import { Book, Edit, X, Database, Folder, Bot, FileAudio, Globe, Music, Library, FolderOpen, Search } from 'lucide-react';
+import { Book, Edit, X, Database, Folder, Bot, FileAudio, Globe, Music, Library, FolderOpen, Search, History } from 'lucide-react';
 import { useToast } from '../Toast';
+import { isTauri } from '../../lib/platform.js';
 
 // Source badge configuration
 const SOURCE_CONFIG = {
@@ -159,7 +160,7 @@ function changedClass(group, field) {
   return isChanged(group, field) ? 'ring-1 ring-amber-500/40 bg-amber-500/5' : '';
 }
 
-export function MetadataPanel({ group, onEdit, onInlineEdit }) {
+export function MetadataPanel({ group, onEdit, onInlineEdit, onShowHistory }) {
   const toast = useToast();
   const [coverData, setCoverData] = useState(null);
   const [coverUrl, setCoverUrl] = useState(null);
@@ -169,6 +170,9 @@ export function MetadataPanel({ group, onEdit, onInlineEdit }) {
   const [absChapters, setAbsChapters] = useState([]);
   const [loadingChapters, setLoadingChapters] = useState(false);
   const [chaptersError, setChaptersError] = useState(null);
+  const [chaptersDirty, setChaptersDirty] = useState(false);
+  const [chapterComparison, setChapterComparison] = useState(null);
+  const [chapterAction, setChapterAction] = useState(null); // 'compare' | 'push' | 'embed' | null
 
   // Inline editing state
   const [editingField, setEditingField] = useState(null); // 'title' | 'author' | 'narrator' | 'description' | null
@@ -314,6 +318,8 @@ export function MetadataPanel({ group, onEdit, onInlineEdit }) {
       setActiveTab('about');
       setDescriptionExpanded(false);
       setAbsChapters([]);
+      setChaptersDirty(false);
+      setChapterComparison(null);
       setChaptersError(null);
     } else {
       if (blobUrlRef.current) {
@@ -323,6 +329,8 @@ export function MetadataPanel({ group, onEdit, onInlineEdit }) {
       setCoverUrl(null);
       setCoverData(null);
       setAbsChapters([]);
+      setChaptersDirty(false);
+      setChapterComparison(null);
     }
   }, [group?.id, refreshTrigger]);
 
@@ -350,6 +358,88 @@ export function MetadataPanel({ group, onEdit, onInlineEdit }) {
     }
   };
 
+  // Chapter round trip with ABS (desktop only: needs the Rust ABS client and FFmpeg)
+  const localChapterFile = group?.files?.[0]?.path || null;
+
+  const editChapterTitle = (idx, title) => {
+    setAbsChapters(prev => prev.map((c, i) => (i === idx ? { ...c, title } : c)));
+    setChaptersDirty(true);
+  };
+
+  const compareChapters = async () => {
+    setChapterAction('compare');
+    try {
+      const result = await callBackend('compare_abs_chapters', { absId: group.id, filePath: localChapterFile });
+      setChapterComparison(result);
+      if (result.matches) toast.success('Chapters Match', 'ABS and the file agree');
+    } catch (error) {
+      toast.error('Compare Failed', error.message || String(error));
+    } finally {
+      setChapterAction(null);
+    }
+  };
+
+  // Take timings and titles from the file, to review before pushing
+  const useFileChapters = () => {
+    const local = (chapterComparison?.rows || []).map(r => r.local).filter(Boolean);
+    setAbsChapters(local.map((c, id) => ({ id, title: c.title, start: c.start_ms / 1000, end: c.end_ms / 1000 })));
+    setChaptersDirty(true);
+    setChapterComparison(null);
+  };
+
+  const pushChapters = async () => {
+    setChapterAction('push');
+    try {
+      const chapters = absChapters.map(c => ({
+        title: c.title,
+        start_ms: Math.round(c.start * 1000),
+        end_ms: Math.round(c.end * 1000),
+      }));
+      const count = await callBackend('push_abs_chapters', { absId: group.id, chapters });
+      setChaptersDirty(false);
+      toast.success('Chapters Pushed', `${count} chapters saved to AudiobookShelf`);
+    } catch (error) {
+      toast.error('Push Failed', error.message || String(error));
+    } finally {
+      setChapterAction(null);
+    }
+  };
+
+  const embedChapters = async () => {
+    setChapterAction('embed');
+    try {
+      const count = await callBackend('embed_abs_chapters', { absId: group.id, filePath: localChapterFile });
+      setChapterComparison(null);
+      toast.success('Chapters Embedded', `${count} chapters written to ${group.files[0].filename || 'the file'}`);
+    } catch (error) {
+      toast.error('Embed Failed', error.message || String(error));
+    } finally {
+      setChapterAction(null);
+    }
+  };
+
+  // Transcribe the start of each chapter and take the announced headings
+  // as titles, to review before pushing
+  const nameChaptersFromAudio = async () => {
+    setChapterAction('name');
+    try {
+      const files = (group.files || []).map(f => f.path).filter(Boolean);
+      const chapters = absChapters.map(c => ({
+        title: c.title,
+        start_ms: Math.round(c.start * 1000),
+        end_ms: Math.round(c.end * 1000),
+      }));
+      const result = await callBackend('propose_chapter_titles', { request: { files, chapters } });
+      setAbsChapters(result.chapters.map((c, id) => ({ id, title: c.title, start: c.start_ms / 1000, end: c.end_ms / 1000 })));
+      if (result.named > 0) setChaptersDirty(true);
+      toast.success('Chapters Named', `Headings found for ${result.named} of ${result.chapters.length} chapters`);
+    } catch (error) {
+      toast.error('Naming Failed', error.message || String(error));
+    } finally {
+      setChapterAction(null);
+    }
+  };
+
   // Format time from seconds to HH:MM:SS or MM:SS
   const formatTime = (seconds) => {
     if (!seconds && seconds !== 0) return '--:--';
@@ -586,6 +676,17 @@ export function MetadataPanel({ group, onEdit, onInlineEdit }) {
                 Edit
               </button>
             )}
+
+            {/* Versions recorded in the library database (desktop only) */}
+            {onShowHistory && isTauri() && (
+              <button
+                onClick={() => onShowHistory(group)}
+                className="mt-4 px-4 py-2 bg-neutral-800 hover:bg-neutral-700 text-white rounded-lg transition-colors font-medium flex items-center gap-2 text-sm"
+              >
+                <History className="w-4 h-4" />
+                History
+              </button>
+            )}
           </div>
         </div>
       </div>
@@ -783,21 +884,86 @@ export function MetadataPanel({ group, onEdit, onInlineEdit }) {
               </div>
             ) : absChapters.length > 0 ? (
               <>
-                <div className="text-xs font-semibold text-gray-500 uppercase tracking-wider mb-3">
-                  Chapters ({absChapters.length})
+                <div className="flex items-center gap-2 mb-3">
+                  <div className="text-xs font-semibold text-gray-500 uppercase tracking-wider flex-1">
+                    Chapters ({absChapters.length})
+                  </div>
+                  {isTauri() && (
+                    <>
+                      {localChapterFile && (
+                        <button
+                          onClick={compareChapters}
+                          disabled={!!chapterAction}
+                          className="px-2 py-1 bg-neutral-800 hover:bg-neutral-700 disabled:opacity-50 text-gray-300 rounded text-xs"
+                        >
+                          {chapterAction === 'compare' ? 'Comparing...' : 'Compare with file'}
+                        </button>
+                      )}
+                      {localChapterFile && (
+                        <button
+                          onClick={embedChapters}
+                          disabled={!!chapterAction || chaptersDirty}
+                          title={chaptersDirty ? 'Push your edits first' : 'Write the ABS chapters into the local file'}
+                          className="px-2 py-1 bg-neutral-800 hover:bg-neutral-700 disabled:opacity-50 text-gray-300 rounded text-xs"
+                        >
+                          {chapterAction === 'embed' ? 'Embedding...' : 'Embed in file'}
+                        </button>
+                      )}
+                      {localChapterFile && (
+                        <button
+                          onClick={nameChaptersFromAudio}
+                          disabled={!!chapterAction}
+                          title="Transcribe the start of each chapter and use the spoken headings as titles"
+                          className="px-2 py-1 bg-neutral-800 hover:bg-neutral-700 disabled:opacity-50 text-gray-300 rounded text-xs"
+                        >
+                          {chapterAction === 'name' ? 'Listening...' : 'Name from audio'}
+                        </button>
+                      )}
+                      <button
+                        onClick={pushChapters}
+                        disabled={!!chapterAction || !chaptersDirty}
+                        className="px-2 py-1 bg-indigo-600 hover:bg-indigo-500 disabled:opacity-50 text-white rounded text-xs"
+                      >
+                        {chapterAction === 'push' ? 'Pushing...' : 'Push to ABS'}
+                      </button>
+                    </>
+                  )}
                 </div>
+                {chapterComparison && !chapterComparison.matches && (
+                  <div className="flex items-center gap-3 p-3 mb-2 bg-amber-500/10 border border-amber-500/30 rounded-lg text-xs text-amber-300">
+                    <span className="flex-1">
+                      The file has {chapterComparison.rows.filter(r => r.local).length} chapters;
+                      {' '}{chapterComparison.rows.filter(r => r.title_differs || !r.abs || !r.local || Math.abs(r.start_delta_ms || 0) > 2000).length} differ from ABS.
+                    </span>
+                    <button onClick={useFileChapters} className="px-2 py-1 bg-amber-600/30 hover:bg-amber-600/50 rounded">
+                      Use file chapters
+                    </button>
+                  </div>
+                )}
                 <div className="space-y-1">
-                  {absChapters.map((chapter, idx) => (
+                  {absChapters.map((chapter, idx) => {
+                    const row = chapterComparison?.rows?.[idx];
+                    const differs = row && (row.title_differs || !row.local || Math.abs(row.start_delta_ms || 0) > 2000);
+                    return (
                     <div
                       key={chapter.id}
-                      className="flex items-center gap-3 p-3 bg-neutral-900 rounded-lg border border-neutral-800 hover:border-neutral-700 transition-colors"
+                      className={`flex items-center gap-3 p-3 bg-neutral-900 rounded-lg border transition-colors ${differs ? 'border-amber-600/50' : 'border-neutral-800 hover:border-neutral-700'}`}
+                      title={differs && row.local ? `File: ${row.local.title} at ${formatTime(row.local.start_ms / 1000)}` : undefined}
                     >
                       <span className="w-8 h-8 flex-shrink-0 flex items-center justify-center bg-indigo-500/20 text-indigo-400 rounded text-sm font-bold">
                         {idx + 1}
                       </span>
-                      <span className="text-gray-300 text-sm flex-1 truncate">
-                        {chapter.title}
-                      </span>
+                      {isTauri() ? (
+                        <input
+                          value={chapter.title}
+                          onChange={(e) => editChapterTitle(idx, e.target.value)}
+                          className="text-gray-300 text-sm flex-1 min-w-0 bg-transparent focus:outline-none focus:bg-neutral-800 rounded px-1"
+                        />
+                      ) : (
+                        <span className="text-gray-300 text-sm flex-1 truncate">
+                          {chapter.title}
+                        </span>
+                      )}
                       <span className="text-gray-500 text-xs font-mono flex-shrink-0">
                         {formatTime(chapter.start)}
                       </span>
@@ -806,7 +972,8 @@ export function MetadataPanel({ group, onEdit, onInlineEdit }) {
                         {formatTime(chapter.end)}
                       </span>
                     </div>
-                  ))}
+                    );
+                  })}
                 </div>
               </>
             ) : (
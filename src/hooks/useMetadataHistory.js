@@ -0,0 +1,60 @@
+// src/hooks/useMetadataHistory.js
+import { useCallback, useEffect, useRef, useState } from 'react';
+import { callBackend } from '../api';
+import { isTauri } from '../lib/platform.js';
+
+/**
+ * useMetadataHistory - Records metadata edits as versions in the library
+ * database so they show up in a book's history.
+ *
+ * Edits are applied through setGroups, so the changed metadata is only
+ * visible after React re-renders. Callers bracket an edit with begin/finish;
+ * once the groups update, every group whose metadata changed in between is
+ * saved with the given source ('ai', 'whisper' or 'manual').
+ *
+ *   history.begin('titles');
+ *   ...setGroups(...)...
+ *   history.finish('titles', 'ai');
+ */
+export function useMetadataHistory(groups) {
+  const groupsRef = useRef(groups);
+  groupsRef.current = groups;
+
+  const snapshotsRef = useRef({});
+  const pendingRef = useRef([]);
+  const [flushes, setFlushes] = useState(0);
+
+  const begin = useCallback((op) => {
+    const snapshot = new Map();
+    groupsRef.current.forEach(g => snapshot.set(g.id, g.metadata));
+    snapshotsRef.current[op] = snapshot;
+  }, []);
+
+  const finish = useCallback((op, source) => {
+    const snapshot = snapshotsRef.current[op];
+    delete snapshotsRef.current[op];
+    if (!snapshot) return;
+    pendingRef.current.push({ snapshot, source });
+    setFlushes(n => n + 1);
+  }, []);
+
+  useEffect(() => {
+    if (!flushes || !isTauri()) {
+      pendingRef.current = [];
+      return;
+    }
+    const pending = pendingRef.current;
+    pendingRef.current = [];
+
+    for (const { snapshot, source } of pending) {
+      // Books imported from ABS are not in the library and have no history
+      const changed = groups.filter(g => snapshot.has(g.id) && snapshot.get(g.id) !== g.metadata);
+      for (const group of changed) {
+        callBackend('library_save_metadata', { groupId: group.id, metadata: group.metadata, source })
+          .catch(error => console.warn(`History not recorded for ${group.id}:`, error));
+      }
+    }
+  }, [flushes, groups]);
+
+  return { begin, finish };
+}
@@ -13,6 +13,7 @@ const MODAL_NAMES = [
   'validation',
   'author',
   'batchFix',
+  'history',
 ];
 
 // Build initial state: every modal closed, no associated data
@@ -25,6 +26,7 @@ function buildInitialState() {
     // Associated data keyed by modal name
     data: {
       edit: { group: null },
+      history: { group: null },
       push: { groups: [] },
       batchFix: {
         pending: { validation: 0, author: 0, series: 0 },
@@ -41,6 +43,7 @@ const initialState = buildInitialState();
 // Default data values used when closing a modal (to reset associated data)
 const DATA_DEFAULTS = {
   edit: { group: null },
+  history: { group: null },
   push: { groups: [] },
   batchFix: {
     pending: { validation: 0, author: 0, series: 0 },
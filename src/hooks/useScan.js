@@ -2,10 +2,18 @@
 import { useState, useCallback, useRef, useEffect } from 'react';
 import { callBackend, subscribe, pickPath } from '../api';
 import { useApp } from '../context/AppContext';
+import { isTauri } from '../lib/platform.js';
+
+// Books per library_query page. Scanned books live in the backend's library
+// database; the list only holds the pages it has asked for.
+const LIBRARY_PAGE_SIZE = 200;
 
 export function useScan() {
-  const { setGroups } = useApp();
+  const { groups, setGroups } = useApp();
   const [scanning, setScanning] = useState(false);
+  const [libraryTotal, setLibraryTotal] = useState(0);
+  const loadedRef = useRef(0);
+  const libraryLoadedRef = useRef(false);
   const [scanProgress, setScanProgress] = useState({
     current: 0,
     total: 0,
@@ -29,6 +37,32 @@ export function useScan() {
     };
   }, []);
 
+  // Replace the list with the first page of the library database
+  const loadLibrary = useCallback(async () => {
+    const page = await callBackend('library_query', { filter: { offset: 0, limit: LIBRARY_PAGE_SIZE } });
+    loadedRef.current = page.groups.length;
+    setLibraryTotal(page.total);
+    setGroups(page.groups);
+  }, [setGroups]);
+
+  // Append the next page of the library database
+  const loadMoreBooks = useCallback(async () => {
+    const page = await callBackend('library_query', { filter: { offset: loadedRef.current, limit: LIBRARY_PAGE_SIZE } });
+    loadedRef.current += page.groups.length;
+    setLibraryTotal(page.total);
+    setGroups(prev => {
+      const known = new Set(prev.map(g => g.id));
+      return [...prev, ...page.groups.filter(g => !known.has(g.id))];
+    });
+  }, [setGroups]);
+
+  // Show the books earlier scans stored, once, unless the list already has some
+  useEffect(() => {
+    if (!isTauri() || libraryLoadedRef.current || groups?.length > 0) return;
+    libraryLoadedRef.current = true;
+    loadLibrary().catch(error => console.warn('Library load failed:', error));
+  }, [groups, loadLibrary]);
+
   const calculateETA = useCallback(() => {
     const { current, total, startTime, filesPerSecond } = scanProgress;
     
@@ -124,8 +158,13 @@ export function useScan() {
           progressIntervalRef.current = null;
         }
 
-        // Simple direct set - replace all groups
+        // The scanned folders are the working set. The backend has already
+        // swapped in their library ids and kept edits from earlier scans, so
+        // there are no further library pages to load.
         if (result && result.groups) {
+          libraryLoadedRef.current = true;
+          loadedRef.current = 0;
+          setLibraryTotal(0);
           setGroups(result.groups);
         }
 
@@ -816,6 +855,8 @@ export function useScan() {
     scanning,
     scanProgress,
     calculateETA,
+    libraryTotal,
+    loadMoreBooks,
     handleScan,
     handleImport,
     handleImportFromAbs,
@@ -5,6 +5,7 @@ import { MetadataPanel } from '../components/scanner/MetadataPanel';
 import { ActionBar } from '../components/scanner/ActionBar';
 import { ProgressBar } from '../components/scanner/ProgressBar';
 import { EditMetadataModal } from '../components/EditMetadataModal';
+import { HistoryModal } from '../components/HistoryModal';
 import { BulkEditModal } from '../components/BulkEditModal';
 import { BulkCoverAssignment } from '../components/BulkCoverAssignment';
 import { RenamePreviewModal } from '../components/RenamePreviewModal';
@@ -21,8 +22,10 @@ import { useScan } from '../hooks/useScan';
 import { useFileSelection } from '../hooks/useFileSelection';
 import { useTagOperations } from '../hooks/useTagOperations';
 import { useBatchOperations } from '../hooks/useBatchOperations';
+import { useMetadataHistory } from '../hooks/useMetadataHistory';
 import { useModals } from '../hooks/useModals';
 import { useApp } from '../context/AppContext';
+import { isTauri } from '../lib/platform.js';
 import { severityForKind } from '../lib/errorDetail';
 import { summarizeBatch, scrollToFirstErrorGroup } from '../lib/batchToast';
 
@@ -39,7 +42,22 @@ export function ScannerPage({ onNavigateToSettings, activeTab, navigateTo, logoS
 
   // Consolidated modal and batch operation state
   const modals = useModals();
-  const batch = useBatchOperations({ dnaEnabledDefault: !config?.local_skip_dna });
+  const operations = useBatchOperations({ dnaEnabledDefault: !config?.local_skip_dna });
+
+  // Record every batch fix in the library history once its results land;
+  // the audio check applies Whisper findings, every other batch AI fixes
+  const history = useMetadataHistory(groups);
+  const batch = {
+    ...operations,
+    start: (op, initial) => {
+      history.begin(op);
+      operations.start(op, initial);
+    },
+    end: (op, delayMs) => {
+      operations.end(op, delayMs);
+      history.finish(op, op === 'audio_check' ? 'whisper' : 'ai');
+    },
+  };
 
   // Toast notifications
   const toast = useToast();
@@ -98,6 +116,8 @@ export function ScannerPage({ onNavigateToSettings, activeTab, navigateTo, logoS
     scanning,
     scanProgress,
     calculateETA,
+    libraryTotal,
+    loadMoreBooks,
     handleScan,
     handleImport,
     handleImportFromAbs,
@@ -109,6 +129,28 @@ export function ScannerPage({ onNavigateToSettings, activeTab, navigateTo, logoS
     cancelScan
   } = useScan();
 
+  // Library database notices from scans, e.g. a scan that couldn't be saved
+  useEffect(() => {
+    if (!isTauri()) return;
+    let unlisten = null;
+    let cancelled = false;
+    import('@tauri-apps/api/event')
+      .then(({ listen }) => listen('library_progress', (event) => {
+        const { stage, status } = event.payload;
+        if (stage === 'error') {
+          toast.warning('Library', status);
+        } else {
+          toast.info('Library', status);
+        }
+      }))
+      .then(fn => { if (cancelled) fn(); else unlisten = fn; })
+      .catch(() => { /* not in Tauri */ });
+    return () => {
+      cancelled = true;
+      if (unlisten) unlisten();
+    };
+  }, []);
+
   // Keep selectedGroup in sync when groups are updated (e.g., after rescan)
   useEffect(() => {
     if (selectedGroup) {
@@ -257,10 +299,21 @@ export function ScannerPage({ onNavigateToSettings, activeTab, navigateTo, logoS
     modals.open('edit', { group });
   };
 
+  // A field reverted from the history modal; the library already recorded
+  // the revert as a new version, so this only updates the loaded group
+  const handleHistoryReverted = (groupId, fields) => {
+    const restored = { ...fields };
+    if ('series_number' in fields) restored.sequence = fields.series_number;
+    setGroups(prev => prev.map(g => (
+      g.id === groupId ? { ...g, metadata: { ...g.metadata, ...restored } } : g
+    )));
+  };
+
   const handleSaveMetadata = (newMetadata) => {
     const editGroup = modals.data.edit?.group;
     if (!editGroup) return;
 
+    history.begin('edit');
     setGroups(prevGroups =>
       prevGroups.map(group => {
         if (group.id === editGroup.id) {
@@ -331,6 +384,7 @@ export function ScannerPage({ onNavigateToSettings, activeTab, navigateTo, logoS
         return group;
       })
     );
+    history.finish('edit', 'manual');
   };
 
   // Get selected groups for bulk edit
@@ -343,6 +397,7 @@ export function ScannerPage({ onNavigateToSettings, activeTab, navigateTo, logoS
   const handleBulkSave = (updates) => {
     if (selectedGroupIds.size === 0 && !allSelected) return;
 
+    history.begin('bulkEdit');
     setGroups(prevGroups =>
       prevGroups.map(group => {
         if (!allSelected && !selectedGroupIds.has(group.id)) return group;
@@ -422,6 +477,7 @@ export function ScannerPage({ onNavigateToSettings, activeTab, navigateTo, logoS
         };
       })
     );
+    history.finish('bulkEdit', 'manual');
 
   };
 
@@ -1183,16 +1239,14 @@ export function ScannerPage({ onNavigateToSettings, activeTab, navigateTo, logoS
       source: g.id ? 'abs' : 'local',
       title: g.metadata?.title || null,
       author: g.metadata?.author || null,
+      narrator: g.metadata?.narrator || null,
       file_ino: g.files?.[0]?.ino || null,
       file_path: g.files?.[0]?.path || null,
-      abs_base_url: config?.abs_base_url || null,
-      abs_api_token: config?.abs_api_token || null,
-      openai_api_key: config?.openai_api_key || null,
-      use_local_ai: config?.use_local_ai || false,
-      ollama_model: config?.ollama_model || null,
-      ollama_base_url: config?.ollama_base_url || null,
-      use_local_whisper: config?.use_local_whisper || false,
-      whisper_model: config?.whisper_model || 'base',
+      // Final file for the closing-credits pass
+      last_file_ino: g.files?.[g.files.length - 1]?.ino || null,
+      last_file_path: g.files?.[g.files.length - 1]?.path || null,
+      // Connection settings, API keys and concurrency come from the
+      // backend's saved config.
     }));
 
     // Listen for Tauri progress events with stage tracking
@@ -1214,10 +1268,14 @@ export function ScannerPage({ onNavigateToSettings, activeTab, navigateTo, logoS
     } catch (e) { /* not in Tauri */ }
 
     try {
-      const results = await callBackend('batch_extract_audio_intros', { items, force: forceFresh });
+      const results = await callBackend('batch_extract_audio_intros', {
+        items,
+        force: forceFresh,
+      });
 
       const resultMap = new Map(results.map(r => [r.item_id, r]));
       let updatedCount = 0;
+      let conflictCount = 0;
 
       setGroups(prevGroups => prevGroups.map(g => {
         const key = g.id || g.group_name;
@@ -1227,20 +1285,32 @@ export function ScannerPage({ onNavigateToSettings, activeTab, navigateTo, logoS
         // Build merge object based on requested field
         const merge = {};
         const shouldMerge = (f, value) => value && (forceFresh || !g.metadata?.[f]);
+        // Never overwrite a value the audio contradicts; leave it for review
+        const conflicts = Object.values(result.field_status || {}).filter(c => c.status === 'conflicting').length;
+        const agrees = (f) => result.field_status?.[f]?.status !== 'conflicting';
+        if (conflicts > 0) conflictCount++;
 
         if ((field === 'all' || field === 'narrator') && result.narrators.length > 0) {
-          if (forceFresh || !g.metadata?.narrator) {
+          if ((forceFresh && agrees('narrators')) || !g.metadata?.narrator) {
             merge.narrators = result.narrators;
-            merge.narrator = result.narrators[0];
+            merge.narrator = result.narrators.join(', ');
           }
         }
         if ((field === 'all' || field === 'author') && result.authors.length > 0) {
-          if (forceFresh || !g.metadata?.author) {
-            merge.author = result.authors[0];
+          if ((forceFresh && agrees('authors')) || !g.metadata?.author) {
+            merge.author = result.authors.join(', ');
+          }
+        }
+        // Full cast, dramatized or narrator count, as a DNA production tag
+        if ((field === 'all' || field === 'narrator') && result.production) {
+          const tags = g.metadata?.tags || [];
+          const hasProduction = tags.some(t => t.startsWith('dna:production:'));
+          if (forceFresh || !hasProduction) {
+            merge.tags = [...tags.filter(t => !t.startsWith('dna:production:')), `dna:production:${result.production}`];
           }
         }
         if ((field === 'all' || field === 'title') && result.title) {
-          if (forceFresh || !g.metadata?.title || g.metadata?.title === 'Untitled') {
+          if ((forceFresh && agrees('title')) || !g.metadata?.title || g.metadata?.title === 'Untitled') {
             merge.title = result.title;
           }
           if (result.subtitle && (forceFresh || !g.metadata?.subtitle)) {
@@ -1253,6 +1323,10 @@ export function ScannerPage({ onNavigateToSettings, activeTab, navigateTo, logoS
             merge.publisher = pub;
           }
         }
+        // Copyright year, usually from the closing credits
+        if (field === 'all' && result.copyright_year && (forceFresh || !g.metadata?.year)) {
+          merge.year = result.copyright_year;
+        }
         // Language from Whisper detection
         if ((field === 'all' || field === 'language') && result.language) {
           if (forceFresh || !g.metadata?.language) {
@@ -1271,6 +1345,16 @@ export function ScannerPage({ onNavigateToSettings, activeTab, navigateTo, logoS
       }));
 
       toast.success(`Check ${label}`, `Updated ${updatedCount} of ${needsProcessing.length} books from audio`);
+      if (conflictCount > 0) {
+        toast.warning(`Check ${label}: ${conflictCount} to review`, 'The audio disagrees with existing metadata; those fields were left unchanged');
+      }
+
+      // Per-book failures come back on the result instead of failing the batch
+      const failed = results.filter(r => r.error);
+      if (failed.length > 0) {
+        const first = failed[0].error;
+        toast.warning(`Check ${label}: ${failed.length} failed`, [first.message, first.hint].filter(Boolean).join('. '));
+      }
     } catch (error) {
       toast.error(`Check ${label}`, String(error));
     } finally {
@@ -3036,16 +3120,21 @@ export function ScannerPage({ onNavigateToSettings, activeTab, navigateTo, logoS
           validationResults={validationResults}
           hasAbsConnection={!!(config?.abs_base_url && config?.abs_api_token)}
           onNavigateToSettings={onNavigateToSettings}
+          libraryTotal={libraryTotal}
+          onLoadMore={() => loadMoreBooks().catch(error => toast.error('Load more', String(error)))}
         />
 
         <MetadataPanel
           group={selectedGroup}
           onEdit={handleEditMetadata}
+          onShowHistory={(group) => modals.open('history', { group })}
           onInlineEdit={(groupId, field, value) => {
+            history.begin('inlineEdit');
             setGroups(prev => prev.map(g => {
               if (g.id !== groupId) return g;
               return { ...g, metadata: { ...g.metadata, [field]: value } };
             }));
+            history.finish('inlineEdit', 'manual');
           }}
           validationData={selectedGroup ? validationResults[selectedGroup.id] : null}
           onFixIssue={handleFixSingleIssue}
@@ -3189,6 +3278,15 @@ export function ScannerPage({ onNavigateToSettings, activeTab, navigateTo, logoS
       )}
 
       {/* Modals */}
+      {modals.isOpen('history') && modals.data.history?.group && (
+        <HistoryModal
+          isOpen={modals.isOpen('history')}
+          onClose={() => modals.close('history')}
+          group={modals.data.history.group}
+          onReverted={handleHistoryReverted}
+        />
+      )}
+
       {modals.isOpen('edit') && modals.data.edit?.group && (
         <EditMetadataModal
           isOpen={modals.isOpen('edit')}
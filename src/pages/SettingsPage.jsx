@@ -165,6 +165,7 @@ export function SettingsPage({ activeTab, navigateTo, logoSvg, onOpenWizard }) {
   const [whisperDownloading, setWhisperDownloading] = useState(false);
   const [whisperDownloadProgress, setWhisperDownloadProgress] = useState(null);
   const [whisperDiskUsage, setWhisperDiskUsage] = useState(0);
+  const [transcriptCache, setTranscriptCache] = useState(null);
 
   // Auto-fetch libraries when URL + token are both set
   useEffect(() => {
@@ -263,6 +264,31 @@ export function SettingsPage({ activeTab, navigateTo, logoSvg, onOpenWizard }) {
     loadWhisperState();
   }, []);
 
+  // Load transcript cache summary
+  const loadTranscriptCache = async () => {
+    try {
+      const entries = await callBackend('transcript_cache_list');
+      const bytes = await callBackend('transcript_cache_get_disk_usage');
+      setTranscriptCache({ count: entries.length, legacy: entries.filter(e => e.legacy).length, bytes });
+    } catch (e) { /* cache commands not available */ }
+  };
+  useEffect(() => {
+    if (isTauri()) loadTranscriptCache();
+  }, []);
+
+  const handlePurgeTranscriptCache = async (legacyOnly) => {
+    try {
+      const keys = legacyOnly
+        ? (await callBackend('transcript_cache_list')).filter(e => e.legacy).map(e => e.key)
+        : null;
+      const { removed, freed_bytes } = await callBackend('transcript_cache_purge', { keys, olderThanDays: null });
+      toast.info(`Removed ${removed} cached transcripts (${formatBytes(freed_bytes)})`);
+    } catch (e) {
+      toast.error('Clear cache failed', String(e));
+    }
+    loadTranscriptCache();
+  };
+
   // Listen for whisper install/download progress events
   useEffect(() => {
     if (!isTauri()) return;
@@ -325,12 +351,32 @@ export function SettingsPage({ activeTab, navigateTo, logoSvg, onOpenWizard }) {
     }
   };
 
+  // Server mode keeps the model loaded between clips instead of starting
+  // whisper-cpp for each one
+  const handleToggleWhisperServer = async () => {
+    try {
+      if (localConfig.whisper_server) {
+        await callBackend('whisper_server_stop');
+        toast.info('Whisper server stopped');
+      } else {
+        const message = await callBackend('whisper_server_start', { modelId: localConfig.whisper_model || null });
+        toast.success(message);
+      }
+      const newConfig = { ...localConfig, whisper_server: !localConfig.whisper_server };
+      setLocalConfig(newConfig);
+      await saveConfig(newConfig);
+      setWhisperStatus(await callBackend('whisper_local_get_status'));
+    } catch (err) {
+      toast.error(`Failed: ${err.message || err}`);
+    }
+  };
+
   const handleUninstallWhisper = async () => {
     try {
       await callBackend('whisper_local_uninstall');
-      setWhisperStatus({ installed: false, binary_path: null, models: [], active_model: null });
+      setWhisperStatus({ installed: false, binary_path: null, models: [], active_model: null, server_installed: false });
       setWhisperDiskUsage(0);
-      setLocalConfig(prev => ({ ...prev, use_local_whisper: false, whisper_model: null }));
+      setLocalConfig(prev => ({ ...prev, use_local_whisper: false, whisper_model: null, whisper_server: false }));
       toast.info('Local Whisper removed');
     } catch (e) {
       toast.error('Uninstall failed', String(e));
@@ -1014,6 +1060,25 @@ export function SettingsPage({ activeTab, navigateTo, logoSvg, onOpenWizard }) {
                       </button>
                     </div>
 
+                    {whisperStatus.server_installed && (
+                      <div className="flex items-center justify-between">
+                        <div>
+                          <span className="text-sm text-gray-400">Keep Model Loaded</span>
+                          <p className="text-xs text-gray-600">
+                            {whisperStatus.active_model
+                              ? `whisper-server running ${whisperStatus.active_model}`
+                              : 'Runs whisper-server instead of a process per clip'}
+                          </p>
+                        </div>
+                        <button
+                          onClick={handleToggleWhisperServer}
+                          className={`relative w-10 h-5 rounded-full transition-colors ${localConfig.whisper_server ? 'bg-violet-600' : 'bg-neutral-700'}`}
+                        >
+                          <div className={`absolute top-0.5 w-4 h-4 rounded-full bg-white transition-transform ${localConfig.whisper_server ? 'translate-x-5' : 'translate-x-0.5'}`} />
+                        </button>
+                      </div>
+                    )}
+
                     {/* Downloaded models */}
                     {whisperStatus.models?.length > 0 && (
                       <div>
@@ -1113,6 +1178,53 @@ export function SettingsPage({ activeTab, navigateTo, logoSvg, onOpenWizard }) {
               </div>
             )}
 
+            {/* Cloud Transcription - OpenAI or any compatible server */}
+            {isTauri() && !localConfig.use_local_whisper && (
+              <div className="bg-neutral-900/50 rounded-xl p-6 space-y-4">
+                <div className="flex items-center gap-2">
+                  <Mic className="w-4 h-4 text-gray-400" />
+                  <h3 className="text-lg font-semibold text-white">Cloud Transcription</h3>
+                </div>
+                <p className="text-sm text-gray-400">
+                  Leave the server URL empty to use OpenAI with the key above, or point it at a self-hosted
+                  faster-whisper or whisper.cpp server with an OpenAI-compatible API.
+                </p>
+                <Input
+                  label="Server URL"
+                  value={localConfig.transcription_base_url || ''}
+                  onChange={(v) => setLocalConfig({ ...localConfig, transcription_base_url: v || null })}
+                  placeholder="https://api.openai.com/v1"
+                />
+                <div className="grid grid-cols-2 gap-4">
+                  <Input
+                    label="Model"
+                    value={localConfig.transcription_model || ''}
+                    onChange={(v) => setLocalConfig({ ...localConfig, transcription_model: v || null })}
+                    placeholder="whisper-1"
+                  />
+                  <div>
+                    <label className="block text-sm text-gray-500 mb-1.5">Response Format</label>
+                    <select
+                      value={localConfig.transcription_response_format || 'verbose_json'}
+                      onChange={(e) => setLocalConfig({ ...localConfig, transcription_response_format: e.target.value })}
+                      className="w-full px-3 py-2 bg-neutral-800 border border-neutral-700 rounded-lg text-sm text-white focus:outline-none cursor-pointer"
+                    >
+                      <option value="verbose_json">verbose_json (timestamps)</option>
+                      <option value="json">json</option>
+                      <option value="text">text</option>
+                    </select>
+                  </div>
+                </div>
+                <Input
+                  label="Server API Key"
+                  type="password"
+                  value={localConfig.transcription_api_key}
+                  onChange={(v) => setLocalConfig({ ...localConfig, transcription_api_key: v })}
+                  placeholder="Optional for self-hosted servers"
+                />
+              </div>
+            )}
+
           </div>
         </div>
 
@@ -1127,6 +1239,31 @@ export function SettingsPage({ activeTab, navigateTo, logoSvg, onOpenWizard }) {
                   label="Enforce approved genres"
                 />
                 <p className="text-sm text-gray-400">When enabled, AI genre suggestions are filtered to the approved list only. Disable to allow free-form genres.</p>
+                {transcriptCache && (
+                  <div className="flex items-center justify-between pt-2 border-t border-neutral-800">
+                    <p className="text-sm text-gray-400">
+                      Transcript cache: {transcriptCache.count} books, {formatBytes(transcriptCache.bytes)}
+                    </p>
+                    <div className="flex gap-3">
+                      {transcriptCache.legacy > 0 && (
+                        <button
+                          onClick={() => handlePurgeTranscriptCache(true)}
+                          className="text-xs text-gray-500 hover:text-white transition-colors"
+                        >
+                          Remove {transcriptCache.legacy} outdated
+                        </button>
+                      )}
+                      {transcriptCache.count > 0 && (
+                        <button
+                          onClick={() => handlePurgeTranscriptCache(false)}
+                          className="text-xs text-red-500/60 hover:text-red-400 transition-colors"
+                        >
+                          Clear
+                        </button>
+                      )}
+                    </div>
+                  </div>
+                )}
               </div>
             </div>
 
@@ -1281,12 +1418,14 @@ export function SettingsPage({ activeTab, navigateTo, logoSvg, onOpenWizard }) {
             abs_api_token: '',
             openai_api_key: null,
             anthropic_api_key: null,
+            transcription_api_key: null,
           }));
           saveConfig({
             ...localConfig,
             abs_api_token: '',
             openai_api_key: null,
             anthropic_api_key: null,
+            transcription_api_key: null,
           });
           setConfirmClearKeys(false);
           toast.success('Keys Cleared', 'All API keys and tokens have been removed from browser storage.');